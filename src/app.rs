@@ -0,0 +1,373 @@
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr;
+use std::sync::Arc;
+
+use browser::Browser;
+use capture::{self, on_render_process_message};
+use command_line::CommandLine;
+use process_message::{ProcessId, ProcessMessage};
+use rc::{CefRc, RefCounted, release_base};
+use scheme::SchemeRegistrar;
+use string::{CefStr, CefString};
+use sys::*;
+
+/// The process-wide handlers, called in the browser process as well as in the subprocesses.
+///
+/// Every method does nothing by default, so only the needed hooks have to be implemented.
+pub trait App: Send + Sync {
+    /// Change the command line before CEF and Chromium parse it. `process_type` is empty in the browser
+    /// process.
+    fn on_before_command_line_processing(&self, _process_type: &str, _command_line: &CommandLine) {
+    }
+
+    /// Must register the same schemes in every process.
+    fn on_register_custom_schemes(&self, _registrar: &SchemeRegistrar) {
+    }
+
+    fn resource_bundle_handler(&self) -> Option<Arc<dyn ResourceBundleHandler>> {
+        None
+    }
+
+    fn browser_process_handler(&self) -> Option<Arc<dyn BrowserProcessHandler>> {
+        None
+    }
+
+    fn render_process_handler(&self) -> Option<Arc<dyn RenderProcessHandler>> {
+        None
+    }
+}
+
+impl<A: App + ?Sized> App for Arc<A> {
+    fn on_before_command_line_processing(&self, process_type: &str, command_line: &CommandLine) {
+        (**self).on_before_command_line_processing(process_type, command_line)
+    }
+
+    fn on_register_custom_schemes(&self, registrar: &SchemeRegistrar) {
+        (**self).on_register_custom_schemes(registrar)
+    }
+
+    fn resource_bundle_handler(&self) -> Option<Arc<dyn ResourceBundleHandler>> {
+        (**self).resource_bundle_handler()
+    }
+
+    fn browser_process_handler(&self) -> Option<Arc<dyn BrowserProcessHandler>> {
+        (**self).browser_process_handler()
+    }
+
+    fn render_process_handler(&self) -> Option<Arc<dyn RenderProcessHandler>> {
+        (**self).render_process_handler()
+    }
+}
+
+/// Called in the browser process.
+pub trait BrowserProcessHandler: Send + Sync {
+    /// Called on the UI thread once CEF is initialized, to create the first browsers.
+    fn on_context_initialized(&self) {
+    }
+
+    /// Change the command line of a subprocess before it is launched.
+    fn on_before_child_process_launch(&self, _command_line: &CommandLine) {
+    }
+
+    /// Only called with `external_message_pump`: `cef_do_message_loop_work` should be called in `delay_ms`, or
+    /// right away if it is 0 or less.
+    fn on_schedule_message_pump_work(&self, _delay_ms: i64) {
+    }
+}
+
+/// Called in the render processes.
+pub trait RenderProcessHandler: Send + Sync {
+    fn on_web_kit_initialized(&self) {
+    }
+
+    fn on_browser_created(&self, _browser: &Browser) {
+    }
+
+    fn on_browser_destroyed(&self, _browser: &Browser) {
+    }
+
+    /// Returns true if the message was handled. The messages named `cef-capture.*` are answered by the bindings
+    /// for `Browser::capture`, and are not given to this method.
+    fn on_process_message_received(&self, _browser: &Browser, _source_process: ProcessId,
+        _message: &ProcessMessage) -> bool
+    {
+        false
+    }
+}
+
+/// Replaces the strings and resources of the locale and pak files.
+pub trait ResourceBundleHandler: Send + Sync {
+    /// Returns `None` to use the string of the locale file.
+    fn localized_string(&self, _string_id: i32) -> Option<String> {
+        None
+    }
+
+    /// Returns `None` to use the resource of the pak file. The data is not copied by CEF.
+    fn data_resource(&self, _resource_id: i32) -> Option<&'static [u8]> {
+        None
+    }
+}
+
+/// Create a `cef_app_t` calling `app`, whose reference is owned by the caller.
+///
+/// Each call creates a new object, so pass an `Arc` to share the same `App` between `cef_execute_process` and
+/// `cef_initialize`.
+pub fn wrap_app<A: App + 'static>(app: A) -> *mut cef_app_t {
+    let mut raw: cef_app_t = unsafe { mem::zeroed() };
+    raw.on_before_command_line_processing = Some(on_before_command_line_processing::<A>);
+    raw.on_register_custom_schemes = Some(on_register_custom_schemes::<A>);
+    raw.get_resource_bundle_handler = Some(get_resource_bundle_handler::<A>);
+    raw.get_browser_process_handler = Some(get_browser_process_handler::<A>);
+    raw.get_render_process_handler = Some(get_render_process_handler::<A>);
+    RefCounted::wrap(raw, app)
+}
+
+extern "C" fn on_before_command_line_processing<A: App>(self_: *mut cef_app_t, process_type: *const cef_string_t,
+    command_line: *mut cef_command_line_t)
+{
+    let app = unsafe { RefCounted::<_, A>::value(self_) };
+    let process_type = unsafe { CefStr::from_ptr(process_type) }.to_string();
+    if let Some(command_line) = unsafe { CefRc::<CommandLine>::from_raw(command_line) } {
+        app.on_before_command_line_processing(&process_type, &command_line);
+    }
+}
+
+extern "C" fn on_register_custom_schemes<A: App>(self_: *mut cef_app_t, registrar: *mut cef_scheme_registrar_t) {
+    let app = unsafe { RefCounted::<_, A>::value(self_) };
+    if !registrar.is_null() {
+        app.on_register_custom_schemes(unsafe { SchemeRegistrar::from_raw(registrar) });
+    }
+}
+
+extern "C" fn get_resource_bundle_handler<A: App>(self_: *mut cef_app_t) -> *mut cef_resource_bundle_handler_t {
+    let app = unsafe { RefCounted::<_, A>::value(self_) };
+    match app.resource_bundle_handler() {
+        Some(handler) => wrap_resource_bundle_handler(handler),
+        None => ptr::null_mut(),
+    }
+}
+
+extern "C" fn get_browser_process_handler<A: App>(self_: *mut cef_app_t) -> *mut cef_browser_process_handler_t {
+    let app = unsafe { RefCounted::<_, A>::value(self_) };
+    match app.browser_process_handler() {
+        Some(handler) => wrap_browser_process_handler(handler),
+        None => ptr::null_mut(),
+    }
+}
+
+// There is always a handler, which answers the process messages of `Browser::capture`.
+extern "C" fn get_render_process_handler<A: App>(self_: *mut cef_app_t) -> *mut cef_render_process_handler_t {
+    let app = unsafe { RefCounted::<_, A>::value(self_) };
+    wrap_render_process_handler(app.render_process_handler())
+}
+
+type BrowserProcessHandlerRc = RefCounted<cef_browser_process_handler_t, Arc<dyn BrowserProcessHandler>>;
+
+fn wrap_browser_process_handler(handler: Arc<dyn BrowserProcessHandler>) -> *mut cef_browser_process_handler_t {
+    extern "C" fn on_context_initialized(self_: *mut cef_browser_process_handler_t) {
+        let handler = unsafe { BrowserProcessHandlerRc::value(self_) };
+        handler.on_context_initialized();
+    }
+
+    extern "C" fn on_before_child_process_launch(self_: *mut cef_browser_process_handler_t,
+        command_line: *mut cef_command_line_t)
+    {
+        let handler = unsafe { BrowserProcessHandlerRc::value(self_) };
+        if let Some(command_line) = unsafe { CefRc::<CommandLine>::from_raw(command_line) } {
+            handler.on_before_child_process_launch(&command_line);
+        }
+    }
+
+    extern "C" fn on_schedule_message_pump_work(self_: *mut cef_browser_process_handler_t, delay_ms: i64) {
+        let handler = unsafe { BrowserProcessHandlerRc::value(self_) };
+        handler.on_schedule_message_pump_work(delay_ms);
+    }
+
+    let mut raw: cef_browser_process_handler_t = unsafe { mem::zeroed() };
+    raw.on_context_initialized = Some(on_context_initialized);
+    raw.on_before_child_process_launch = Some(on_before_child_process_launch);
+    raw.on_schedule_message_pump_work = Some(on_schedule_message_pump_work);
+    RefCounted::wrap(raw, handler)
+}
+
+type RenderProcessHandlerRc = RefCounted<cef_render_process_handler_t, Option<Arc<dyn RenderProcessHandler>>>;
+
+fn wrap_render_process_handler(handler: Option<Arc<dyn RenderProcessHandler>>)
+    -> *mut cef_render_process_handler_t
+{
+    extern "C" fn on_web_kit_initialized(self_: *mut cef_render_process_handler_t) {
+        if let Some(ref handler) = *unsafe { RenderProcessHandlerRc::value(self_) } {
+            handler.on_web_kit_initialized();
+        }
+    }
+
+    extern "C" fn on_browser_created(self_: *mut cef_render_process_handler_t, browser: *mut cef_browser_t) {
+        let handler = unsafe { RenderProcessHandlerRc::value(self_) };
+        if let (Some(handler), Some(browser)) = (handler, unsafe { CefRc::<Browser>::from_raw(browser) }) {
+            handler.on_browser_created(&browser);
+        }
+    }
+
+    extern "C" fn on_browser_destroyed(self_: *mut cef_render_process_handler_t, browser: *mut cef_browser_t) {
+        let handler = unsafe { RenderProcessHandlerRc::value(self_) };
+        if let (Some(handler), Some(browser)) = (handler, unsafe { CefRc::<Browser>::from_raw(browser) }) {
+            handler.on_browser_destroyed(&browser);
+        }
+    }
+
+    extern "C" fn on_process_message_received(self_: *mut cef_render_process_handler_t,
+        browser: *mut cef_browser_t, source_process: cef_process_id_t, message: *mut cef_process_message_t) -> i32
+    {
+        let handler = unsafe { RenderProcessHandlerRc::value(self_) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        if message.is_null() {
+            return 0;
+        }
+        let message = unsafe {
+            let copy = ProcessMessage::from_raw(message);
+            release_base(message as *mut cef_base_ref_counted_t);
+            copy
+        };
+        let browser = match browser {
+            Some(browser) => browser,
+            None => return 0,
+        };
+        if message.name.starts_with(capture::MESSAGE_PREFIX) {
+            on_render_process_message(&browser, &message) as i32
+        }
+        else {
+            match *handler {
+                Some(ref handler) => {
+                    handler.on_process_message_received(&browser, ProcessId::from_raw(source_process), &message) as i32
+                },
+                None => 0,
+            }
+        }
+    }
+
+    let mut raw: cef_render_process_handler_t = unsafe { mem::zeroed() };
+    raw.on_web_kit_initialized = Some(on_web_kit_initialized);
+    raw.on_browser_created = Some(on_browser_created);
+    raw.on_browser_destroyed = Some(on_browser_destroyed);
+    raw.on_process_message_received = Some(on_process_message_received);
+    RefCounted::wrap(raw, handler)
+}
+
+type ResourceBundleHandlerRc = RefCounted<cef_resource_bundle_handler_t, Arc<dyn ResourceBundleHandler>>;
+
+fn wrap_resource_bundle_handler(handler: Arc<dyn ResourceBundleHandler>) -> *mut cef_resource_bundle_handler_t {
+    extern "C" fn get_localized_string(self_: *mut cef_resource_bundle_handler_t, string_id: i32,
+        string: *mut cef_string_t) -> i32
+    {
+        let handler = unsafe { ResourceBundleHandlerRc::value(self_) };
+        match handler.localized_string(string_id) {
+            Some(value) => {
+                unsafe { CefString::new(&value).copy_to(string) };
+                1
+            },
+            None => 0,
+        }
+    }
+
+    extern "C" fn get_data_resource(self_: *mut cef_resource_bundle_handler_t, resource_id: i32,
+        data: *mut *mut c_void, data_size: *mut usize) -> i32
+    {
+        let handler = unsafe { ResourceBundleHandlerRc::value(self_) };
+        match handler.data_resource(resource_id) {
+            Some(resource) => {
+                unsafe {
+                    *data = resource.as_ptr() as *mut c_void;
+                    *data_size = resource.len();
+                }
+                1
+            },
+            None => 0,
+        }
+    }
+
+    let mut raw: cef_resource_bundle_handler_t = unsafe { mem::zeroed() };
+    raw.get_localized_string = Some(get_localized_string);
+    raw.get_data_resource = Some(get_data_resource);
+    RefCounted::wrap(raw, handler)
+}
+
+#[cfg(test)]
+mod tests {
+    use context::{Cef, MainArgs};
+    use mock::{self, Recorder, TestClient};
+    use scheme::SchemeOptions;
+    use settings::Settings;
+    use super::*;
+
+    struct TestApp(Arc<Recorder>);
+
+    impl App for TestApp {
+        fn on_before_command_line_processing(&self, process_type: &str, command_line: &CommandLine) {
+            command_line.append_switch("disable-gpu");
+            self.0.push(format!("command line {:?} {}", process_type, command_line.has_switch("disable-gpu")));
+        }
+
+        fn on_register_custom_schemes(&self, registrar: &SchemeRegistrar) {
+            let added = registrar.add_custom_scheme("app", SchemeOptions::default());
+            self.0.push(format!("scheme {}", added));
+        }
+
+        fn browser_process_handler(&self) -> Option<Arc<dyn BrowserProcessHandler>> {
+            Some(self.0.clone())
+        }
+
+        fn render_process_handler(&self) -> Option<Arc<dyn RenderProcessHandler>> {
+            Some(self.0.clone())
+        }
+    }
+
+    impl BrowserProcessHandler for Recorder {
+        fn on_context_initialized(&self) {
+            self.push("context initialized");
+        }
+    }
+
+    impl RenderProcessHandler for Recorder {
+        fn on_process_message_received(&self, _browser: &Browser, source_process: ProcessId,
+            message: &ProcessMessage) -> bool
+        {
+            self.push(format!("message {:?} {}", source_process, message.name));
+            message.name == "handled"
+        }
+    }
+
+    #[test]
+    fn app_callbacks() {
+        let _guard = mock::lock();
+        let events = Arc::new(Recorder::default());
+        let args = MainArgs::new(vec!["test"]).unwrap();
+        let cef = Cef::start(args, &Settings::default(), TestApp(events.clone())).unwrap();
+        mock::run_pending_tasks();
+        assert_eq!(events.events(), ["command line \"\" true", "scheme true", "context initialized"]);
+        assert!(mock::calls().contains(&"SchemeRegistrar::add_custom_scheme app".to_string()));
+        drop(cef);
+        assert_eq!(Arc::strong_count(&events), 1);
+        assert_eq!(mock::live_objects(), 0);
+    }
+    #[test]
+    fn render_process_messages() {
+        let _guard = mock::lock();
+        let events = Arc::new(Recorder::default());
+        let args = MainArgs::new(vec!["test"]).unwrap();
+        let cef = Cef::start(args, &Settings::default(), TestApp(events.clone())).unwrap();
+        mock::run_pending_tasks();
+        let browser = mock::create_browser(TestClient::new());
+        let id = browser.identifier();
+        assert!(mock::render_process_message(id, &ProcessMessage::new("handled")));
+        assert!(!mock::render_process_message(id, &ProcessMessage::new("ignored")));
+        // The messages of the captures are answered without the handler of the application.
+        mock::clear_calls();
+        assert!(mock::render_process_message(id, &ProcessMessage::new("cef-capture.measure")));
+        assert!(mock::calls().iter().any(|call| call.contains("cef-capture.height")));
+        assert_eq!(events.events()[3..], ["message Browser handled", "message Browser ignored"]);
+        drop(browser);
+        drop(cef);
+        assert_eq!(mock::live_objects(), 0);
+    }
+}
@@ -0,0 +1,331 @@
+use capture::{Capture, CaptureOptions};
+use find::{FindResults, next_find_id};
+use navigation::Navigation;
+use process_message::{ProcessId, ProcessMessage};
+use rc::{CefObject, CefRc};
+use render::PaintElementType;
+use string::CefString;
+use sys::{
+    cef_browser_host_t,
+    cef_browser_t,
+    cef_frame_t,
+    cef_window_handle_t,
+};
+use v8::V8Context;
+
+/// A browser, which is a window showing a tree of frames.
+#[repr(transparent)]
+pub struct Browser(cef_browser_t);
+
+cef_object!(Browser, cef_browser_t);
+
+impl Browser {
+    /// Create a windowless browser loading `url`, and capture the page once it is painted. The windowless
+    /// rendering must be enabled in the `Settings`; it needs no GPU, but on Linux CEF may still need an X
+    /// display, like the one of Xvfb.
+    pub fn capture(url: &str, options: CaptureOptions) -> Capture {
+        Capture::start(url, options)
+    }
+
+    pub fn host(&self) -> CefRc<BrowserHost> {
+        unsafe { CefRc::from_raw(call!(&self.0, get_host())) }
+            .expect("browser host")
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        call!(&self.0, can_go_back()) != 0
+    }
+
+    pub fn go_back(&self) {
+        call!(&self.0, go_back())
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        call!(&self.0, can_go_forward()) != 0
+    }
+
+    pub fn go_forward(&self) {
+        call!(&self.0, go_forward())
+    }
+
+    pub fn is_loading(&self) -> bool {
+        call!(&self.0, is_loading()) != 0
+    }
+
+    pub fn reload(&self) {
+        call!(&self.0, reload())
+    }
+
+    pub fn reload_ignore_cache(&self) {
+        call!(&self.0, reload_ignore_cache())
+    }
+
+    pub fn stop_load(&self) {
+        call!(&self.0, stop_load())
+    }
+
+    /// The identifier is unique among the browsers of the process.
+    pub fn identifier(&self) -> i32 {
+        call!(&self.0, get_identifier())
+    }
+
+    pub fn is_same(&self, other: &Browser) -> bool {
+        call!(&self.0, is_same(other.to_raw())) != 0
+    }
+
+    pub fn is_popup(&self) -> bool {
+        call!(&self.0, is_popup()) != 0
+    }
+
+    pub fn has_document(&self) -> bool {
+        call!(&self.0, has_document()) != 0
+    }
+
+    /// Load `url` in the main frame, and return a future resolved when it is loaded.
+    pub fn load_url(&self, url: &str) -> Navigation {
+        Navigation::start(self, url)
+    }
+
+    pub fn main_frame(&self) -> Option<CefRc<Frame>> {
+        unsafe { CefRc::from_raw(call!(&self.0, get_main_frame())) }
+    }
+
+    pub fn focused_frame(&self) -> Option<CefRc<Frame>> {
+        unsafe { CefRc::from_raw(call!(&self.0, get_focused_frame())) }
+    }
+
+    pub fn frame_by_identifier(&self, identifier: i64) -> Option<CefRc<Frame>> {
+        unsafe { CefRc::from_raw(call!(&self.0, get_frame_byident(identifier))) }
+    }
+
+    pub fn frame(&self, name: &str) -> Option<CefRc<Frame>> {
+        let name = CefString::new(name);
+        unsafe { CefRc::from_raw(call!(&self.0, get_frame(name.as_raw()))) }
+    }
+
+    pub fn frame_count(&self) -> usize {
+        call!(&self.0, get_frame_count())
+    }
+
+    pub fn frame_identifiers(&self) -> Vec<i64> {
+        let mut count = self.frame_count();
+        let mut identifiers = vec![0; count];
+        call!(&self.0, get_frame_identifiers(&mut count, identifiers.as_mut_ptr()));
+        identifiers.truncate(count);
+        identifiers
+    }
+
+    pub fn send_process_message(&self, target_process: ProcessId, message: &ProcessMessage) -> bool {
+        call!(&self.0, send_process_message(target_process.to_raw(), message.to_raw())) != 0
+    }
+}
+
+/// The browser-process side of a browser.
+#[repr(transparent)]
+pub struct BrowserHost(cef_browser_host_t);
+
+cef_object!(BrowserHost, cef_browser_host_t);
+
+impl BrowserHost {
+    pub fn browser(&self) -> CefRc<Browser> {
+        unsafe { CefRc::from_raw(call!(&self.0, get_browser())) }
+            .expect("host browser")
+    }
+
+    /// Without `force_close`, the page can cancel the closing in its `onbeforeunload` event.
+    pub fn close_browser(&self, force_close: bool) {
+        call!(&self.0, close_browser(force_close as i32))
+    }
+
+    /// Returns whether the browser can be closed right away.
+    pub fn try_close_browser(&self) -> bool {
+        call!(&self.0, try_close_browser()) != 0
+    }
+
+    pub fn set_focus(&self, focus: bool) {
+        call!(&self.0, set_focus(focus as i32))
+    }
+
+    pub fn window_handle(&self) -> cef_window_handle_t {
+        call!(&self.0, get_window_handle())
+    }
+
+    pub fn opener_window_handle(&self) -> cef_window_handle_t {
+        call!(&self.0, get_opener_window_handle())
+    }
+
+    /// Whether the browser is hosted in a `BrowserView`.
+    pub fn has_view(&self) -> bool {
+        call!(&self.0, has_view()) != 0
+    }
+
+    pub fn zoom_level(&self) -> f64 {
+        call!(&self.0, get_zoom_level())
+    }
+
+    pub fn set_zoom_level(&self, zoom_level: f64) {
+        call!(&self.0, set_zoom_level(zoom_level))
+    }
+
+    pub fn start_download(&self, url: &str) {
+        let url = CefString::new(url);
+        call!(&self.0, start_download(url.as_raw()))
+    }
+
+    pub fn print(&self) {
+        call!(&self.0, print())
+    }
+
+    /// Search `text` in the page, and return the identifier of the search, given to the `FindHandler` with its
+    /// results. With `find_next`, the next match of the previous search of the same text is selected.
+    pub fn find(&self, text: &str, forward: bool, match_case: bool, find_next: bool) -> i32 {
+        let identifier = next_find_id();
+        let text = CefString::new(text);
+        call!(&self.0, find(identifier, text.as_raw(), forward as i32, match_case as i32, find_next as i32));
+        identifier
+    }
+
+    /// Stop the search, and unselect the active match with `clear_selection`.
+    pub fn stop_finding(&self, clear_selection: bool) {
+        call!(&self.0, stop_finding(clear_selection as i32))
+    }
+
+    /// A stream of the results of the searches received from now on.
+    pub fn find_results(&self) -> FindResults {
+        FindResults::new(&self.browser())
+    }
+
+    pub fn close_dev_tools(&self) {
+        call!(&self.0, close_dev_tools())
+    }
+
+    pub fn has_dev_tools(&self) -> bool {
+        call!(&self.0, has_dev_tools()) != 0
+    }
+
+    pub fn is_window_rendering_disabled(&self) -> bool {
+        call!(&self.0, is_window_rendering_disabled()) != 0
+    }
+
+    pub fn was_resized(&self) {
+        call!(&self.0, was_resized())
+    }
+
+    /// Paint the view or the popup widget of a windowless browser again, with the `RenderHandler`.
+    pub fn invalidate(&self, element_type: PaintElementType) {
+        call!(&self.0, invalidate(element_type.to_raw()))
+    }
+
+    pub fn was_hidden(&self, hidden: bool) {
+        call!(&self.0, was_hidden(hidden as i32))
+    }
+
+    pub fn notify_screen_info_changed(&self) {
+        call!(&self.0, notify_screen_info_changed())
+    }
+
+    pub fn windowless_frame_rate(&self) -> i32 {
+        call!(&self.0, get_windowless_frame_rate())
+    }
+
+    pub fn set_windowless_frame_rate(&self, frame_rate: i32) {
+        call!(&self.0, set_windowless_frame_rate(frame_rate))
+    }
+}
+
+/// A frame of a browser: the main frame or an iframe.
+#[repr(transparent)]
+pub struct Frame(cef_frame_t);
+
+cef_object!(Frame, cef_frame_t);
+
+impl Frame {
+    pub fn is_valid(&self) -> bool {
+        call!(&self.0, is_valid()) != 0
+    }
+
+    pub fn undo(&self) {
+        call!(&self.0, undo())
+    }
+
+    pub fn redo(&self) {
+        call!(&self.0, redo())
+    }
+
+    pub fn cut(&self) {
+        call!(&self.0, cut())
+    }
+
+    pub fn copy(&self) {
+        call!(&self.0, copy())
+    }
+
+    pub fn paste(&self) {
+        call!(&self.0, paste())
+    }
+
+    pub fn delete(&self) {
+        call!(&self.0, del())
+    }
+
+    pub fn select_all(&self) {
+        call!(&self.0, select_all())
+    }
+
+    pub fn view_source(&self) {
+        call!(&self.0, view_source())
+    }
+
+    pub fn load_url(&self, url: &str) {
+        let url = CefString::new(url);
+        call!(&self.0, load_url(url.as_raw()))
+    }
+
+    /// Load the contents of `string` as if it came from `url`.
+    pub fn load_string(&self, string: &str, url: &str) {
+        let string = CefString::new(string);
+        let url = CefString::new(url);
+        call!(&self.0, load_string(string.as_raw(), url.as_raw()))
+    }
+
+    /// `script_url` and `start_line` are used in the error messages.
+    pub fn execute_java_script(&self, code: &str, script_url: &str, start_line: i32) {
+        let code = CefString::new(code);
+        let script_url = CefString::new(script_url);
+        call!(&self.0, execute_java_script(code.as_raw(), script_url.as_raw(), start_line))
+    }
+
+    pub fn is_main(&self) -> bool {
+        call!(&self.0, is_main()) != 0
+    }
+
+    /// The JavaScript context of the frame, in a render process on its render thread.
+    pub(crate) fn v8_context(&self) -> Option<CefRc<V8Context>> {
+        unsafe { CefRc::from_raw(call!(&self.0, get_v8context())) }
+    }
+
+    pub fn is_focused(&self) -> bool {
+        call!(&self.0, is_focused()) != 0
+    }
+
+    pub fn name(&self) -> String {
+        unsafe { CefString::from_userfree(call!(&self.0, get_name())) }.to_string()
+    }
+
+    pub fn identifier(&self) -> i64 {
+        call!(&self.0, get_identifier())
+    }
+
+    pub fn parent(&self) -> Option<CefRc<Frame>> {
+        unsafe { CefRc::from_raw(call!(&self.0, get_parent())) }
+    }
+
+    pub fn url(&self) -> String {
+        unsafe { CefString::from_userfree(call!(&self.0, get_url())) }.to_string()
+    }
+
+    pub fn browser(&self) -> CefRc<Browser> {
+        unsafe { CefRc::from_raw(call!(&self.0, get_browser())) }
+            .expect("frame browser")
+    }
+}
@@ -0,0 +1,268 @@
+use std::mem;
+use std::ptr;
+
+use settings::{Color, string};
+use string::CefString;
+use sys::{cef_browser_settings_t, cef_state_t, cef_string_utf16_set};
+
+/// The font settings of the browsers. Every font left unset keeps the default of CEF.
+#[derive(Clone, Debug, Default)]
+pub struct Fonts {
+    pub standard_family: Option<String>,
+    pub fixed_family: Option<String>,
+    pub serif_family: Option<String>,
+    pub sans_serif_family: Option<String>,
+    pub cursive_family: Option<String>,
+    pub fantasy_family: Option<String>,
+    pub default_size: Option<u32>,
+    pub default_fixed_size: Option<u32>,
+    pub minimum_size: Option<u32>,
+    pub minimum_logical_size: Option<u32>,
+    /// Allow the web fonts.
+    pub remote_fonts: Option<bool>,
+}
+
+/// The settings of a browser, built by `BrowserSettingsBuilder`.
+///
+/// The raw struct points to the strings owned by this object, which must outlive the creation of the browser.
+pub struct BrowserSettings {
+    raw: cef_browser_settings_t,
+    _strings: Vec<CefString>,
+}
+
+unsafe impl Send for BrowserSettings {}
+
+impl BrowserSettings {
+    pub fn builder() -> BrowserSettingsBuilder {
+        BrowserSettingsBuilder::default()
+    }
+
+    pub fn as_raw(&self) -> *const cef_browser_settings_t {
+        &self.raw
+    }
+
+    /// Copy into a struct owned by CEF, like the settings of a popup, whose strings are replaced by copies that
+    /// CEF frees.
+    ///
+    /// # Safety
+    ///
+    /// `output` must be a valid struct.
+    pub(crate) unsafe fn copy_to(&self, output: *mut cef_browser_settings_t) {
+        let output = &mut *output;
+        let previous = ptr::replace(output, ptr::read(&self.raw));
+        macro_rules! copy_strings {
+            ($($field:ident),*) => {
+                $(
+                    // Setting the string frees the previous one.
+                    let borrowed = mem::replace(&mut output.$field, previous.$field);
+                    cef_string_utf16_set(borrowed.str, borrowed.length, &mut output.$field, 1);
+                )*
+            };
+        }
+        copy_strings!(standard_font_family, fixed_font_family, serif_font_family, sans_serif_font_family,
+            cursive_font_family, fantasy_font_family, default_encoding, accept_language_list);
+    }
+}
+
+impl Default for BrowserSettings {
+    fn default() -> Self {
+        BrowserSettingsBuilder::default().build()
+    }
+}
+
+macro_rules! state_setters {
+    ($($(#[$attr:meta])* $name:ident),* $(,)*) => {
+        $(
+            $(#[$attr])*
+            pub fn $name<S: Into<Option<bool>>>(mut self, state: S) -> Self {
+                self.$name = state.into();
+                self
+            }
+        )*
+    };
+}
+
+/// The features are tri-states: `None` keeps the default of CEF, which can be changed by command-line switches,
+/// while `Some` enables or disables them.
+#[derive(Clone, Debug, Default)]
+pub struct BrowserSettingsBuilder {
+    windowless_frame_rate: Option<u32>,
+    fonts: Fonts,
+    default_encoding: Option<String>,
+    javascript: Option<bool>,
+    javascript_close_windows: Option<bool>,
+    javascript_access_clipboard: Option<bool>,
+    javascript_dom_paste: Option<bool>,
+    plugins: Option<bool>,
+    universal_access_from_file_urls: Option<bool>,
+    file_access_from_file_urls: Option<bool>,
+    web_security: Option<bool>,
+    image_loading: Option<bool>,
+    image_shrink_standalone_to_fit: Option<bool>,
+    text_area_resize: Option<bool>,
+    tab_to_links: Option<bool>,
+    local_storage: Option<bool>,
+    databases: Option<bool>,
+    application_cache: Option<bool>,
+    webgl: Option<bool>,
+    background_color: Option<Color>,
+    accept_language_list: Option<String>,
+}
+
+impl BrowserSettingsBuilder {
+    /// A locked-down browser for kiosks: the pages cannot use the clipboard, close their window, run plugins
+    /// or read other files from file URLs, and the web security cannot be turned off from the command line.
+    pub fn kiosk() -> Self {
+        Self::default()
+            .javascript_close_windows(false)
+            .javascript_access_clipboard(false)
+            .javascript_dom_paste(false)
+            .plugins(false)
+            .universal_access_from_file_urls(false)
+            .file_access_from_file_urls(false)
+            .web_security(true)
+    }
+
+    /// The frame rate of the windowless browsers, from 1 to 60. CEF defaults to 30.
+    pub fn windowless_frame_rate(mut self, frame_rate: u32) -> Self {
+        self.windowless_frame_rate = Some(frame_rate);
+        self
+    }
+
+    pub fn fonts(mut self, fonts: Fonts) -> Self {
+        self.fonts = fonts;
+        self
+    }
+
+    /// Like `"ISO-8859-1"`.
+    pub fn default_encoding(mut self, encoding: &str) -> Self {
+        self.default_encoding = Some(encoding.to_string());
+        self
+    }
+
+    state_setters!(
+        javascript,
+        /// Allow `window.close()` in the windows not opened by JavaScript.
+        javascript_close_windows,
+        javascript_access_clipboard,
+        javascript_dom_paste,
+        plugins,
+        /// Allow the pages loaded from file URLs to access any URL.
+        universal_access_from_file_urls,
+        /// Allow the pages loaded from file URLs to access other file URLs.
+        file_access_from_file_urls,
+        /// Disabling it allows the pages to access any URL, whatever their origin.
+        web_security,
+        image_loading,
+        image_shrink_standalone_to_fit,
+        text_area_resize,
+        tab_to_links,
+        local_storage,
+        databases,
+        application_cache,
+        webgl,
+    );
+
+    /// The color shown before a page is loaded, which defaults to the one of the `Settings`.
+    pub fn background_color(mut self, color: Color) -> Self {
+        self.background_color = Some(color);
+        self
+    }
+
+    /// Like `"en-US,en"`.
+    pub fn accept_language_list(mut self, languages: &str) -> Self {
+        self.accept_language_list = Some(languages.to_string());
+        self
+    }
+
+    pub fn build(&self) -> BrowserSettings {
+        let mut strings = vec![];
+        let mut raw: cef_browser_settings_t = unsafe { mem::zeroed() };
+        raw.size = mem::size_of::<cef_browser_settings_t>();
+        raw.windowless_frame_rate = size(self.windowless_frame_rate);
+        raw.standard_font_family = string(&mut strings, &self.fonts.standard_family);
+        raw.fixed_font_family = string(&mut strings, &self.fonts.fixed_family);
+        raw.serif_font_family = string(&mut strings, &self.fonts.serif_family);
+        raw.sans_serif_font_family = string(&mut strings, &self.fonts.sans_serif_family);
+        raw.cursive_font_family = string(&mut strings, &self.fonts.cursive_family);
+        raw.fantasy_font_family = string(&mut strings, &self.fonts.fantasy_family);
+        raw.default_font_size = size(self.fonts.default_size);
+        raw.default_fixed_font_size = size(self.fonts.default_fixed_size);
+        raw.minimum_font_size = size(self.fonts.minimum_size);
+        raw.minimum_logical_font_size = size(self.fonts.minimum_logical_size);
+        raw.default_encoding = string(&mut strings, &self.default_encoding);
+        raw.remote_fonts = state(self.fonts.remote_fonts);
+        raw.javascript = state(self.javascript);
+        raw.javascript_close_windows = state(self.javascript_close_windows);
+        raw.javascript_access_clipboard = state(self.javascript_access_clipboard);
+        raw.javascript_dom_paste = state(self.javascript_dom_paste);
+        raw.plugins = state(self.plugins);
+        raw.universal_access_from_file_urls = state(self.universal_access_from_file_urls);
+        raw.file_access_from_file_urls = state(self.file_access_from_file_urls);
+        raw.web_security = state(self.web_security);
+        raw.image_loading = state(self.image_loading);
+        raw.image_shrink_standalone_to_fit = state(self.image_shrink_standalone_to_fit);
+        raw.text_area_resize = state(self.text_area_resize);
+        raw.tab_to_links = state(self.tab_to_links);
+        raw.local_storage = state(self.local_storage);
+        raw.databases = state(self.databases);
+        raw.application_cache = state(self.application_cache);
+        raw.webgl = state(self.webgl);
+        raw.background_color = self.background_color.map(Color::to_raw).unwrap_or(0);
+        raw.accept_language_list = string(&mut strings, &self.accept_language_list);
+        BrowserSettings {
+            raw,
+            _strings: strings,
+        }
+    }
+}
+
+fn state(state: Option<bool>) -> cef_state_t {
+    match state {
+        None => cef_state_t::STATE_DEFAULT,
+        Some(true) => cef_state_t::STATE_ENABLED,
+        Some(false) => cef_state_t::STATE_DISABLED,
+    }
+}
+
+fn size(size: Option<u32>) -> i32 {
+    size.map(|size| size as i32).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use string::CefStr;
+    use sys::cef_string_utf16_clear;
+    use super::*;
+
+    #[test]
+    fn kiosk() {
+        let settings = BrowserSettingsBuilder::kiosk()
+            .fonts(Fonts {
+                standard_family: Some("Serif".to_string()),
+                default_size: Some(16),
+                ..Fonts::default()
+            })
+            .build();
+        let raw = unsafe { &*settings.as_raw() };
+        assert!(matches!(raw.javascript, cef_state_t::STATE_DEFAULT));
+        assert!(matches!(raw.plugins, cef_state_t::STATE_DISABLED));
+        assert!(matches!(raw.web_security, cef_state_t::STATE_ENABLED));
+        assert!(unsafe { CefStr::from_ptr(&raw.standard_font_family) } == "Serif");
+        assert_eq!(raw.default_font_size, 16);
+        assert_eq!(raw.minimum_font_size, 0);
+    }
+
+    #[test]
+    fn copy_to_cef() {
+        let settings = BrowserSettings::builder().default_encoding("ISO-8859-1").build();
+        unsafe {
+            let mut output: cef_browser_settings_t = mem::zeroed();
+            settings.copy_to(&mut output);
+            assert_ne!(output.default_encoding.str, (*settings.as_raw()).default_encoding.str);
+            assert!(CefStr::from_ptr(&output.default_encoding) == "ISO-8859-1");
+            assert!(output.default_encoding.dtor.is_some());
+            cef_string_utf16_clear(&mut output.default_encoding);
+        }
+    }
+}
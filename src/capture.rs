@@ -0,0 +1,663 @@
+use std::error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::ptr;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use browser::{Browser, Frame};
+use browser_settings::BrowserSettings;
+use client::{Client, wrap_client};
+use error::NetError;
+use geometry::Rect;
+use image::Image;
+use life_span::{LifeSpanHandler, PopupAction, PopupRequest};
+use load::LoadHandler;
+use navigation::{HttpStatus, LoadError};
+use pending::{self, Pending, PendingState, locked};
+use process_message::{ProcessId, ProcessMessage, Value};
+use rc::{CefObject, CefRc};
+use render::{PaintElementType, RenderHandler, ScreenInfo};
+use string::CefString;
+use sys::*;
+use task::post_delayed_task;
+use window_info::WindowInfo;
+
+// How long the view must not be painted before it is captured.
+const SETTLE_DELAY: Duration = Duration::from_millis(100);
+// The number of settle delays after which the page is captured anyway when it keeps painting an animation, or
+// not captured when it is still not measured or painted with the size of the view.
+const MAX_SETTLE_CHECKS: u32 = 20;
+// The full pages are cut to this height, in pixels, to limit the size of the buffers.
+const MAX_FULL_PAGE_HEIGHT: f32 = 16384.0;
+// How long a capture can take when no other timeout is given.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+// The prefix of the messages between the captures and the render processes, which are not given to the
+// `RenderProcessHandler`.
+pub(crate) const MESSAGE_PREFIX: &str = "cef-capture.";
+// The message asking the render process to measure the page, and its answer with the height of the page, or
+// without argument when the page cannot be measured.
+const MEASURE_MESSAGE: &str = "cef-capture.measure";
+const HEIGHT_MESSAGE: &str = "cef-capture.height";
+const MEASURE_SCRIPT: &str =
+    "Math.ceil(Math.max(document.documentElement.scrollHeight, document.body ? document.body.scrollHeight : 0))";
+
+/// How `Browser::capture` renders a page.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CaptureOptions {
+    /// The width and the height of the view, in view units.
+    pub size: (u32, u32),
+    /// The number of pixels per view unit, like 2.0 to capture the page as on a high DPI screen.
+    pub scale_factor: f32,
+    /// Whether to wait until the main frame is loaded. Otherwise, the page is captured once it stops being
+    /// painted, even if it is still loading.
+    pub wait_for_load: bool,
+    /// Whether to capture the whole height of the page instead of the height of the view. It needs JavaScript
+    /// to measure the page, and the capture fails with `CaptureError::MeasureFailed` if it cannot be measured.
+    pub full_page: bool,
+}
+
+impl Default for CaptureOptions {
+    /// A 1280x720 view, with a scale factor of 1, captured once loaded.
+    fn default() -> Self {
+        CaptureOptions {
+            size: (1280, 720),
+            scale_factor: 1.0,
+            wait_for_load: true,
+            full_page: false,
+        }
+    }
+}
+
+/// Why a page was not captured.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CaptureError {
+    /// CEF did not create the browser, like when the windowless rendering is not enabled in the `Settings`.
+    CreateFailed,
+    Load(LoadError),
+    /// The browser was closed before the page was captured.
+    Closed,
+    /// The page was not captured before the timeout, or was not painted with the size of the view.
+    TimedOut,
+    /// The height of the page could not be measured for `CaptureOptions::full_page`.
+    MeasureFailed,
+    /// A task could not be posted to the UI thread, because CEF is not running.
+    PostTask,
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CaptureError::CreateFailed => write!(formatter, "cannot create a windowless browser"),
+            CaptureError::Load(ref error) => error.fmt(formatter),
+            CaptureError::Closed => write!(formatter, "the browser was closed before the capture"),
+            CaptureError::TimedOut => write!(formatter, "the capture timed out"),
+            CaptureError::MeasureFailed => write!(formatter, "the height of the page cannot be measured"),
+            CaptureError::PostTask => write!(formatter, "cannot post a task to the UI thread"),
+        }
+    }
+}
+
+impl error::Error for CaptureError {}
+
+type CaptureResult = Result<Image, CaptureError>;
+
+struct State {
+    options: CaptureOptions,
+    // The height of the view, which becomes the height of the page with `full_page`.
+    view_height: i32,
+    browser: Option<CefRc<Browser>>,
+    // The BGRA pixels of the view, updated with the dirty rectangles of each paint.
+    frame: Vec<u8>,
+    frame_width: i32,
+    frame_height: i32,
+    loaded: bool,
+    measure_requested: bool,
+    measured: bool,
+    // Whether the view was painted since the last check.
+    painted: bool,
+    checking: bool,
+    settle_checks: u32,
+    pending: Pending<CaptureResult>,
+}
+
+impl State {
+    fn view_rect(&self) -> Rect {
+        Rect::new(0, 0, (self.options.size.0 as i32).max(1), self.view_height.max(1))
+    }
+
+    // Whether the last paint has the size of the view, with some tolerance for the rounding of the scaling.
+    fn has_view_size(&self) -> bool {
+        let rect = self.view_rect();
+        let scale = self.options.scale_factor;
+        (self.frame_width as f32 - rect.width as f32 * scale).abs() <= 1.0
+            && (self.frame_height as f32 - rect.height as f32 * scale).abs() <= 1.0
+    }
+
+    fn paint(&mut self, dirty_rects: &[Rect], buffer: &[u8], width: i32, height: i32) {
+        let stride = width as usize * 4;
+        if self.frame.is_empty() || self.frame_width != width || self.frame_height != height {
+            self.frame = buffer.to_vec();
+            self.frame_width = width;
+            self.frame_height = height;
+        }
+        else {
+            for rect in dirty_rects {
+                let left = rect.x.max(0).min(width) as usize * 4;
+                let right = (rect.x + rect.width).max(0).min(width) as usize * 4;
+                let top = rect.y.max(0).min(height) as usize;
+                let bottom = (rect.y + rect.height).max(0).min(height) as usize;
+                for row in top..bottom {
+                    let range = row * stride + left..row * stride + right;
+                    self.frame[range.clone()].copy_from_slice(&buffer[range]);
+                }
+            }
+        }
+        self.painted = true;
+    }
+
+}
+
+impl PendingState for State {
+    type Output = CaptureResult;
+    type Finished = (Option<Waker>, Option<CefRc<Browser>>);
+
+    fn pending(&mut self) -> &mut Pending<CaptureResult> {
+        &mut self.pending
+    }
+
+    fn finish(&mut self, result: CaptureResult) -> Self::Finished {
+        if self.pending.is_finished() {
+            return (None, None);
+        }
+        (self.pending.finish(result), self.browser.take())
+    }
+
+    fn complete(_state: &Arc<Mutex<State>>, (waker, browser): Self::Finished) {
+        if let Some(browser) = browser {
+            browser.host().close_browser(true);
+        }
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+fn schedule_check(state: &Arc<Mutex<State>>) {
+    {
+        let mut state = locked(state);
+        if state.checking || state.pending.is_finished() {
+            return;
+        }
+        state.checking = true;
+    }
+    let task_state = state.clone();
+    if !post_delayed_task(cef_thread_id_t::TID_UI, SETTLE_DELAY, move || check(&task_state)) {
+        locked(state).checking = false;
+    }
+}
+
+// Capture the view once it is loaded, measured with `full_page`, and not painted during the last settle delay.
+fn check(state: &Arc<Mutex<State>>) {
+    let mut measured_browser = None;
+    let finished = {
+        let mut state = locked(state);
+        state.checking = false;
+        if state.pending.is_finished() || state.frame.is_empty() || (state.options.wait_for_load && !state.loaded) {
+            return;
+        }
+        let measuring = state.options.full_page && !state.measured;
+        let wrong_size = !state.has_view_size();
+        let busy = state.painted || measuring || wrong_size;
+        if busy && state.settle_checks < MAX_SETTLE_CHECKS {
+            if measuring && !state.measure_requested {
+                state.measure_requested = true;
+                measured_browser = state.browser.clone();
+            }
+            state.painted = false;
+            state.settle_checks += 1;
+            None
+        }
+        else if measuring {
+            Some(state.finish(Err(CaptureError::MeasureFailed)))
+        }
+        else if wrong_size {
+            // Like when the view is not painted again after being resized to the height of the page.
+            Some(state.finish(Err(CaptureError::TimedOut)))
+        }
+        else {
+            let image = Image::from_bgra(state.frame_width as u32, state.frame_height as u32, &state.frame);
+            Some(state.finish(Ok(image)))
+        }
+    };
+    match finished {
+        Some(finished) => State::complete(state, finished),
+        None => {
+            if let Some(browser) = measured_browser {
+                if !browser.send_process_message(ProcessId::Renderer, &ProcessMessage::new(MEASURE_MESSAGE)) {
+                    pending::finish(state, Err(CaptureError::MeasureFailed));
+                    return;
+                }
+            }
+            schedule_check(state);
+        },
+    }
+}
+
+struct CaptureHandler {
+    state: Arc<Mutex<State>>,
+}
+
+impl LifeSpanHandler for CaptureHandler {
+    fn on_before_popup(&self, _browser: &Browser, _frame: &Frame, _request: &PopupRequest) -> PopupAction {
+        PopupAction::Block
+    }
+
+    fn on_after_created(&self, browser: &Browser) {
+        let mut state = locked(&self.state);
+        if state.pending.is_finished() {
+            drop(state);
+            browser.host().close_browser(true);
+        }
+        else {
+            state.browser = Some(browser.to_rc());
+        }
+    }
+
+    fn on_before_close(&self, _browser: &Browser) {
+        locked(&self.state).browser = None;
+        pending::finish(&self.state, Err(CaptureError::Closed));
+    }
+}
+
+impl LoadHandler for CaptureHandler {
+    fn on_load_end(&self, _browser: &Browser, frame: &Frame, _status: HttpStatus) {
+        if frame.is_main() {
+            locked(&self.state).loaded = true;
+            schedule_check(&self.state);
+        }
+    }
+
+    fn on_load_error(&self, _browser: &Browser, frame: &Frame, error: NetError, error_text: &str, failed_url: &str) {
+        // A navigation is aborted when it is replaced, like by a redirection of the page.
+        if !frame.is_main() || error == NetError::ABORTED {
+            return;
+        }
+        pending::finish(&self.state, Err(CaptureError::Load(LoadError::Failed {
+            error,
+            text: error_text.to_string(),
+            url: failed_url.to_string(),
+        })));
+    }
+}
+
+impl RenderHandler for CaptureHandler {
+    fn get_view_rect(&self, _browser: &Browser) -> Rect {
+        locked(&self.state).view_rect()
+    }
+
+    fn get_screen_info(&self, _browser: &Browser) -> Option<ScreenInfo> {
+        let state = locked(&self.state);
+        Some(ScreenInfo {
+            device_scale_factor: state.options.scale_factor,
+            rect: state.view_rect(),
+            available_rect: state.view_rect(),
+            ..ScreenInfo::default()
+        })
+    }
+
+    // The popup widgets are not captured.
+    fn on_paint(&self, _browser: &Browser, element_type: PaintElementType, dirty_rects: &[Rect], buffer: &[u8],
+        width: i32, height: i32)
+    {
+        if element_type == PaintElementType::View {
+            locked(&self.state).paint(dirty_rects, buffer, width, height);
+            schedule_check(&self.state);
+        }
+    }
+}
+
+struct CaptureClient {
+    handler: Arc<CaptureHandler>,
+}
+
+impl Client for CaptureClient {
+    fn life_span_handler(&self) -> Option<Arc<dyn LifeSpanHandler>> {
+        Some(self.handler.clone())
+    }
+
+    fn load_handler(&self) -> Option<Arc<dyn LoadHandler>> {
+        Some(self.handler.clone())
+    }
+
+    fn render_handler(&self) -> Option<Arc<dyn RenderHandler>> {
+        Some(self.handler.clone())
+    }
+
+    fn on_process_message_received(&self, browser: &Browser, source_process: ProcessId,
+        message: &ProcessMessage) -> bool
+    {
+        if source_process != ProcessId::Renderer || message.name != HEIGHT_MESSAGE {
+            return false;
+        }
+        let state = &self.handler.state;
+        let height = match message.arguments[..] {
+            [Value::Int(height)] if height > 0 => height,
+            _ => {
+                pending::finish(state, Err(CaptureError::MeasureFailed));
+                return true;
+            },
+        };
+        let resized = {
+            let mut state = locked(state);
+            let max_height = (MAX_FULL_PAGE_HEIGHT / state.options.scale_factor.max(0.01)) as i32;
+            let view_height = height.max(state.options.size.1 as i32).min(max_height);
+            state.measured = true;
+            let resized = view_height != state.view_height;
+            state.view_height = view_height;
+            resized
+        };
+        if resized {
+            browser.host().was_resized();
+        }
+        schedule_check(state);
+        true
+    }
+}
+
+// Answer the measure messages of the captures in the render process.
+pub(crate) fn on_render_process_message(browser: &Browser, message: &ProcessMessage) -> bool {
+    if message.name != MEASURE_MESSAGE {
+        return false;
+    }
+    let height = browser.main_frame()
+        .and_then(|frame| frame.v8_context())
+        .and_then(|context| context.eval(MEASURE_SCRIPT, ""))
+        .and_then(|value| value.int_value());
+    let mut answer = ProcessMessage::new(HEIGHT_MESSAGE);
+    if let Some(height) = height {
+        answer = answer.with_argument(Value::Int(height));
+    }
+    browser.send_process_message(ProcessId::Browser, &answer);
+    true
+}
+
+/// A future resolved with the image of the page captured by `Browser::capture`.
+///
+/// The browser is closed once the page is captured, or when the future is dropped.
+#[must_use = "the page is only captured by awaiting the future"]
+pub struct Capture {
+    state: Arc<Mutex<State>>,
+}
+
+impl Capture {
+    pub(crate) fn start(url: &str, options: CaptureOptions) -> Self {
+        let state = Arc::new(Mutex::new(State {
+            options,
+            view_height: options.size.1 as i32,
+            browser: None,
+            frame: vec![],
+            frame_width: 0,
+            frame_height: 0,
+            loaded: false,
+            measure_requested: false,
+            measured: false,
+            painted: false,
+            checking: false,
+            settle_checks: 0,
+            pending: Pending::new(),
+        }));
+        let client = wrap_client(CaptureClient {
+            handler: Arc::new(CaptureHandler {
+                state: state.clone(),
+            }),
+        });
+        let window_info = WindowInfo::windowless(0).to_raw();
+        let url = CefString::new(url);
+        let created = unsafe {
+            cef_browser_host_create_browser(&window_info, client, url.as_raw(), BrowserSettings::default().as_raw(),
+                ptr::null_mut())
+        };
+        if created == 0 {
+            locked(&state).finish(Err(CaptureError::CreateFailed));
+        }
+        Capture {
+            state,
+        }
+        .timeout(DEFAULT_TIMEOUT)
+    }
+
+    /// Resolve with `CaptureError::TimedOut` when the page is not captured after `timeout`, measured on the UI
+    /// thread, instead of the default of 30 seconds.
+    ///
+    /// If the timeout cannot be posted to the UI thread, because CEF is not running, the capture fails right
+    /// away with `CaptureError::PostTask`.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        pending::set_timeout(&self.state, timeout, Err(CaptureError::TimedOut), Err(CaptureError::PostTask));
+        self
+    }
+}
+
+impl Future for Capture {
+    type Output = CaptureResult;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<CaptureResult> {
+        locked(&self.state).pending.poll(context)
+    }
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        let (_, browser) = locked(&self.state).finish(Err(CaptureError::Closed));
+        State::complete(&self.state, (None, browser));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::Poll;
+
+    use mock::{self, TestClient};
+    use super::*;
+
+    fn options(full_page: bool) -> CaptureOptions {
+        CaptureOptions {
+            size: (100, 50),
+            full_page,
+            ..CaptureOptions::default()
+        }
+    }
+
+    // Create the browser of the capture and load its page.
+    fn load() -> i32 {
+        mock::run_pending_tasks();
+        let id = mock::browser_ids()[0];
+        mock::load_end(id, mock::main_frame_id(id).expect("main frame"), 200);
+        id
+    }
+
+    fn paint(id: i32, width: i32, height: i32) {
+        let buffer = vec![255; width as usize * height as usize * 4];
+        mock::paint(id, PaintElementType::View, &[Rect::new(0, 0, width, height)], &buffer, width, height);
+    }
+
+    fn sent_messages() -> Vec<String> {
+        mock::calls().into_iter()
+            .filter(|call| call.starts_with("Browser::send_process_message"))
+            .collect()
+    }
+
+    #[test]
+    fn capture() {
+        let _guard = mock::lock();
+        let cef = mock::start();
+        let mut capture = Browser::capture("https://example.com", options(false));
+        let id = load();
+        assert_eq!(mock::view_rect(id), Some(Rect::new(0, 0, 100, 50)));
+        paint(id, 100, 50);
+        mock::advance_time(100);
+        assert!(mock::poll(&mut capture).is_pending());
+        mock::advance_time(100);
+        let image = match mock::poll(&mut capture) {
+            Poll::Ready(Ok(image)) => image,
+            result => panic!("{:?}", result),
+        };
+        assert_eq!((image.width, image.height), (100, 50));
+        assert_eq!(sent_messages(), Vec::<String>::new());
+        mock::run_pending_tasks();
+        assert_eq!(mock::browser_ids(), vec![]);
+        drop(capture);
+        drop(cef);
+        assert_eq!(mock::live_objects(), 0);
+    }
+
+    #[test]
+    fn full_page() {
+        let _guard = mock::lock();
+        let cef = mock::start();
+        mock::set_script_result(Some(2000));
+        let mut capture = Browser::capture("https://example.com", options(true));
+        let id = load();
+        paint(id, 100, 50);
+        mock::advance_time(100);
+        assert_eq!(sent_messages(), vec!["Browser::send_process_message Renderer cef-capture.measure []"]);
+
+        // The render process measures the page.
+        mock::clear_calls();
+        assert!(mock::render_process_message(id, &ProcessMessage::new(MEASURE_MESSAGE)));
+        assert!(mock::calls().contains(&"V8Context::eval".to_string()));
+        assert_eq!(sent_messages(), vec!["Browser::send_process_message Browser cef-capture.height [Int(2000)]"]);
+
+        let height = ProcessMessage::new(HEIGHT_MESSAGE).with_argument(Value::Int(2000));
+        assert!(mock::receive_process_message(id, &height));
+        assert!(mock::calls().contains(&"BrowserHost::was_resized".to_string()));
+        assert_eq!(mock::view_rect(id), Some(Rect::new(0, 0, 100, 2000)));
+        mock::advance_time(100);
+        assert!(mock::poll(&mut capture).is_pending());
+        paint(id, 100, 2000);
+        mock::advance_time(100);
+        mock::advance_time(100);
+        let image = match mock::poll(&mut capture) {
+            Poll::Ready(Ok(image)) => image,
+            result => panic!("{:?}", result),
+        };
+        assert_eq!((image.width, image.height), (100, 2000));
+        drop(capture);
+        drop(cef);
+        assert_eq!(mock::live_objects(), 0);
+    }
+
+    // Wait for the settle checks of a capture, until it gives up.
+    fn settle(capture: &mut Capture) -> CaptureResult {
+        for _ in 0..=MAX_SETTLE_CHECKS {
+            mock::advance_time(100);
+            if let Poll::Ready(result) = mock::poll(capture) {
+                return result;
+            }
+        }
+        panic!("the capture is not finished after {} settle checks", MAX_SETTLE_CHECKS);
+    }
+
+    #[test]
+    fn unsettled() {
+        mock::run(|| {
+            // The render process never answers.
+            let mut capture = Browser::capture("https://example.com", options(true));
+            let id = load();
+            paint(id, 100, 50);
+            assert_eq!(settle(&mut capture), Err(CaptureError::MeasureFailed));
+
+            // The view is not painted again once resized to the height of the page.
+            let mut capture = Browser::capture("https://example.com", options(true));
+            let id = load();
+            paint(id, 100, 50);
+            let height = ProcessMessage::new(HEIGHT_MESSAGE).with_argument(Value::Int(2000));
+            assert!(mock::receive_process_message(id, &height));
+            assert_eq!(settle(&mut capture), Err(CaptureError::TimedOut));
+
+            // The page keeps painting an animation.
+            let mut capture = Browser::capture("https://example.com", options(false));
+            let id = load();
+            for _ in 0..=MAX_SETTLE_CHECKS {
+                paint(id, 100, 50);
+                mock::advance_time(100);
+            }
+            assert!(matches!(mock::poll(&mut capture), Poll::Ready(Ok(_))));
+            mock::run_pending_tasks();
+            assert_eq!(mock::browser_ids(), vec![]);
+        });
+    }
+
+    #[test]
+    fn measure_failed() {
+        let _guard = mock::lock();
+        let cef = mock::start();
+
+        // The script throws, and the render process answers without height.
+        let browser = mock::create_browser(TestClient::new());
+        mock::clear_calls();
+        assert!(mock::render_process_message(browser.identifier(), &ProcessMessage::new(MEASURE_MESSAGE)));
+        assert_eq!(sent_messages(), vec!["Browser::send_process_message Browser cef-capture.height []"]);
+        assert!(!mock::render_process_message(browser.identifier(), &ProcessMessage::new("other")));
+        browser.host().close_browser(true);
+        mock::run_pending_tasks();
+        drop(browser);
+
+        let invalid_heights = vec![
+            ProcessMessage::new(HEIGHT_MESSAGE),
+            ProcessMessage::new(HEIGHT_MESSAGE).with_argument(Value::Int(0)),
+            ProcessMessage::new(HEIGHT_MESSAGE).with_argument(Value::String("2000".to_string())),
+            ProcessMessage::new(HEIGHT_MESSAGE).with_argument(Value::Int(2000)).with_argument(Value::Int(1)),
+        ];
+        for message in invalid_heights {
+            let mut capture = Browser::capture("https://example.com", options(true));
+            mock::run_pending_tasks();
+            let id = *mock::browser_ids().last().expect("browser");
+            assert!(mock::receive_process_message(id, &message));
+            assert_eq!(mock::poll(&mut capture), Poll::Ready(Err(CaptureError::MeasureFailed)));
+            assert_eq!(mock::view_rect(id), Some(Rect::new(0, 0, 100, 50)));
+            mock::run_pending_tasks();
+        }
+        assert_eq!(CaptureError::MeasureFailed.to_string(), "the height of the page cannot be measured");
+        assert_eq!(mock::browser_ids(), vec![]);
+        drop(cef);
+        assert_eq!(mock::live_objects(), 0);
+    }
+
+    #[test]
+    fn timeout() {
+        let _guard = mock::lock();
+        let cef = mock::start();
+        let mut capture = Browser::capture("https://example.com", options(false));
+        mock::run_pending_tasks();
+        mock::advance_time(29_999);
+        assert!(mock::poll(&mut capture).is_pending());
+        mock::advance_time(1);
+        assert_eq!(mock::poll(&mut capture), Poll::Ready(Err(CaptureError::TimedOut)));
+        mock::run_pending_tasks();
+        assert_eq!(mock::browser_ids(), vec![]);
+
+        // Only the last timeout fires.
+        let mut capture = Browser::capture("https://example.com", options(false)).timeout(Duration::from_secs(60));
+        mock::run_pending_tasks();
+        mock::advance_time(30_000);
+        assert!(mock::poll(&mut capture).is_pending());
+        mock::advance_time(30_000);
+        assert_eq!(mock::poll(&mut capture), Poll::Ready(Err(CaptureError::TimedOut)));
+
+        let mut capture = Browser::capture("https://example.com", options(false)).timeout(Duration::from_secs(1));
+        mock::advance_time(1000);
+        assert_eq!(mock::poll(&mut capture), Poll::Ready(Err(CaptureError::TimedOut)));
+        drop(capture);
+        drop(cef);
+        assert_eq!(mock::live_objects(), 0);
+
+        // Nothing can be captured once CEF is shut down.
+        let mut capture = Browser::capture("https://example.com", options(false));
+        assert_eq!(mock::poll(&mut capture), Poll::Ready(Err(CaptureError::CreateFailed)));
+        drop(capture);
+        assert_eq!(mock::live_objects(), 0);
+    }
+}
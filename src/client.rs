@@ -0,0 +1,391 @@
+use std::mem;
+use std::ptr;
+use std::sync::{Arc, Mutex};
+
+use browser::Browser;
+use context_menu::{ContextMenuHandler, wrap_context_menu_handler};
+use dialog::{DialogHandler, wrap_dialog_handler};
+use display::{DisplayHandler, wrap_display_handler};
+use download::{DownloadHandler, wrap_download_handler};
+use drag::{DragHandler, wrap_drag_handler};
+use find::{DefaultFindHandler, FindHandler, wrap_find_handler};
+use focus::{FocusHandler, wrap_focus_handler};
+use jsdialog::{JsDialogHandler, wrap_jsdialog_handler};
+use keyboard::{KeyboardHandler, wrap_keyboard_handler};
+use life_span::{DefaultLifeSpanHandler, LifeSpanHandler, wrap_life_span_handler};
+use load::{DefaultLoadHandler, LoadHandler, wrap_load_handler};
+use process_message::{ProcessId, ProcessMessage};
+use rc::{CefRc, CefRefCounted, RefCounted, add_base_ref, release_base};
+use render::{RenderHandler, wrap_render_handler};
+use sys::*;
+
+/// The handlers of the browsers created with this client.
+///
+/// Every handler is `None` by default, which keeps the default behavior of CEF.
+pub trait Client: Send + Sync {
+    fn context_menu_handler(&self) -> Option<Arc<dyn ContextMenuHandler>> {
+        None
+    }
+
+    fn dialog_handler(&self) -> Option<Arc<dyn DialogHandler>> {
+        None
+    }
+
+    fn display_handler(&self) -> Option<Arc<dyn DisplayHandler>> {
+        None
+    }
+
+    fn download_handler(&self) -> Option<Arc<dyn DownloadHandler>> {
+        None
+    }
+
+    fn drag_handler(&self) -> Option<Arc<dyn DragHandler>> {
+        None
+    }
+
+    /// A default handler is used when `None`, to deliver the results of `BrowserHost::find_results`.
+    fn find_handler(&self) -> Option<Arc<dyn FindHandler>> {
+        None
+    }
+
+    fn focus_handler(&self) -> Option<Arc<dyn FocusHandler>> {
+        None
+    }
+
+    fn jsdialog_handler(&self) -> Option<Arc<dyn JsDialogHandler>> {
+        None
+    }
+
+    fn keyboard_handler(&self) -> Option<Arc<dyn KeyboardHandler>> {
+        None
+    }
+
+    /// A default handler is used when `None`, to fail the navigations of a browser once it is closed.
+    fn life_span_handler(&self) -> Option<Arc<dyn LifeSpanHandler>> {
+        None
+    }
+
+    /// A default handler is used when `None`, to resolve the navigations of `Browser::load_url`.
+    fn load_handler(&self) -> Option<Arc<dyn LoadHandler>> {
+        None
+    }
+
+    /// Required by the windowless browsers.
+    fn render_handler(&self) -> Option<Arc<dyn RenderHandler>> {
+        None
+    }
+
+    /// Returns true if the message was handled.
+    fn on_process_message_received(&self, _browser: &Browser, _source_process: ProcessId,
+        _message: &ProcessMessage) -> bool
+    {
+        false
+    }
+}
+
+impl<C: Client + ?Sized> Client for Arc<C> {
+    fn context_menu_handler(&self) -> Option<Arc<dyn ContextMenuHandler>> {
+        (**self).context_menu_handler()
+    }
+
+    fn dialog_handler(&self) -> Option<Arc<dyn DialogHandler>> {
+        (**self).dialog_handler()
+    }
+
+    fn display_handler(&self) -> Option<Arc<dyn DisplayHandler>> {
+        (**self).display_handler()
+    }
+
+    fn download_handler(&self) -> Option<Arc<dyn DownloadHandler>> {
+        (**self).download_handler()
+    }
+
+    fn drag_handler(&self) -> Option<Arc<dyn DragHandler>> {
+        (**self).drag_handler()
+    }
+
+    fn find_handler(&self) -> Option<Arc<dyn FindHandler>> {
+        (**self).find_handler()
+    }
+
+    fn focus_handler(&self) -> Option<Arc<dyn FocusHandler>> {
+        (**self).focus_handler()
+    }
+
+    fn jsdialog_handler(&self) -> Option<Arc<dyn JsDialogHandler>> {
+        (**self).jsdialog_handler()
+    }
+
+    fn keyboard_handler(&self) -> Option<Arc<dyn KeyboardHandler>> {
+        (**self).keyboard_handler()
+    }
+
+    fn life_span_handler(&self) -> Option<Arc<dyn LifeSpanHandler>> {
+        (**self).life_span_handler()
+    }
+
+    fn load_handler(&self) -> Option<Arc<dyn LoadHandler>> {
+        (**self).load_handler()
+    }
+
+    fn render_handler(&self) -> Option<Arc<dyn RenderHandler>> {
+        (**self).render_handler()
+    }
+
+    fn on_process_message_received(&self, browser: &Browser, source_process: ProcessId,
+        message: &ProcessMessage) -> bool
+    {
+        (**self).on_process_message_received(browser, source_process, message)
+    }
+}
+
+// A C handler built by the first call of its getter, and kept with a reference until the client is destroyed.
+struct Cached<T: CefRefCounted> {
+    raw: Mutex<Option<*mut T>>,
+}
+
+unsafe impl<T: CefRefCounted> Send for Cached<T> {}
+unsafe impl<T: CefRefCounted> Sync for Cached<T> {}
+
+impl<T: CefRefCounted> Cached<T> {
+    fn new() -> Self {
+        Cached {
+            raw: Mutex::new(None),
+        }
+    }
+
+    // Returns a new reference, or null when the client has no such handler.
+    fn get<F: FnOnce() -> Option<*mut T>>(&self, wrap: F) -> *mut T {
+        let mut raw = self.raw.lock().expect("cached handler");
+        let raw = *raw.get_or_insert_with(|| wrap().unwrap_or(ptr::null_mut()));
+        if !raw.is_null() {
+            unsafe { add_base_ref(raw as *mut cef_base_ref_counted_t) };
+        }
+        raw
+    }
+}
+
+impl<T: CefRefCounted> Drop for Cached<T> {
+    fn drop(&mut self) {
+        if let Ok(&mut Some(raw)) = self.raw.get_mut() {
+            if !raw.is_null() {
+                unsafe { release_base(raw as *mut cef_base_ref_counted_t) };
+            }
+        }
+    }
+}
+
+struct ClientState<C> {
+    client: C,
+    context_menu_handler: Cached<cef_context_menu_handler_t>,
+    dialog_handler: Cached<cef_dialog_handler_t>,
+    display_handler: Cached<cef_display_handler_t>,
+    download_handler: Cached<cef_download_handler_t>,
+    drag_handler: Cached<cef_drag_handler_t>,
+    find_handler: Cached<cef_find_handler_t>,
+    focus_handler: Cached<cef_focus_handler_t>,
+    jsdialog_handler: Cached<cef_jsdialog_handler_t>,
+    keyboard_handler: Cached<cef_keyboard_handler_t>,
+    life_span_handler: Cached<cef_life_span_handler_t>,
+    load_handler: Cached<cef_load_handler_t>,
+    render_handler: Cached<cef_render_handler_t>,
+}
+
+/// Create a `cef_client_t` calling `client`, whose reference is owned by the caller.
+///
+/// Each handler is asked for once, the first time CEF needs it.
+pub fn wrap_client<C: Client + 'static>(client: C) -> *mut cef_client_t {
+    let mut raw: cef_client_t = unsafe { mem::zeroed() };
+    raw.get_context_menu_handler = Some(get_context_menu_handler::<C>);
+    raw.get_dialog_handler = Some(get_dialog_handler::<C>);
+    raw.get_display_handler = Some(get_display_handler::<C>);
+    raw.get_download_handler = Some(get_download_handler::<C>);
+    raw.get_drag_handler = Some(get_drag_handler::<C>);
+    raw.get_find_handler = Some(get_find_handler::<C>);
+    raw.get_focus_handler = Some(get_focus_handler::<C>);
+    raw.get_jsdialog_handler = Some(get_jsdialog_handler::<C>);
+    raw.get_keyboard_handler = Some(get_keyboard_handler::<C>);
+    raw.get_life_span_handler = Some(get_life_span_handler::<C>);
+    raw.get_load_handler = Some(get_load_handler::<C>);
+    raw.get_render_handler = Some(get_render_handler::<C>);
+    raw.on_process_message_received = Some(on_process_message_received::<C>);
+    RefCounted::wrap(raw, ClientState {
+        client,
+        context_menu_handler: Cached::new(),
+        dialog_handler: Cached::new(),
+        display_handler: Cached::new(),
+        download_handler: Cached::new(),
+        drag_handler: Cached::new(),
+        find_handler: Cached::new(),
+        focus_handler: Cached::new(),
+        jsdialog_handler: Cached::new(),
+        keyboard_handler: Cached::new(),
+        life_span_handler: Cached::new(),
+        load_handler: Cached::new(),
+        render_handler: Cached::new(),
+    })
+}
+
+extern "C" fn get_context_menu_handler<C: Client>(self_: *mut cef_client_t) -> *mut cef_context_menu_handler_t {
+    let state = unsafe { RefCounted::<_, ClientState<C>>::value(self_) };
+    state.context_menu_handler.get(|| state.client.context_menu_handler().map(wrap_context_menu_handler))
+}
+
+extern "C" fn get_dialog_handler<C: Client>(self_: *mut cef_client_t) -> *mut cef_dialog_handler_t {
+    let state = unsafe { RefCounted::<_, ClientState<C>>::value(self_) };
+    state.dialog_handler.get(|| state.client.dialog_handler().map(wrap_dialog_handler))
+}
+
+extern "C" fn get_display_handler<C: Client>(self_: *mut cef_client_t) -> *mut cef_display_handler_t {
+    let state = unsafe { RefCounted::<_, ClientState<C>>::value(self_) };
+    state.display_handler.get(|| state.client.display_handler().map(wrap_display_handler))
+}
+
+extern "C" fn get_download_handler<C: Client>(self_: *mut cef_client_t) -> *mut cef_download_handler_t {
+    let state = unsafe { RefCounted::<_, ClientState<C>>::value(self_) };
+    state.download_handler.get(|| state.client.download_handler().map(wrap_download_handler))
+}
+
+extern "C" fn get_drag_handler<C: Client>(self_: *mut cef_client_t) -> *mut cef_drag_handler_t {
+    let state = unsafe { RefCounted::<_, ClientState<C>>::value(self_) };
+    state.drag_handler.get(|| state.client.drag_handler().map(wrap_drag_handler))
+}
+
+extern "C" fn get_find_handler<C: Client>(self_: *mut cef_client_t) -> *mut cef_find_handler_t {
+    let state = unsafe { RefCounted::<_, ClientState<C>>::value(self_) };
+    state.find_handler.get(|| {
+        let handler = state.client.find_handler().unwrap_or_else(|| Arc::new(DefaultFindHandler));
+        Some(wrap_find_handler(handler))
+    })
+}
+
+extern "C" fn get_focus_handler<C: Client>(self_: *mut cef_client_t) -> *mut cef_focus_handler_t {
+    let state = unsafe { RefCounted::<_, ClientState<C>>::value(self_) };
+    state.focus_handler.get(|| state.client.focus_handler().map(wrap_focus_handler))
+}
+
+extern "C" fn get_jsdialog_handler<C: Client>(self_: *mut cef_client_t) -> *mut cef_jsdialog_handler_t {
+    let state = unsafe { RefCounted::<_, ClientState<C>>::value(self_) };
+    state.jsdialog_handler.get(|| state.client.jsdialog_handler().map(wrap_jsdialog_handler))
+}
+
+extern "C" fn get_keyboard_handler<C: Client>(self_: *mut cef_client_t) -> *mut cef_keyboard_handler_t {
+    let state = unsafe { RefCounted::<_, ClientState<C>>::value(self_) };
+    state.keyboard_handler.get(|| state.client.keyboard_handler().map(wrap_keyboard_handler))
+}
+
+extern "C" fn get_life_span_handler<C: Client>(self_: *mut cef_client_t) -> *mut cef_life_span_handler_t {
+    let state = unsafe { RefCounted::<_, ClientState<C>>::value(self_) };
+    state.life_span_handler.get(|| {
+        let handler = state.client.life_span_handler().unwrap_or_else(|| Arc::new(DefaultLifeSpanHandler));
+        Some(wrap_life_span_handler(handler))
+    })
+}
+
+extern "C" fn get_load_handler<C: Client>(self_: *mut cef_client_t) -> *mut cef_load_handler_t {
+    let state = unsafe { RefCounted::<_, ClientState<C>>::value(self_) };
+    state.load_handler.get(|| {
+        let handler = state.client.load_handler().unwrap_or_else(|| Arc::new(DefaultLoadHandler));
+        Some(wrap_load_handler(handler))
+    })
+}
+
+extern "C" fn get_render_handler<C: Client>(self_: *mut cef_client_t) -> *mut cef_render_handler_t {
+    let state = unsafe { RefCounted::<_, ClientState<C>>::value(self_) };
+    state.render_handler.get(|| state.client.render_handler().map(wrap_render_handler))
+}
+
+extern "C" fn on_process_message_received<C: Client>(self_: *mut cef_client_t, browser: *mut cef_browser_t,
+    source_process: cef_process_id_t, message: *mut cef_process_message_t) -> i32
+{
+    let state = unsafe { RefCounted::<_, ClientState<C>>::value(self_) };
+    let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+    if message.is_null() {
+        return 0;
+    }
+    let message = unsafe {
+        let copy = ProcessMessage::from_raw(message);
+        release_base(message as *mut cef_base_ref_counted_t);
+        copy
+    };
+    match browser {
+        Some(browser) =>
+            state.client.on_process_message_received(&browser, ProcessId::from_raw(source_process), &message) as i32,
+        None => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use display::DisplayHandler;
+    use mock;
+    use process_message::Value;
+    use super::*;
+
+    #[derive(Default)]
+    struct TestClient {
+        display_handlers: AtomicUsize,
+        titles: Arc<Mutex<Vec<String>>>,
+        messages: Mutex<Vec<(ProcessId, ProcessMessage)>>,
+    }
+
+    impl Client for TestClient {
+        fn display_handler(&self) -> Option<Arc<dyn DisplayHandler>> {
+            self.display_handlers.fetch_add(1, Ordering::SeqCst);
+            Some(Arc::new(Titles(self.titles.clone())))
+        }
+
+        fn on_process_message_received(&self, _browser: &Browser, source_process: ProcessId,
+            message: &ProcessMessage) -> bool
+        {
+            self.messages.lock().unwrap().push((source_process, message.clone()));
+            message.name == "handled"
+        }
+    }
+
+    struct Titles(Arc<Mutex<Vec<String>>>);
+
+    impl DisplayHandler for Titles {
+        fn on_title_change(&self, _browser: &Browser, title: &str) {
+            self.0.lock().unwrap().push(title.to_string());
+        }
+    }
+
+    #[test]
+    fn cached_handlers() {
+        let _guard = mock::lock();
+        let cef = mock::start();
+        let client = Arc::new(TestClient::default());
+        let browser = mock::create_browser(client.clone());
+        mock::title_change(browser.identifier(), "first");
+        mock::title_change(browser.identifier(), "second");
+        let titles = client.titles.lock().unwrap().clone();
+        assert_eq!(titles, ["first", "second"]);
+        assert_eq!(client.display_handlers.load(Ordering::SeqCst), 1);
+        drop(browser);
+        drop(cef);
+        assert_eq!(Arc::strong_count(&client), 1);
+        assert_eq!(mock::live_objects(), 0);
+    }
+
+    #[test]
+    fn process_messages() {
+        let _guard = mock::lock();
+        let cef = mock::start();
+        let client = Arc::new(TestClient::default());
+        let browser = mock::create_browser(client.clone());
+        let message = ProcessMessage::new("handled")
+            .with_argument(Value::Int(1))
+            .with_argument(Value::List(vec![Value::String("a".to_string()), Value::Bool(true)]));
+        assert!(mock::receive_process_message(browser.identifier(), &message));
+        assert!(!mock::receive_process_message(browser.identifier(), &ProcessMessage::new("ignored")));
+        let messages = [(ProcessId::Renderer, message), (ProcessId::Renderer, ProcessMessage::new("ignored"))];
+        let received = client.messages.lock().unwrap().clone();
+        assert_eq!(received, messages);
+        drop(browser);
+        drop(cef);
+        assert_eq!(mock::live_objects(), 0);
+    }
+}
@@ -0,0 +1,91 @@
+use rc::CefRc;
+use string::CefString;
+use sys::{
+    cef_command_line_create,
+    cef_command_line_get_global,
+    cef_command_line_t,
+};
+
+/// The switches and arguments of a process.
+#[repr(transparent)]
+pub struct CommandLine(cef_command_line_t);
+
+cef_object!(CommandLine, cef_command_line_t);
+
+impl CommandLine {
+    pub fn new() -> CefRc<CommandLine> {
+        unsafe { CefRc::from_raw(cef_command_line_create()) }
+            .expect("command line")
+    }
+
+    /// The command line of the current process, which is read-only.
+    pub fn global() -> CefRc<CommandLine> {
+        unsafe { CefRc::from_raw(cef_command_line_get_global()) }
+            .expect("global command line")
+    }
+
+    pub fn is_valid(&self) -> bool {
+        call!(&self.0, is_valid()) != 0
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        call!(&self.0, is_read_only()) != 0
+    }
+
+    pub fn command_line_string(&self) -> String {
+        unsafe { CefString::from_userfree(call!(&self.0, get_command_line_string())) }.to_string()
+    }
+
+    pub fn program(&self) -> String {
+        unsafe { CefString::from_userfree(call!(&self.0, get_program())) }.to_string()
+    }
+
+    pub fn set_program(&self, program: &str) {
+        let program = CefString::new(program);
+        call!(&self.0, set_program(program.as_raw()))
+    }
+
+    pub fn has_switches(&self) -> bool {
+        call!(&self.0, has_switches()) != 0
+    }
+
+    /// `name` is given without the leading dashes.
+    pub fn has_switch(&self, name: &str) -> bool {
+        let name = CefString::new(name);
+        call!(&self.0, has_switch(name.as_raw())) != 0
+    }
+
+    pub fn switch_value(&self, name: &str) -> Option<String> {
+        if !self.has_switch(name) {
+            return None;
+        }
+        let name = CefString::new(name);
+        Some(unsafe { CefString::from_userfree(call!(&self.0, get_switch_value(name.as_raw()))) }.to_string())
+    }
+
+    pub fn append_switch(&self, name: &str) {
+        let name = CefString::new(name);
+        call!(&self.0, append_switch(name.as_raw()))
+    }
+
+    pub fn append_switch_with_value(&self, name: &str, value: &str) {
+        let name = CefString::new(name);
+        let value = CefString::new(value);
+        call!(&self.0, append_switch_with_value(name.as_raw(), value.as_raw()))
+    }
+
+    pub fn has_arguments(&self) -> bool {
+        call!(&self.0, has_arguments()) != 0
+    }
+
+    pub fn append_argument(&self, argument: &str) {
+        let argument = CefString::new(argument);
+        call!(&self.0, append_argument(argument.as_raw()))
+    }
+
+    /// Run the program through a wrapper, like `gdb --args`.
+    pub fn prepend_wrapper(&self, wrapper: &str) {
+        let wrapper = CefString::new(wrapper);
+        call!(&self.0, prepend_wrapper(wrapper.as_raw()))
+    }
+}
@@ -0,0 +1,200 @@
+use std::env;
+use std::error;
+use std::ffi::{CString, NulError, OsStr};
+use std::fmt;
+use std::marker::PhantomData;
+use std::os::raw::c_char;
+use std::os::unix::ffi::OsStrExt;
+use std::process;
+use std::ptr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use app::{App, wrap_app};
+use settings::Settings;
+use sys::{
+    cef_do_message_loop_work,
+    cef_execute_process,
+    cef_initialize,
+    cef_main_args_t,
+    cef_quit_message_loop,
+    cef_run_message_loop,
+    cef_shutdown,
+};
+
+const UNINITIALIZED: usize = 0;
+const RUNNING: usize = 1;
+const SHUT_DOWN: usize = 2;
+
+// CEF can only be initialized once per process, even after it is shut down.
+static STATE: AtomicUsize = AtomicUsize::new(UNINITIALIZED);
+
+// The mock libcef can be initialized again by each test.
+#[cfg(test)]
+pub(crate) fn reset_state() {
+    STATE.store(UNINITIALIZED, Ordering::SeqCst);
+}
+
+/// The arguments of the process, as given to CEF.
+pub struct MainArgs {
+    raw: cef_main_args_t,
+    _argv: Vec<*mut c_char>,
+    _args: Vec<CString>,
+}
+
+impl MainArgs {
+    /// The arguments of the current process.
+    pub fn from_env() -> Self {
+        Self::new(env::args_os())
+            .expect("process arguments cannot contain a nul byte")
+    }
+
+    pub fn new<I, S>(args: I) -> Result<Self, NulError>
+    where I: IntoIterator<Item=S>,
+          S: AsRef<OsStr>,
+    {
+        let args = args.into_iter()
+            .map(|arg| CString::new(arg.as_ref().as_bytes()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut argv: Vec<_> = args.iter()
+            .map(|arg| arg.as_ptr() as *mut c_char)
+            .collect();
+        let argc = argv.len() as i32;
+        // Like the argv of main, the array ends with a null pointer.
+        argv.push(ptr::null_mut());
+        let raw = cef_main_args_t {
+            argc,
+            argv: argv.as_mut_ptr(),
+        };
+        Ok(MainArgs {
+            raw,
+            _argv: argv,
+            _args: args,
+        })
+    }
+
+    pub fn as_raw(&self) -> *const cef_main_args_t {
+        &self.raw
+    }
+}
+
+#[derive(Debug)]
+pub enum StartError {
+    AlreadyInitialized,
+    /// CEF cannot be initialized again after it was shut down.
+    ShutDown,
+    InitializationFailed,
+}
+
+impl fmt::Display for StartError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let message =
+            match *self {
+                StartError::AlreadyInitialized => "CEF is already initialized",
+                StartError::ShutDown => "CEF was shut down and cannot be initialized again",
+                StartError::InitializationFailed => "cef_initialize failed",
+            };
+        write!(formatter, "{}", message)
+    }
+}
+
+impl error::Error for StartError {}
+
+/// The CEF runtime of the browser process, shut down on drop.
+///
+/// It must be dropped on the thread that started it, after every browser is closed.
+pub struct Cef {
+    _args: MainArgs,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Cef {
+    /// Initialize CEF in the browser process.
+    ///
+    /// In a subprocess (a renderer, the GPU process...), this runs the subprocess and exits the process when it
+    /// is done, so this must be called at the start of `main`, before any other initialization.
+    pub fn start<A: App + 'static>(args: MainArgs, settings: &Settings, app: A) -> Result<Cef, StartError> {
+        let app = Arc::new(app);
+        match STATE.compare_exchange(UNINITIALIZED, RUNNING, Ordering::SeqCst, Ordering::SeqCst) {
+            Ok(_) => (),
+            Err(RUNNING) => return Err(StartError::AlreadyInitialized),
+            Err(_) => return Err(StartError::ShutDown),
+        }
+
+        let exit_code = unsafe { cef_execute_process(args.as_raw(), wrap_app(app.clone()), ptr::null_mut()) };
+        if exit_code >= 0 {
+            process::exit(exit_code);
+        }
+
+        if unsafe { cef_initialize(args.as_raw(), settings.as_raw(), wrap_app(app), ptr::null_mut()) } == 0 {
+            STATE.store(SHUT_DOWN, Ordering::SeqCst);
+            return Err(StartError::InitializationFailed);
+        }
+
+        Ok(Cef {
+            _args: args,
+            _not_send: PhantomData,
+        })
+    }
+
+    /// Run the message loop until `quit` is called. Not used with `multi_threaded_message_loop`.
+    pub fn run(&self) {
+        unsafe { cef_run_message_loop() };
+    }
+
+    /// Process the pending work, with `external_message_pump`.
+    pub fn do_message_loop_work(&self) {
+        unsafe { cef_do_message_loop_work() };
+    }
+
+    /// Make `run` return. Must be called on the UI thread, like from a handler.
+    pub fn quit() {
+        unsafe { cef_quit_message_loop() };
+    }
+}
+
+impl Drop for Cef {
+    fn drop(&mut self) {
+        unsafe { cef_shutdown() };
+        STATE.store(SHUT_DOWN, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use mock;
+    use super::*;
+
+    struct NoApp;
+
+    impl App for NoApp {}
+
+    fn start() -> Result<Cef, StartError> {
+        Cef::start(MainArgs::new(vec!["test"]).unwrap(), &Settings::default(), NoApp)
+    }
+
+    #[test]
+    fn start_once() {
+        let _guard = mock::lock();
+        let cef = start().unwrap();
+        assert!(matches!(start(), Err(StartError::AlreadyInitialized)));
+        drop(cef);
+        assert!(matches!(start(), Err(StartError::ShutDown)));
+        assert_eq!(mock::calls(), ["cef_execute_process", "cef_initialize", "cef_shutdown"]);
+        assert_eq!(mock::live_objects(), 0);
+    }
+
+    #[test]
+    fn main_args() {
+        let args = MainArgs::new(vec!["cef", "--type=renderer"]).unwrap();
+        let raw = unsafe { &*args.as_raw() };
+        assert_eq!(raw.argc, 2);
+        let arg = |index| unsafe { CStr::from_ptr(*raw.argv.offset(index)) };
+        assert_eq!(arg(0).to_bytes(), b"cef");
+        assert_eq!(arg(1).to_bytes(), b"--type=renderer");
+        assert!(unsafe { *raw.argv.offset(2) }.is_null());
+        assert!(MainArgs::new(vec!["a\0b"]).is_err());
+    }
+}
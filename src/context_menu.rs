@@ -0,0 +1,508 @@
+use std::collections::HashMap;
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+use browser::{Browser, Frame};
+use keyboard::Modifiers;
+use menu_model::MenuModel;
+use rc::{CefRc, RefCounted, release_base};
+use string::{CefString, string_list};
+use sys::*;
+
+bitflags! {
+    /// What was clicked to open the menu.
+    #[derive(Default)]
+    pub struct ContextMenuTypeFlags: cef_context_menu_type_flags_t {
+        const PAGE = CM_TYPEFLAG_PAGE;
+        const FRAME = CM_TYPEFLAG_FRAME;
+        const LINK = CM_TYPEFLAG_LINK;
+        const MEDIA = CM_TYPEFLAG_MEDIA;
+        const SELECTION = CM_TYPEFLAG_SELECTION;
+        const EDITABLE = CM_TYPEFLAG_EDITABLE;
+    }
+}
+
+bitflags! {
+    /// The edit commands available in the clicked editable element.
+    #[derive(Default)]
+    pub struct EditStateFlags: cef_context_menu_edit_state_flags_t {
+        const CAN_UNDO = CM_EDITFLAG_CAN_UNDO;
+        const CAN_REDO = CM_EDITFLAG_CAN_REDO;
+        const CAN_CUT = CM_EDITFLAG_CAN_CUT;
+        const CAN_COPY = CM_EDITFLAG_CAN_COPY;
+        const CAN_PASTE = CM_EDITFLAG_CAN_PASTE;
+        const CAN_DELETE = CM_EDITFLAG_CAN_DELETE;
+        const CAN_SELECT_ALL = CM_EDITFLAG_CAN_SELECT_ALL;
+        const CAN_TRANSLATE = CM_EDITFLAG_CAN_TRANSLATE;
+    }
+}
+
+/// The type of the clicked media.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MediaType {
+    #[default]
+    None,
+    Image,
+    Video,
+    Audio,
+    File,
+    Plugin,
+}
+
+impl MediaType {
+    pub fn from_raw(media_type: cef_context_menu_media_type_t) -> Self {
+        match media_type {
+            cef_context_menu_media_type_t::CM_MEDIATYPE_NONE => MediaType::None,
+            cef_context_menu_media_type_t::CM_MEDIATYPE_IMAGE => MediaType::Image,
+            cef_context_menu_media_type_t::CM_MEDIATYPE_VIDEO => MediaType::Video,
+            cef_context_menu_media_type_t::CM_MEDIATYPE_AUDIO => MediaType::Audio,
+            cef_context_menu_media_type_t::CM_MEDIATYPE_FILE => MediaType::File,
+            cef_context_menu_media_type_t::CM_MEDIATYPE_PLUGIN => MediaType::Plugin,
+        }
+    }
+
+    pub fn to_raw(self) -> cef_context_menu_media_type_t {
+        match self {
+            MediaType::None => cef_context_menu_media_type_t::CM_MEDIATYPE_NONE,
+            MediaType::Image => cef_context_menu_media_type_t::CM_MEDIATYPE_IMAGE,
+            MediaType::Video => cef_context_menu_media_type_t::CM_MEDIATYPE_VIDEO,
+            MediaType::Audio => cef_context_menu_media_type_t::CM_MEDIATYPE_AUDIO,
+            MediaType::File => cef_context_menu_media_type_t::CM_MEDIATYPE_FILE,
+            MediaType::Plugin => cef_context_menu_media_type_t::CM_MEDIATYPE_PLUGIN,
+        }
+    }
+}
+
+/// Where and on what a context menu was opened. The strings are empty when they do not apply.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContextMenuParams {
+    /// The position of the click, relative to the browser.
+    pub x: i32,
+    pub y: i32,
+    pub type_flags: ContextMenuTypeFlags,
+    pub link_url: String,
+    /// The link URL, before the security filters of Chromium.
+    pub unfiltered_link_url: String,
+    /// The URL of the clicked element, like the source of an image.
+    pub source_url: String,
+    pub has_image_contents: bool,
+    pub title_text: String,
+    pub page_url: String,
+    pub frame_url: String,
+    pub media_type: MediaType,
+    pub selection_text: String,
+    pub misspelled_word: String,
+    pub dictionary_suggestions: Vec<String>,
+    pub is_editable: bool,
+    pub edit_state_flags: EditStateFlags,
+}
+
+impl ContextMenuParams {
+    /// Copy the parameters, which CEF only keeps during the callback.
+    ///
+    /// # Safety
+    ///
+    /// `params` must be a valid pointer.
+    pub unsafe fn from_raw(params: *mut cef_context_menu_params_t) -> Self {
+        let params = &*params;
+        let string = |string| CefString::from_userfree(string).to_string();
+        let suggestions = cef_string_list_alloc();
+        call!(params, get_dictionary_suggestions(suggestions));
+        let dictionary_suggestions = string_list(suggestions);
+        cef_string_list_free(suggestions);
+        ContextMenuParams {
+            x: call!(params, get_xcoord()),
+            y: call!(params, get_ycoord()),
+            type_flags: ContextMenuTypeFlags::from_bits_truncate(call!(params, get_type_flags())),
+            link_url: string(call!(params, get_link_url())),
+            unfiltered_link_url: string(call!(params, get_unfiltered_link_url())),
+            source_url: string(call!(params, get_source_url())),
+            has_image_contents: call!(params, has_image_contents()) != 0,
+            title_text: string(call!(params, get_title_text())),
+            page_url: string(call!(params, get_page_url())),
+            frame_url: string(call!(params, get_frame_url())),
+            media_type: MediaType::from_raw(call!(params, get_media_type())),
+            selection_text: string(call!(params, get_selection_text())),
+            misspelled_word: string(call!(params, get_misspelled_word())),
+            dictionary_suggestions,
+            is_editable: call!(params, is_editable()) != 0,
+            edit_state_flags: EditStateFlags::from_bits_truncate(call!(params, get_edit_state_flags())),
+        }
+    }
+}
+
+type Command = Box<dyn FnOnce(&Browser, &Frame) + Send>;
+
+// The closures of the items of a menu, by command id.
+struct Commands {
+    next_id: i32,
+    actions: HashMap<i32, Command>,
+}
+
+impl Commands {
+    fn new() -> Self {
+        Commands {
+            next_id: MENU_ID_USER_FIRST,
+            actions: HashMap::new(),
+        }
+    }
+
+    fn new_id(&mut self) -> Option<i32> {
+        if self.next_id > MENU_ID_USER_LAST {
+            return None;
+        }
+        self.next_id += 1;
+        Some(self.next_id - 1)
+    }
+}
+
+/// A context menu being built, whose items can run a closure when they are chosen.
+///
+/// The command ids of these items are taken from `MENU_ID_USER_FIRST` to `MENU_ID_USER_LAST`, so the items added
+/// directly to the model should use other ids. The methods adding an item return its command id, or `None` if
+/// the item cannot be added.
+pub struct ContextMenu<'a> {
+    model: CefRc<MenuModel>,
+    commands: &'a mut Commands,
+}
+
+impl<'a> ContextMenu<'a> {
+    /// The model of the menu, which starts with the default items of CEF.
+    pub fn model(&self) -> &MenuModel {
+        &self.model
+    }
+
+    /// Remove every item, like the default ones, with their closures.
+    pub fn clear(&mut self) {
+        fn remove_actions(model: &MenuModel, commands: &mut Commands) {
+            for index in 0..model.count() {
+                commands.actions.remove(&model.command_id_at(index));
+                if let Some(sub_menu) = model.sub_menu_at(index) {
+                    remove_actions(&sub_menu, commands);
+                }
+            }
+        }
+
+        remove_actions(&self.model, self.commands);
+        self.model.clear();
+    }
+
+    pub fn add_separator(&mut self) {
+        self.model.add_separator();
+    }
+
+    pub fn add_item<F>(&mut self, label: &str, action: F) -> Option<i32>
+        where F: FnOnce(&Browser, &Frame) + Send + 'static
+    {
+        let command_id = self.commands.new_id()?;
+        if !self.model.add_item(command_id, label) {
+            return None;
+        }
+        self.commands.actions.insert(command_id, Box::new(action));
+        Some(command_id)
+    }
+
+    pub fn add_check_item<F>(&mut self, label: &str, checked: bool, action: F) -> Option<i32>
+        where F: FnOnce(&Browser, &Frame) + Send + 'static
+    {
+        let command_id = self.commands.new_id()?;
+        if !self.model.add_check_item(command_id, label) {
+            return None;
+        }
+        self.model.set_checked(command_id, checked);
+        self.commands.actions.insert(command_id, Box::new(action));
+        Some(command_id)
+    }
+
+    /// Checking a radio item unchecks the other items of its group.
+    pub fn add_radio_item<F>(&mut self, label: &str, group_id: i32, checked: bool, action: F) -> Option<i32>
+        where F: FnOnce(&Browser, &Frame) + Send + 'static
+    {
+        let command_id = self.commands.new_id()?;
+        if !self.model.add_radio_item(command_id, label, group_id) {
+            return None;
+        }
+        self.model.set_checked(command_id, checked);
+        self.commands.actions.insert(command_id, Box::new(action));
+        Some(command_id)
+    }
+
+    pub fn add_sub_menu(&mut self, label: &str) -> Option<ContextMenu<'_>> {
+        let command_id = self.commands.new_id()?;
+        let model = self.model.add_sub_menu(command_id, label)?;
+        Some(ContextMenu {
+            model,
+            commands: &mut *self.commands,
+        })
+    }
+}
+
+/// Called when a context menu is opened in a browser, on the UI thread.
+pub trait ContextMenuHandler: Send + Sync {
+    /// Called before the menu is shown, to change its items. Clearing the menu prevents it from being shown.
+    fn on_before_context_menu(&self, _browser: &Browser, _frame: &Frame, _params: &ContextMenuParams,
+        _menu: &mut ContextMenu)
+    {
+    }
+
+    /// Called when an item without closure is chosen, like a default item or an item added to the model.
+    /// Returns true if the command is handled, or false to run the default command.
+    fn on_context_menu_command(&self, _browser: &Browser, _frame: &Frame, _params: &ContextMenuParams,
+        _command_id: i32, _modifiers: Modifiers) -> bool
+    {
+        false
+    }
+
+    /// Called when the menu is closed, whether an item was chosen or not.
+    fn on_context_menu_dismissed(&self, _browser: &Browser, _frame: &Frame) {
+    }
+}
+
+struct ContextMenuState {
+    handler: Arc<dyn ContextMenuHandler>,
+    // The commands of the last menu of each browser, by browser id.
+    commands: Mutex<HashMap<i32, Commands>>,
+}
+
+type ContextMenuHandlerRc = RefCounted<cef_context_menu_handler_t, ContextMenuState>;
+
+unsafe fn params(params: *mut cef_context_menu_params_t) -> Option<ContextMenuParams> {
+    if params.is_null() {
+        return None;
+    }
+    let copy = ContextMenuParams::from_raw(params);
+    release_base(params as *mut cef_base_ref_counted_t);
+    Some(copy)
+}
+
+pub(crate) fn wrap_context_menu_handler(handler: Arc<dyn ContextMenuHandler>) -> *mut cef_context_menu_handler_t {
+    extern "C" fn on_before_context_menu(self_: *mut cef_context_menu_handler_t, browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t, params: *mut cef_context_menu_params_t, model: *mut cef_menu_model_t)
+    {
+        let state = unsafe { ContextMenuHandlerRc::value(self_) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        let frame = unsafe { CefRc::<Frame>::from_raw(frame) };
+        let params = unsafe { self::params(params) };
+        let model = unsafe { CefRc::<MenuModel>::from_raw(model) };
+        if let (Some(browser), Some(frame), Some(params), Some(model)) = (browser, frame, params, model) {
+            // The lock is not held while the handler runs, since it could open another menu.
+            let mut commands = Commands::new();
+            state.handler.on_before_context_menu(&browser, &frame, &params, &mut ContextMenu {
+                model,
+                commands: &mut commands,
+            });
+            // The commands of the previous menu of this browser cannot be chosen anymore, and a menu without
+            // closure is not kept until it is dismissed.
+            let mut menus = state.commands.lock().unwrap_or_else(|error| error.into_inner());
+            let previous =
+                if commands.actions.is_empty() {
+                    menus.remove(&browser.identifier())
+                }
+                else {
+                    menus.insert(browser.identifier(), commands)
+                };
+            drop(menus);
+            drop(previous);
+        }
+    }
+
+    extern "C" fn on_context_menu_command(self_: *mut cef_context_menu_handler_t, browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t, params: *mut cef_context_menu_params_t, command_id: i32,
+        event_flags: cef_event_flags_t) -> i32
+    {
+        let state = unsafe { ContextMenuHandlerRc::value(self_) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        let frame = unsafe { CefRc::<Frame>::from_raw(frame) };
+        let params = unsafe { self::params(params) };
+        let (browser, frame, params) = match (browser, frame, params) {
+            (Some(browser), Some(frame), Some(params)) => (browser, frame, params),
+            _ => return 0,
+        };
+        let action = state.commands.lock().unwrap_or_else(|error| error.into_inner())
+            .get_mut(&browser.identifier())
+            .and_then(|commands| commands.actions.remove(&command_id));
+        match action {
+            Some(action) => {
+                action(&browser, &frame);
+                1
+            },
+            None => state.handler.on_context_menu_command(&browser, &frame, &params, command_id,
+                Modifiers::from_bits_truncate(event_flags)) as i32,
+        }
+    }
+
+    extern "C" fn on_context_menu_dismissed(self_: *mut cef_context_menu_handler_t, browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t)
+    {
+        let state = unsafe { ContextMenuHandlerRc::value(self_) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        let frame = unsafe { CefRc::<Frame>::from_raw(frame) };
+        if let (Some(browser), Some(frame)) = (browser, frame) {
+            // Drop the closures of the menu, and what they own, without holding the lock.
+            let commands = state.commands.lock().unwrap_or_else(|error| error.into_inner())
+                .remove(&browser.identifier());
+            drop(commands);
+            state.handler.on_context_menu_dismissed(&browser, &frame);
+        }
+    }
+
+    let mut raw: cef_context_menu_handler_t = unsafe { mem::zeroed() };
+    raw.on_before_context_menu = Some(on_before_context_menu);
+    raw.on_context_menu_command = Some(on_context_menu_command);
+    raw.on_context_menu_dismissed = Some(on_context_menu_dismissed);
+    RefCounted::wrap(raw, ContextMenuState {
+        handler,
+        commands: Mutex::new(HashMap::new()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use mock::{self, Recorder, TestClient};
+    use super::*;
+
+    // Adds an item opening the link to the menus of the links, and hides the menus of the images.
+    struct Handler(Arc<Recorder>);
+
+    impl ContextMenuHandler for Handler {
+        fn on_before_context_menu(&self, browser: &Browser, _frame: &Frame, params: &ContextMenuParams,
+            menu: &mut ContextMenu)
+        {
+            self.0.push(format!("before {} {:?} {}", browser.identifier(), params.type_flags, params.link_url));
+            if params.media_type == MediaType::Image {
+                menu.clear();
+                return;
+            }
+            let recorder = self.0.clone();
+            let link_url = params.link_url.clone();
+            menu.add_separator();
+            menu.add_item("Open link", move |browser, _frame| {
+                recorder.push(format!("open {} {}", browser.identifier(), link_url));
+            });
+            let mut sub_menu = menu.add_sub_menu("Zoom").expect("sub-menu");
+            sub_menu.add_radio_item("100%", 1, true, |_browser, _frame| {});
+        }
+
+        fn on_context_menu_command(&self, browser: &Browser, _frame: &Frame, params: &ContextMenuParams,
+            command_id: i32, modifiers: Modifiers) -> bool
+        {
+            self.0.push(format!("command {} {} {} {:?}", browser.identifier(), params.link_url, command_id,
+                modifiers));
+            command_id == 132
+        }
+
+        fn on_context_menu_dismissed(&self, browser: &Browser, _frame: &Frame) {
+            self.0.push(format!("dismissed {}", browser.identifier()));
+        }
+    }
+
+    fn link(url: &str) -> ContextMenuParams {
+        ContextMenuParams {
+            type_flags: ContextMenuTypeFlags::PAGE | ContextMenuTypeFlags::LINK,
+            link_url: url.to_string(),
+            ..ContextMenuParams::default()
+        }
+    }
+
+    #[test]
+    fn context_menu_handler() {
+        mock::run(|| {
+            let recorder = Arc::new(Recorder::default());
+            // The browsers share the handler, like the popups of a browser.
+            let handler: Arc<dyn ContextMenuHandler> = Arc::new(Handler(recorder.clone()));
+            let first = mock::create_browser(TestClient::new().context_menu(handler.clone()));
+            let second = mock::create_browser(TestClient::new().context_menu(handler));
+            let (first_id, second_id) = (first.identifier(), second.identifier());
+            let first_user_id = MENU_ID_USER_FIRST;
+            assert_eq!(mock::show_context_menu(first_id, &link("https://first/")), [
+                "100 Back".to_string(),
+                "101 Forward".to_string(),
+                "-".to_string(),
+                "132 View source".to_string(),
+                "-".to_string(),
+                format!("{} Open link", first_user_id),
+                format!("{} Zoom >", first_user_id + 1),
+                format!("  {} (x) 100%", first_user_id + 2),
+            ]);
+            assert_eq!(mock::show_context_menu(second_id, &link("https://second/")).len(), 8);
+            // Opening the menu of the second browser does not forget the items of the menu of the first one.
+            assert!(mock::choose_context_menu_item(first_id, first_user_id));
+            assert!(mock::choose_context_menu_item(second_id, 132));
+            assert!(mock::show_context_menu(first_id, &ContextMenuParams {
+                media_type: MediaType::Image,
+                ..ContextMenuParams::default()
+            }).is_empty());
+            assert_eq!(mock::show_context_menu(second_id, &link("https://third/")).len(), 8);
+            assert!(!mock::choose_context_menu_item(second_id, 100));
+            assert_eq!(recorder.events(), [
+                format!("before {} PAGE | LINK https://first/", first_id),
+                format!("before {} PAGE | LINK https://second/", second_id),
+                format!("open {} https://first/", first_id),
+                format!("dismissed {}", first_id),
+                format!("command {} https://second/ 132 (empty)", second_id),
+                format!("dismissed {}", second_id),
+                format!("before {} (empty) ", first_id),
+                format!("dismissed {}", first_id),
+                format!("before {} PAGE | LINK https://third/", second_id),
+                format!("command {} https://third/ 100 (empty)", second_id),
+                format!("dismissed {}", second_id),
+            ]);
+            // A menu closed without choosing an item is only dismissed.
+            assert_eq!(mock::show_context_menu(first_id, &link("https://fourth/")).len(), 8);
+            mock::dismiss_context_menu(first_id);
+            assert!(!mock::choose_context_menu_item(first_id, first_user_id));
+            assert_eq!(recorder.events()[11..], [
+                format!("before {} PAGE | LINK https://fourth/", first_id),
+                format!("dismissed {}", first_id),
+            ]);
+        });
+    }
+
+    // Adds an item owning `owned` to each menu, then clears the menus of the images.
+    struct Owning(Arc<Recorder>, Arc<()>);
+
+    impl ContextMenuHandler for Owning {
+        fn on_before_context_menu(&self, _browser: &Browser, _frame: &Frame, params: &ContextMenuParams,
+            menu: &mut ContextMenu)
+        {
+            let (recorder, owned) = (self.0.clone(), self.1.clone());
+            menu.add_item("Chosen", move |_browser, _frame| {
+                drop(owned);
+                recorder.push("chosen");
+            });
+            if params.media_type == MediaType::Image {
+                menu.clear();
+            }
+        }
+    }
+
+    #[test]
+    fn dropped_commands() {
+        mock::run(|| {
+            let recorder = Arc::new(Recorder::default());
+            let owned = Arc::new(());
+            let handler = Arc::new(Owning(recorder.clone(), owned.clone()));
+            let browser = mock::create_browser(TestClient::new().context_menu(handler));
+            let id = browser.identifier();
+            let image = ContextMenuParams {
+                media_type: MediaType::Image,
+                ..ContextMenuParams::default()
+            };
+            assert!(mock::show_context_menu(id, &image).is_empty());
+            assert_eq!(Arc::strong_count(&owned), 2);
+            assert!(!mock::choose_context_menu_item(id, MENU_ID_USER_FIRST));
+            mock::dismiss_context_menu(id);
+
+            assert_eq!(mock::show_context_menu(id, &link("https://first/")).len(), 5);
+            assert_eq!(Arc::strong_count(&owned), 3);
+            mock::dismiss_context_menu(id);
+            assert_eq!(Arc::strong_count(&owned), 2);
+            assert!(recorder.events().is_empty());
+
+            mock::show_context_menu(id, &link("https://second/"));
+            assert!(mock::choose_context_menu_item(id, MENU_ID_USER_FIRST));
+            assert_eq!(Arc::strong_count(&owned), 2);
+            assert_eq!(recorder.events(), ["chosen"]);
+        });
+    }
+}
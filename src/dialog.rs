@@ -0,0 +1,336 @@
+use std::collections::VecDeque;
+use std::mem;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use browser::Browser;
+use rc::{CefRc, RefCounted};
+use string::{CefStr, CefString, string_list};
+use sys::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileDialogMode {
+    /// Choose an existing file.
+    Open,
+    /// Choose one or more existing files.
+    OpenMultiple,
+    /// Choose an existing folder.
+    OpenFolder,
+    /// Choose a file to write, which may not exist.
+    Save,
+}
+
+impl FileDialogMode {
+    /// The flags of the mode are ignored.
+    pub fn from_raw(mode: cef_file_dialog_mode_t) -> Option<Self> {
+        match mode & FILE_DIALOG_TYPE_MASK {
+            FILE_DIALOG_OPEN => Some(FileDialogMode::Open),
+            FILE_DIALOG_OPEN_MULTIPLE => Some(FileDialogMode::OpenMultiple),
+            FILE_DIALOG_OPEN_FOLDER => Some(FileDialogMode::OpenFolder),
+            FILE_DIALOG_SAVE => Some(FileDialogMode::Save),
+            _ => None,
+        }
+    }
+
+    pub fn to_raw(self) -> cef_file_dialog_mode_t {
+        match self {
+            FileDialogMode::Open => FILE_DIALOG_OPEN,
+            FileDialogMode::OpenMultiple => FILE_DIALOG_OPEN_MULTIPLE,
+            FileDialogMode::OpenFolder => FILE_DIALOG_OPEN_FOLDER,
+            FileDialogMode::Save => FILE_DIALOG_SAVE,
+        }
+    }
+}
+
+/// A file chooser opened by a page, like with `<input type="file">`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileDialogRequest {
+    pub mode: FileDialogMode,
+    /// The title of the dialog, or empty for the default one.
+    pub title: String,
+    /// The file or the folder initially selected.
+    pub default_path: Option<PathBuf>,
+    /// The types of the files accepted, either MIME types like `"image/*"`, extensions like `".png"`, or
+    /// descriptions followed by extensions like `"Images|.png;.jpg"`.
+    pub accept_filters: Vec<String>,
+    /// The index of the filter selected by default.
+    pub selected_accept_filter: usize,
+    /// Whether to ask before overwriting an existing file, in `Save` mode.
+    pub overwrite_prompt: bool,
+    /// Whether to hide the read-only files.
+    pub hide_read_only: bool,
+}
+
+#[repr(transparent)]
+struct RawFileDialogCallback(cef_file_dialog_callback_t);
+
+cef_object!(RawFileDialogCallback, cef_file_dialog_callback_t);
+
+/// Gives the chosen paths to a file chooser, possibly after the handler returned.
+pub struct FileDialogCallback {
+    raw: CefRc<RawFileDialogCallback>,
+    selected_accept_filter: usize,
+}
+
+impl FileDialogCallback {
+    /// Choose files, or a single file or folder depending on the mode. The paths must be valid Unicode.
+    pub fn select(self, paths: Vec<PathBuf>) {
+        let filter = self.selected_accept_filter;
+        self.select_with_filter(filter, paths);
+    }
+
+    /// Choose files, with the index of the accept filter selected in the dialog.
+    pub fn select_with_filter(self, selected_accept_filter: usize, paths: Vec<PathBuf>) {
+        unsafe {
+            let list = cef_string_list_alloc();
+            for path in &paths {
+                cef_string_list_append(list, CefString::new(&path.to_string_lossy()).as_raw());
+            }
+            call!(&self.raw.0, cont(selected_accept_filter as i32, list));
+            cef_string_list_free(list);
+        }
+    }
+
+    pub fn cancel(self) {
+        call!(&self.raw.0, cancel());
+    }
+}
+
+/// Called when a page opens a file chooser, on the UI thread.
+pub trait DialogHandler: Send + Sync {
+    /// Return true to answer with `callback`, or false to let CEF show its own file chooser.
+    fn on_file_dialog(&self, _browser: &Browser, _request: &FileDialogRequest, _callback: FileDialogCallback)
+        -> bool
+    {
+        false
+    }
+}
+
+/// Answer the file choosers with a list of answers given in advance, like in tests. The dialogs opened once the
+/// answers are used up are canceled.
+#[derive(Default)]
+pub struct ScriptedDialogHandler {
+    answers: Mutex<VecDeque<Option<Vec<PathBuf>>>>,
+    requests: Mutex<Vec<FileDialogRequest>>,
+}
+
+impl ScriptedDialogHandler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Choose these paths in the next dialog.
+    pub fn push_selection(&self, paths: Vec<PathBuf>) {
+        self.answers.lock().unwrap_or_else(|error| error.into_inner()).push_back(Some(paths));
+    }
+
+    /// Cancel the next dialog.
+    pub fn push_cancel(&self) {
+        self.answers.lock().unwrap_or_else(|error| error.into_inner()).push_back(None);
+    }
+
+    /// The dialogs opened so far.
+    pub fn requests(&self) -> Vec<FileDialogRequest> {
+        self.requests.lock().unwrap_or_else(|error| error.into_inner()).clone()
+    }
+}
+
+impl DialogHandler for ScriptedDialogHandler {
+    fn on_file_dialog(&self, _browser: &Browser, request: &FileDialogRequest, callback: FileDialogCallback)
+        -> bool
+    {
+        self.requests.lock().unwrap_or_else(|error| error.into_inner()).push(request.clone());
+        let answer = self.answers.lock().unwrap_or_else(|error| error.into_inner()).pop_front();
+        match answer {
+            Some(Some(paths)) => callback.select(paths),
+            Some(None) | None => callback.cancel(),
+        }
+        true
+    }
+}
+
+/// Show the file choosers in a GTK `FileChooserDialog`, which blocks until it is closed.
+///
+/// Only the extensions of the accept filters are used, since GTK does not know the MIME types of the page.
+#[cfg(feature = "gtk")]
+pub struct GtkDialogHandler;
+
+#[cfg(feature = "gtk")]
+impl DialogHandler for GtkDialogHandler {
+    fn on_file_dialog(&self, _browser: &Browser, request: &FileDialogRequest, callback: FileDialogCallback)
+        -> bool
+    {
+        use gtk::{DialogExt, FileChooserAction, FileChooserDialog, FileChooserExt, FileFilter, ResponseType,
+            WidgetExt, Window};
+
+        let (action, accept) = match request.mode {
+            FileDialogMode::Open | FileDialogMode::OpenMultiple => (FileChooserAction::Open, "_Open"),
+            FileDialogMode::OpenFolder => (FileChooserAction::SelectFolder, "_Select"),
+            FileDialogMode::Save => (FileChooserAction::Save, "_Save"),
+        };
+        let title = if request.title.is_empty() { None } else { Some(request.title.as_str()) };
+        let dialog = FileChooserDialog::with_buttons(title, None::<&Window>, action,
+            &[("_Cancel", ResponseType::Cancel), (accept, ResponseType::Accept)]);
+        dialog.set_select_multiple(request.mode == FileDialogMode::OpenMultiple);
+        dialog.set_do_overwrite_confirmation(request.overwrite_prompt);
+        if let Some(ref path) = request.default_path {
+            if request.mode == FileDialogMode::Save && !path.is_dir() {
+                if let Some(folder) = path.parent().filter(|folder| folder.is_dir()) {
+                    dialog.set_current_folder(folder);
+                }
+                if let Some(name) = path.file_name() {
+                    dialog.set_current_name(&name.to_string_lossy());
+                }
+            }
+            else {
+                dialog.set_filename(path);
+            }
+        }
+        for filter in &request.accept_filters {
+            if let Some((name, patterns)) = filter_patterns(filter) {
+                let file_filter = FileFilter::new();
+                file_filter.set_name(Some(name));
+                for pattern in &patterns {
+                    file_filter.add_pattern(pattern);
+                }
+                dialog.add_filter(&file_filter);
+            }
+        }
+        let response = ResponseType::from(dialog.run());
+        let paths = dialog.get_filenames();
+        dialog.destroy();
+        if response == ResponseType::Accept && !paths.is_empty() {
+            callback.select(paths);
+        }
+        else {
+            callback.cancel();
+        }
+        true
+    }
+}
+
+// The name and the glob patterns of an accept filter, which is a description followed by the extensions, or a
+// single MIME type or extension. The filters without extension are skipped.
+#[cfg(any(feature = "gtk", test))]
+fn filter_patterns(filter: &str) -> Option<(&str, Vec<String>)> {
+    let (name, extensions) = match filter.find('|') {
+        Some(index) => (&filter[..index], &filter[index + 1..]),
+        None => (filter, filter),
+    };
+    let patterns: Vec<_> = extensions.split(';')
+        .filter(|extension| extension.starts_with('.'))
+        .map(|extension| format!("*{}", extension))
+        .collect();
+    if patterns.is_empty() {
+        None
+    }
+    else {
+        Some((name, patterns))
+    }
+}
+
+type DialogHandlerRc = RefCounted<cef_dialog_handler_t, Arc<dyn DialogHandler>>;
+
+pub(crate) fn wrap_dialog_handler(handler: Arc<dyn DialogHandler>) -> *mut cef_dialog_handler_t {
+    extern "C" fn on_file_dialog(self_: *mut cef_dialog_handler_t, browser: *mut cef_browser_t,
+        mode: cef_file_dialog_mode_t, title: *const cef_string_t, default_file_path: *const cef_string_t,
+        accept_filters: cef_string_list_t, selected_accept_filter: i32, callback: *mut cef_file_dialog_callback_t)
+        -> i32
+    {
+        let handler = unsafe { DialogHandlerRc::value(self_) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        let callback = unsafe { CefRc::<RawFileDialogCallback>::from_raw(callback) };
+        let (browser, callback, dialog_mode) = match (browser, callback, FileDialogMode::from_raw(mode)) {
+            (Some(browser), Some(callback), Some(dialog_mode)) => (browser, callback, dialog_mode),
+            _ => return 0,
+        };
+        let default_path = unsafe { CefStr::from_ptr(default_file_path) }.to_string();
+        let request = FileDialogRequest {
+            mode: dialog_mode,
+            title: unsafe { CefStr::from_ptr(title) }.to_string(),
+            default_path: if default_path.is_empty() { None } else { Some(PathBuf::from(default_path)) },
+            accept_filters: unsafe { string_list(accept_filters) },
+            selected_accept_filter: selected_accept_filter.max(0) as usize,
+            overwrite_prompt: mode & FILE_DIALOG_OVERWRITEPROMPT_FLAG != 0,
+            hide_read_only: mode & FILE_DIALOG_HIDEREADONLY_FLAG != 0,
+        };
+        let callback = FileDialogCallback {
+            raw: callback,
+            selected_accept_filter: request.selected_accept_filter,
+        };
+        handler.on_file_dialog(&browser, &request, callback) as i32
+    }
+
+    let mut raw: cef_dialog_handler_t = unsafe { mem::zeroed() };
+    raw.on_file_dialog = Some(on_file_dialog);
+    RefCounted::wrap(raw, handler)
+}
+
+#[cfg(test)]
+mod tests {
+    use mock::{self, TestClient};
+    use super::*;
+
+    fn answers() -> Vec<String> {
+        mock::calls().into_iter().filter(|call| call.starts_with("FileDialogCallback")).collect()
+    }
+
+    #[test]
+    fn scripted_dialogs() {
+        mock::run(|| {
+            let handler = Arc::new(ScriptedDialogHandler::new());
+            handler.push_selection(vec![PathBuf::from("/tmp/a.txt"), PathBuf::from("/tmp/b.txt")]);
+            handler.push_cancel();
+            let browser = mock::create_browser(TestClient::new().dialog(handler.clone()));
+            let id = browser.identifier();
+            let open = FileDialogRequest {
+                mode: FileDialogMode::OpenMultiple,
+                title: String::new(),
+                default_path: None,
+                accept_filters: vec!["image/*".to_string(), "Text|.txt;.md".to_string()],
+                selected_accept_filter: 1,
+                overwrite_prompt: false,
+                hide_read_only: true,
+            };
+            let save = FileDialogRequest {
+                mode: FileDialogMode::Save,
+                title: "Save as".to_string(),
+                default_path: Some(PathBuf::from("/tmp/page.html")),
+                accept_filters: vec![],
+                selected_accept_filter: 0,
+                overwrite_prompt: true,
+                hide_read_only: false,
+            };
+            mock::clear_calls();
+            assert!(mock::file_dialog(id, &open));
+            assert!(mock::file_dialog(id, &save));
+            // Once the answers are used up, the dialogs are canceled.
+            assert!(mock::file_dialog(id, &open));
+            assert_eq!(answers(), [
+                "FileDialogCallback::cont 1 /tmp/a.txt;/tmp/b.txt",
+                "FileDialogCallback::cancel",
+                "FileDialogCallback::cancel",
+            ]);
+            assert_eq!(handler.requests(), [open.clone(), save, open]);
+        });
+    }
+
+    #[test]
+    fn modes() {
+        for &mode in &[FileDialogMode::Open, FileDialogMode::OpenMultiple, FileDialogMode::OpenFolder,
+            FileDialogMode::Save]
+        {
+            assert_eq!(FileDialogMode::from_raw(mode.to_raw() | FILE_DIALOG_OVERWRITEPROMPT_FLAG), Some(mode));
+        }
+        assert_eq!(FileDialogMode::from_raw(FILE_DIALOG_TYPE_MASK), None);
+    }
+
+    #[test]
+    fn filter_patterns() {
+        assert_eq!(super::filter_patterns("Text|.txt;.md"), Some(("Text", vec!["*.txt".to_string(),
+            "*.md".to_string()])));
+        assert_eq!(super::filter_patterns(".png"), Some((".png", vec!["*.png".to_string()])));
+        assert_eq!(super::filter_patterns("image/*"), None);
+        assert_eq!(super::filter_patterns("Images|image/*"), None);
+    }
+}
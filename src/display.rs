@@ -0,0 +1,228 @@
+use std::mem;
+use std::sync::Arc;
+
+use browser::{Browser, Frame};
+use rc::{CefRc, RefCounted};
+use settings::LogSeverity;
+use string::{CefStr, CefString, string_list};
+use sys::*;
+
+/// A message written to the console of a page, like with `console.log()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsoleMessage {
+    pub level: LogSeverity,
+    pub text: String,
+    /// The URL of the script, or empty.
+    pub source: String,
+    pub line: i32,
+}
+
+/// Called when what is shown around a browser changes, like its title or its URL.
+pub trait DisplayHandler: Send + Sync {
+    fn on_address_change(&self, _browser: &Browser, _frame: &Frame, _url: &str) {
+    }
+
+    fn on_title_change(&self, _browser: &Browser, _title: &str) {
+    }
+
+    fn on_favicon_url_change(&self, _browser: &Browser, _icon_urls: Vec<String>) {
+    }
+
+    /// The page entered or left fullscreen, like with the fullscreen API of JavaScript.
+    fn on_fullscreen_mode_change(&self, _browser: &Browser, _fullscreen: bool) {
+    }
+
+    /// Returns true to show the tooltip itself. Otherwise, the text, which can be changed, is shown by CEF.
+    fn on_tooltip(&self, _browser: &Browser, _text: &mut String) -> bool {
+        false
+    }
+
+    /// `value` is the text of the status bar, like the URL of the hovered link, or empty to clear it.
+    fn on_status_message(&self, _browser: &Browser, _value: &str) {
+    }
+
+    /// Returns true to stop the message from being written to the console log.
+    fn on_console_message(&self, _browser: &Browser, _message: &ConsoleMessage) -> bool {
+        false
+    }
+
+    /// Only called when auto-resize is enabled. Returns true if the browser was resized.
+    fn on_auto_resize(&self, _browser: &Browser, _width: i32, _height: i32) -> bool {
+        false
+    }
+
+    /// `progress` goes from 0.0 to 1.0.
+    fn on_loading_progress_change(&self, _browser: &Browser, _progress: f64) {
+    }
+}
+
+type DisplayHandlerRc = RefCounted<cef_display_handler_t, Arc<dyn DisplayHandler>>;
+
+pub(crate) fn wrap_display_handler(handler: Arc<dyn DisplayHandler>) -> *mut cef_display_handler_t {
+    extern "C" fn on_address_change(self_: *mut cef_display_handler_t, browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t, url: *const cef_string_t)
+    {
+        let handler = unsafe { DisplayHandlerRc::value(self_) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        let frame = unsafe { CefRc::<Frame>::from_raw(frame) };
+        if let (Some(browser), Some(frame)) = (browser, frame) {
+            let url = unsafe { CefStr::from_ptr(url) }.to_string();
+            handler.on_address_change(&browser, &frame, &url);
+        }
+    }
+
+    extern "C" fn on_title_change(self_: *mut cef_display_handler_t, browser: *mut cef_browser_t,
+        title: *const cef_string_t)
+    {
+        let handler = unsafe { DisplayHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            let title = unsafe { CefStr::from_ptr(title) }.to_string();
+            handler.on_title_change(&browser, &title);
+        }
+    }
+
+    extern "C" fn on_favicon_urlchange(self_: *mut cef_display_handler_t, browser: *mut cef_browser_t,
+        icon_urls: cef_string_list_t)
+    {
+        let handler = unsafe { DisplayHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            handler.on_favicon_url_change(&browser, unsafe { string_list(icon_urls) });
+        }
+    }
+
+    extern "C" fn on_fullscreen_mode_change(self_: *mut cef_display_handler_t, browser: *mut cef_browser_t,
+        fullscreen: i32)
+    {
+        let handler = unsafe { DisplayHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            handler.on_fullscreen_mode_change(&browser, fullscreen != 0);
+        }
+    }
+
+    extern "C" fn on_tooltip(self_: *mut cef_display_handler_t, browser: *mut cef_browser_t,
+        text: *mut cef_string_t) -> i32
+    {
+        let handler = unsafe { DisplayHandlerRc::value(self_) };
+        let browser = match unsafe { CefRc::<Browser>::from_raw(browser) } {
+            Some(browser) => browser,
+            None => return 0,
+        };
+        let original = unsafe { CefStr::from_ptr(text) }.to_string();
+        let mut tooltip = original.clone();
+        let handled = handler.on_tooltip(&browser, &mut tooltip);
+        if tooltip != original && !text.is_null() {
+            unsafe { CefString::new(&tooltip).copy_to(text) };
+        }
+        handled as i32
+    }
+
+    extern "C" fn on_status_message(self_: *mut cef_display_handler_t, browser: *mut cef_browser_t,
+        value: *const cef_string_t)
+    {
+        let handler = unsafe { DisplayHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            let value = unsafe { CefStr::from_ptr(value) }.to_string();
+            handler.on_status_message(&browser, &value);
+        }
+    }
+
+    extern "C" fn on_console_message(self_: *mut cef_display_handler_t, browser: *mut cef_browser_t,
+        level: cef_log_severity_t, message: *const cef_string_t, source: *const cef_string_t, line: i32) -> i32
+    {
+        let handler = unsafe { DisplayHandlerRc::value(self_) };
+        let browser = match unsafe { CefRc::<Browser>::from_raw(browser) } {
+            Some(browser) => browser,
+            None => return 0,
+        };
+        let message = ConsoleMessage {
+            level: LogSeverity::from_raw(level),
+            text: unsafe { CefStr::from_ptr(message) }.to_string(),
+            source: unsafe { CefStr::from_ptr(source) }.to_string(),
+            line,
+        };
+        handler.on_console_message(&browser, &message) as i32
+    }
+
+    extern "C" fn on_auto_resize(self_: *mut cef_display_handler_t, browser: *mut cef_browser_t,
+        new_size: *const cef_size_t) -> i32
+    {
+        let handler = unsafe { DisplayHandlerRc::value(self_) };
+        let browser = match unsafe { CefRc::<Browser>::from_raw(browser) } {
+            Some(browser) => browser,
+            None => return 0,
+        };
+        if new_size.is_null() {
+            return 0;
+        }
+        let new_size = unsafe { &*new_size };
+        handler.on_auto_resize(&browser, new_size.width, new_size.height) as i32
+    }
+
+    extern "C" fn on_loading_progress_change(self_: *mut cef_display_handler_t, browser: *mut cef_browser_t,
+        progress: f64)
+    {
+        let handler = unsafe { DisplayHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            handler.on_loading_progress_change(&browser, progress);
+        }
+    }
+
+    let mut raw: cef_display_handler_t = unsafe { mem::zeroed() };
+    raw.on_address_change = Some(on_address_change);
+    raw.on_title_change = Some(on_title_change);
+    raw.on_favicon_urlchange = Some(on_favicon_urlchange);
+    raw.on_fullscreen_mode_change = Some(on_fullscreen_mode_change);
+    raw.on_tooltip = Some(on_tooltip);
+    raw.on_status_message = Some(on_status_message);
+    raw.on_console_message = Some(on_console_message);
+    raw.on_auto_resize = Some(on_auto_resize);
+    raw.on_loading_progress_change = Some(on_loading_progress_change);
+    RefCounted::wrap(raw, handler)
+}
+
+#[cfg(test)]
+mod tests {
+    use mock::{self, Recorder, TestClient};
+    use super::*;
+
+    impl DisplayHandler for Recorder {
+        fn on_address_change(&self, browser: &Browser, frame: &Frame, url: &str) {
+            let main = frame.identifier() == browser.main_frame().unwrap().identifier();
+            self.push(format!("address {} {}", url, main));
+        }
+
+        fn on_favicon_url_change(&self, _browser: &Browser, icon_urls: Vec<String>) {
+            self.push(format!("favicons {:?}", icon_urls));
+        }
+
+        fn on_console_message(&self, _browser: &Browser, message: &ConsoleMessage) -> bool {
+            self.push(format!("console {:?}", message));
+            message.level == LogSeverity::Error
+        }
+
+        fn on_loading_progress_change(&self, _browser: &Browser, progress: f64) {
+            self.push(format!("progress {}", progress));
+        }
+    }
+
+    #[test]
+    fn display_handler() {
+        mock::run(|| {
+            let recorder = Arc::new(Recorder::default());
+            let browser = mock::create_browser(TestClient::new().display(recorder.clone()));
+            let id = browser.identifier();
+            mock::address_change(id, mock::main_frame_id(id).unwrap(), "https://example.com/page");
+            mock::favicon_url_change(id, &["https://example.com/a.ico", "https://example.com/b.png"]);
+            mock::loading_progress_change(id, 0.5);
+            assert!(!mock::console_message(id, cef_log_severity_t::LOGSEVERITY_INFO, "hello", "script.js", 3));
+            assert!(mock::console_message(id, cef_log_severity_t::LOGSEVERITY_ERROR, "failure", "", 0));
+            assert_eq!(recorder.events(), [
+                "address https://example.com/page true",
+                "favicons [\"https://example.com/a.ico\", \"https://example.com/b.png\"]",
+                "progress 0.5",
+                "console ConsoleMessage { level: Info, text: \"hello\", source: \"script.js\", line: 3 }",
+                "console ConsoleMessage { level: Error, text: \"failure\", source: \"\", line: 0 }",
+            ]);
+        });
+    }
+}
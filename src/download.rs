@@ -0,0 +1,388 @@
+use std::collections::HashSet;
+use std::fs;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use browser::Browser;
+use rc::{CefRc, RefCounted, release_base};
+use string::{CefStr, CefString};
+use sys::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DownloadState {
+    InProgress,
+    Complete,
+    /// Canceled, or interrupted by an error.
+    Canceled,
+}
+
+/// A download, as it was when the handler was called.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DownloadItem {
+    /// Unique during the life of the application.
+    pub id: u32,
+    pub state: DownloadState,
+    pub url: String,
+    /// The URL before the redirections.
+    pub original_url: String,
+    pub suggested_file_name: String,
+    pub content_disposition: String,
+    pub mime_type: String,
+    /// Where the file is saved, once it is chosen.
+    pub full_path: Option<PathBuf>,
+    pub received_bytes: u64,
+    /// `None` when the size is unknown.
+    pub total_bytes: Option<u64>,
+    /// From 0 to 100, or `None` when the size is unknown.
+    pub percent_complete: Option<u8>,
+    /// In bytes per second.
+    pub current_speed: u64,
+}
+
+impl DownloadItem {
+    /// Copy the download item, which CEF only keeps during the callback.
+    ///
+    /// # Safety
+    ///
+    /// `item` must be a valid pointer.
+    pub unsafe fn from_raw(item: *mut cef_download_item_t) -> Self {
+        let item = &*item;
+        let string = |string| CefString::from_userfree(string).to_string();
+        let state =
+            if call!(item, is_complete()) != 0 {
+                DownloadState::Complete
+            }
+            else if call!(item, is_canceled()) != 0 {
+                DownloadState::Canceled
+            }
+            else {
+                DownloadState::InProgress
+            };
+        let full_path = string(call!(item, get_full_path()));
+        let total_bytes = call!(item, get_total_bytes());
+        let percent_complete = call!(item, get_percent_complete());
+        DownloadItem {
+            id: call!(item, get_id()),
+            state,
+            url: string(call!(item, get_url())),
+            original_url: string(call!(item, get_original_url())),
+            suggested_file_name: string(call!(item, get_suggested_file_name())),
+            content_disposition: string(call!(item, get_content_disposition())),
+            mime_type: string(call!(item, get_mime_type())),
+            full_path: if full_path.is_empty() { None } else { Some(PathBuf::from(full_path)) },
+            received_bytes: call!(item, get_received_bytes()).max(0) as u64,
+            total_bytes: if total_bytes >= 0 { Some(total_bytes as u64) } else { None },
+            percent_complete: if percent_complete >= 0 { Some(percent_complete.min(100) as u8) } else { None },
+            current_speed: call!(item, get_current_speed()).max(0) as u64,
+        }
+    }
+}
+
+/// What to do with a download which is about to start.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DownloadDecision {
+    /// Save the file to this path, which must be valid Unicode.
+    SaveTo(PathBuf),
+    /// Let the user choose where to save the file, in a dialog starting with this path, or the suggested name
+    /// when `None`.
+    Prompt(Option<PathBuf>),
+    Cancel,
+}
+
+#[repr(transparent)]
+struct RawDownloadItemCallback(cef_download_item_callback_t);
+
+cef_object!(RawDownloadItemCallback, cef_download_item_callback_t);
+
+/// Controls a download in progress, from any thread.
+#[derive(Clone)]
+pub struct DownloadItemCallback {
+    raw: CefRc<RawDownloadItemCallback>,
+}
+
+impl DownloadItemCallback {
+    pub fn cancel(&self) {
+        call!(&self.raw.0, cancel());
+    }
+
+    pub fn pause(&self) {
+        call!(&self.raw.0, pause());
+    }
+
+    pub fn resume(&self) {
+        call!(&self.raw.0, resume());
+    }
+}
+
+/// Called when a page downloads a file, on the UI thread.
+///
+/// Without a handler, the downloads are canceled.
+pub trait DownloadHandler: Send + Sync {
+    /// Called before the download starts. The item does not have a full path yet.
+    fn on_before_download(&self, _browser: &Browser, _item: &DownloadItem, _suggested_name: &str)
+        -> DownloadDecision
+    {
+        DownloadDecision::Cancel
+    }
+
+    /// Called when the state or the progress of a download changes. The callback can be kept to control the
+    /// download later.
+    fn on_download_updated(&self, _browser: &Browser, _item: &DownloadItem, _callback: &DownloadItemCallback) {
+    }
+}
+
+/// Save the downloads into a directory, adding a number to the names of the files which already exist, like
+/// `file (1).txt`.
+pub struct DownloadDirectory {
+    directory: PathBuf,
+    // The paths given to the downloads in progress, whose file might not be created yet.
+    reserved: Mutex<HashSet<PathBuf>>,
+}
+
+impl DownloadDirectory {
+    /// The directory is created when the first download starts, if it does not exist. The downloads are canceled
+    /// when it cannot be created.
+    pub fn new<P: Into<PathBuf>>(directory: P) -> Self {
+        DownloadDirectory {
+            directory: directory.into(),
+            reserved: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// A path in the directory for a file named `name` which is not used yet.
+    pub fn unique_path(&self, name: &str) -> PathBuf {
+        // Only keep the file name, so that the name cannot point out of the directory.
+        let name = Path::new(name).file_name()
+            .and_then(|name| name.to_str())
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or("download");
+        let (stem, extension) = match name.rfind('.') {
+            Some(index) if index > 0 => name.split_at(index),
+            _ => (name, ""),
+        };
+        let mut reserved = self.reserved.lock().unwrap_or_else(|error| error.into_inner());
+        let mut path = self.directory.join(name);
+        let mut number = 1;
+        while path.exists() || reserved.contains(&path) {
+            path = self.directory.join(format!("{} ({}){}", stem, number, extension));
+            number += 1;
+        }
+        reserved.insert(path.clone());
+        path
+    }
+}
+
+impl DownloadHandler for DownloadDirectory {
+    fn on_before_download(&self, _browser: &Browser, _item: &DownloadItem, suggested_name: &str)
+        -> DownloadDecision
+    {
+        if fs::create_dir_all(&self.directory).is_err() {
+            return DownloadDecision::Cancel;
+        }
+        DownloadDecision::SaveTo(self.unique_path(suggested_name))
+    }
+
+    fn on_download_updated(&self, _browser: &Browser, item: &DownloadItem, _callback: &DownloadItemCallback) {
+        if item.state == DownloadState::InProgress {
+            return;
+        }
+        if let Some(ref path) = item.full_path {
+            self.reserved.lock().unwrap_or_else(|error| error.into_inner()).remove(path);
+        }
+    }
+}
+
+type DownloadHandlerRc = RefCounted<cef_download_handler_t, Arc<dyn DownloadHandler>>;
+
+pub(crate) fn wrap_download_handler(handler: Arc<dyn DownloadHandler>) -> *mut cef_download_handler_t {
+    extern "C" fn on_before_download(self_: *mut cef_download_handler_t, browser: *mut cef_browser_t,
+        download_item: *mut cef_download_item_t, suggested_name: *const cef_string_t,
+        callback: *mut cef_before_download_callback_t)
+    {
+        let handler = unsafe { DownloadHandlerRc::value(self_) };
+        let item = unsafe { take_item(download_item) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        let decision = match (browser, item) {
+            (Some(browser), Some(item)) => {
+                let suggested_name = unsafe { CefStr::from_ptr(suggested_name) }.to_string();
+                handler.on_before_download(&browser, &item, &suggested_name)
+            },
+            _ => DownloadDecision::Cancel,
+        };
+        if callback.is_null() {
+            return;
+        }
+        let (path, show_dialog) = match decision {
+            DownloadDecision::SaveTo(path) => (Some(path), false),
+            DownloadDecision::Prompt(path) => (path, true),
+            // The download is canceled when the callback is not called.
+            DownloadDecision::Cancel => (None, false),
+        };
+        if path.is_some() || show_dialog {
+            let path = CefString::new(&path.map(|path| path.to_string_lossy().into_owned()).unwrap_or_default());
+            call!(unsafe { &*callback }, cont(path.as_raw(), show_dialog as i32));
+        }
+        unsafe { release_base(callback as *mut cef_base_ref_counted_t) };
+    }
+
+    extern "C" fn on_download_updated(self_: *mut cef_download_handler_t, browser: *mut cef_browser_t,
+        download_item: *mut cef_download_item_t, callback: *mut cef_download_item_callback_t)
+    {
+        let handler = unsafe { DownloadHandlerRc::value(self_) };
+        let item = unsafe { take_item(download_item) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        let callback = unsafe { CefRc::<RawDownloadItemCallback>::from_raw(callback) };
+        if let (Some(browser), Some(item), Some(callback)) = (browser, item, callback) {
+            handler.on_download_updated(&browser, &item, &DownloadItemCallback { raw: callback });
+        }
+    }
+
+    let mut raw: cef_download_handler_t = unsafe { mem::zeroed() };
+    raw.on_before_download = Some(on_before_download);
+    raw.on_download_updated = Some(on_download_updated);
+    RefCounted::wrap(raw, handler)
+}
+
+// Copy a download item received by a callback and release it.
+unsafe fn take_item(item: *mut cef_download_item_t) -> Option<DownloadItem> {
+    if item.is_null() {
+        return None;
+    }
+    let copy = DownloadItem::from_raw(item);
+    release_base(item as *mut cef_base_ref_counted_t);
+    Some(copy)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+
+    use mock::{self, Recorder, TestClient};
+    use super::*;
+
+    // Saves the text files, asks where to save the images, cancels the other files, and pauses the downloads
+    // whose size is unknown.
+    impl DownloadHandler for Recorder {
+        fn on_before_download(&self, _browser: &Browser, item: &DownloadItem, suggested_name: &str)
+            -> DownloadDecision
+        {
+            self.push(format!("before {} {} {}", item.id, item.mime_type, suggested_name));
+            match item.mime_type.as_str() {
+                "text/plain" => DownloadDecision::SaveTo(PathBuf::from("/downloads").join(suggested_name)),
+                "image/png" => DownloadDecision::Prompt(None),
+                _ => DownloadDecision::Cancel,
+            }
+        }
+
+        fn on_download_updated(&self, _browser: &Browser, item: &DownloadItem, callback: &DownloadItemCallback) {
+            self.push(format!("updated {} {:?} {:?} {:?} {:?}", item.id, item.state,
+                item.full_path, item.total_bytes, item.percent_complete));
+            if item.total_bytes.is_none() {
+                callback.pause();
+            }
+        }
+    }
+
+    fn item(id: u32, mime_type: &str, name: &str) -> DownloadItem {
+        DownloadItem {
+            id,
+            state: DownloadState::InProgress,
+            url: format!("https://example.com/{}", name),
+            original_url: format!("https://example.com/{}", name),
+            suggested_file_name: name.to_string(),
+            content_disposition: String::new(),
+            mime_type: mime_type.to_string(),
+            full_path: None,
+            received_bytes: 0,
+            total_bytes: None,
+            percent_complete: None,
+            current_speed: 0,
+        }
+    }
+
+    fn decisions() -> Vec<String> {
+        mock::calls().into_iter()
+            .filter(|call| call.starts_with("BeforeDownloadCallback") || call.starts_with("DownloadItemCallback"))
+            .collect()
+    }
+
+    #[test]
+    fn download_handler() {
+        mock::run(|| {
+            let recorder = Arc::new(Recorder::default());
+            let browser = mock::create_browser(TestClient::new().download(recorder.clone()));
+            let id = browser.identifier();
+            mock::clear_calls();
+            mock::before_download(id, &item(1, "text/plain", "notes.txt"));
+            mock::before_download(id, &item(2, "image/png", "cat.png"));
+            mock::before_download(id, &item(3, "application/zip", "archive.zip"));
+            mock::download_updated(id, &DownloadItem {
+                full_path: Some(PathBuf::from("/downloads/notes.txt")),
+                ..item(1, "text/plain", "notes.txt")
+            });
+            mock::download_updated(id, &DownloadItem {
+                state: DownloadState::Complete,
+                full_path: Some(PathBuf::from("/downloads/notes.txt")),
+                total_bytes: Some(0),
+                percent_complete: Some(100),
+                ..item(1, "text/plain", "notes.txt")
+            });
+            assert_eq!(decisions(), [
+                "BeforeDownloadCallback::cont false /downloads/notes.txt",
+                "BeforeDownloadCallback::cont true",
+                "DownloadItemCallback::pause 1",
+            ]);
+            assert_eq!(recorder.events(), [
+                "before 1 text/plain notes.txt",
+                "before 2 image/png cat.png",
+                "before 3 application/zip archive.zip",
+                "updated 1 InProgress Some(\"/downloads/notes.txt\") None None",
+                // An empty file has a known size.
+                "updated 1 Complete Some(\"/downloads/notes.txt\") Some(0) Some(100)",
+            ]);
+        });
+    }
+
+    #[test]
+    fn download_directory() {
+        let directory = env::temp_dir().join(format!("cef-download-directory-{}", process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        let downloads = Arc::new(DownloadDirectory::new(directory.join("downloads")));
+        mock::run(|| {
+            let browser = mock::create_browser(TestClient::new().download(downloads.clone()));
+            let id = browser.identifier();
+            mock::clear_calls();
+            mock::before_download(id, &item(1, "text/plain", "../notes.txt"));
+            let first = downloads.directory().join("notes.txt");
+            fs::write(&first, "").unwrap();
+            mock::before_download(id, &item(2, "text/plain", "notes.txt"));
+            mock::before_download(id, &item(3, "text/plain", "notes.txt"));
+            let second = downloads.directory().join("notes (1).txt");
+            let third = downloads.directory().join("notes (2).txt");
+            // The path of a canceled download can be given again, unlike the path of a download in progress.
+            mock::download_updated(id, &DownloadItem {
+                state: DownloadState::Canceled,
+                full_path: Some(second.clone()),
+                ..item(2, "text/plain", "notes.txt")
+            });
+            assert_eq!(downloads.unique_path("notes.txt"), second);
+            assert_eq!(downloads.unique_path(" "), downloads.directory().join("download"));
+            let saved = |path: &Path| format!("BeforeDownloadCallback::cont false {}", path.display());
+            assert_eq!(decisions(), [saved(&first), saved(&second), saved(&third)]);
+
+            // The downloads are canceled when the directory cannot be created, here because a file has its path.
+            let blocked = Arc::new(DownloadDirectory::new(first.join("downloads")));
+            let other = mock::create_browser(TestClient::new().download(blocked));
+            mock::clear_calls();
+            mock::before_download(other.identifier(), &item(4, "text/plain", "notes.txt"));
+            assert!(decisions().is_empty());
+        });
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
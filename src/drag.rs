@@ -0,0 +1,263 @@
+use std::mem;
+use std::slice;
+use std::sync::Arc;
+
+use browser::Browser;
+use geometry::Rect;
+use rc::{CefRc, RefCounted};
+use string::{CefString, string_list};
+use sys::*;
+
+bitflags! {
+    /// The operations allowed by the source of a drag.
+    pub struct DragOperations: cef_drag_operations_mask_t {
+        const COPY = DRAG_OPERATION_COPY;
+        const LINK = DRAG_OPERATION_LINK;
+        const GENERIC = DRAG_OPERATION_GENERIC;
+        const PRIVATE = DRAG_OPERATION_PRIVATE;
+        const MOVE = DRAG_OPERATION_MOVE;
+        const DELETE = DRAG_OPERATION_DELETE;
+    }
+}
+
+/// What is dragged into a browser: a link, a fragment of a page or files.
+#[repr(transparent)]
+pub struct DragData(cef_drag_data_t);
+
+cef_object!(DragData, cef_drag_data_t);
+
+impl DragData {
+    pub fn is_read_only(&self) -> bool {
+        call!(&self.0, is_read_only()) != 0
+    }
+
+    pub fn is_link(&self) -> bool {
+        call!(&self.0, is_link()) != 0
+    }
+
+    /// Whether a text or HTML fragment is dragged.
+    pub fn is_fragment(&self) -> bool {
+        call!(&self.0, is_fragment()) != 0
+    }
+
+    pub fn is_file(&self) -> bool {
+        call!(&self.0, is_file()) != 0
+    }
+
+    pub fn link_url(&self) -> String {
+        unsafe { CefString::from_userfree(call!(&self.0, get_link_url())) }.to_string()
+    }
+
+    pub fn link_title(&self) -> String {
+        unsafe { CefString::from_userfree(call!(&self.0, get_link_title())) }.to_string()
+    }
+
+    pub fn link_metadata(&self) -> String {
+        unsafe { CefString::from_userfree(call!(&self.0, get_link_metadata())) }.to_string()
+    }
+
+    /// The plain text of the fragment.
+    pub fn fragment_text(&self) -> String {
+        unsafe { CefString::from_userfree(call!(&self.0, get_fragment_text())) }.to_string()
+    }
+
+    pub fn fragment_html(&self) -> String {
+        unsafe { CefString::from_userfree(call!(&self.0, get_fragment_html())) }.to_string()
+    }
+
+    /// The URL of the page the fragment comes from.
+    pub fn fragment_base_url(&self) -> String {
+        unsafe { CefString::from_userfree(call!(&self.0, get_fragment_base_url())) }.to_string()
+    }
+
+    /// The name of the file created when the dragged content is dropped out of the browser.
+    pub fn file_name(&self) -> String {
+        unsafe { CefString::from_userfree(call!(&self.0, get_file_name())) }.to_string()
+    }
+
+    /// The paths of the files dragged into the browser.
+    pub fn file_names(&self) -> Vec<String> {
+        unsafe {
+            let names = cef_string_list_alloc();
+            call!(&self.0, get_file_names(names));
+            let file_names = string_list(names);
+            cef_string_list_free(names);
+            file_names
+        }
+    }
+
+    pub fn has_image(&self) -> bool {
+        call!(&self.0, has_image()) != 0
+    }
+}
+
+/// A region of the page set with the CSS property `-webkit-app-region`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DraggableRegion {
+    pub bounds: Rect,
+    /// True for `drag`, false for `no-drag`, which excludes a part of a draggable region, like a button in a
+    /// title bar.
+    pub draggable: bool,
+}
+
+impl DraggableRegion {
+    pub fn from_raw(region: &cef_draggable_region_t) -> Self {
+        DraggableRegion {
+            bounds: Rect::from_raw(&region.bounds),
+            draggable: region.draggable != 0,
+        }
+    }
+
+    pub fn to_raw(&self) -> cef_draggable_region_t {
+        cef_draggable_region_t {
+            bounds: self.bounds.to_raw(),
+            draggable: self.draggable as i32,
+        }
+    }
+
+    /// Whether dragging the point of the page should move the window: the last region containing it decides,
+    /// since the regions are given in the order of the page.
+    pub fn hit_test(regions: &[DraggableRegion], x: i32, y: i32) -> bool {
+        regions.iter().rev()
+            .find(|region| region.bounds.contains(x, y))
+            .map(|region| region.draggable)
+            .unwrap_or(false)
+    }
+}
+
+/// Called when something is dragged into a browser, or when its draggable regions change, on the UI thread.
+pub trait DragHandler: Send + Sync {
+    /// Returns true to refuse the drag, like to prevent files from being dropped into the page.
+    fn on_drag_enter(&self, _browser: &Browser, _drag_data: &DragData, _mask: DragOperations) -> bool {
+        false
+    }
+
+    /// Called with every region of the main frame when they change. Only called for the browsers without a
+    /// frame, like the views of frameless windows, whose application moves the window when they are dragged.
+    fn on_draggable_regions_changed(&self, _browser: &Browser, _regions: Vec<DraggableRegion>) {
+    }
+}
+
+type DragHandlerRc = RefCounted<cef_drag_handler_t, Arc<dyn DragHandler>>;
+
+pub(crate) fn wrap_drag_handler(handler: Arc<dyn DragHandler>) -> *mut cef_drag_handler_t {
+    extern "C" fn on_drag_enter(self_: *mut cef_drag_handler_t, browser: *mut cef_browser_t,
+        drag_data: *mut cef_drag_data_t, mask: cef_drag_operations_mask_t) -> i32
+    {
+        let handler = unsafe { DragHandlerRc::value(self_) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        let drag_data = unsafe { CefRc::<DragData>::from_raw(drag_data) };
+        match (browser, drag_data) {
+            (Some(browser), Some(drag_data)) =>
+                handler.on_drag_enter(&browser, &drag_data, DragOperations::from_bits_truncate(mask)) as i32,
+            _ => 0,
+        }
+    }
+
+    extern "C" fn on_draggable_regions_changed(self_: *mut cef_drag_handler_t, browser: *mut cef_browser_t,
+        regions_count: usize, regions: *const cef_draggable_region_t)
+    {
+        let handler = unsafe { DragHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            let regions =
+                if regions.is_null() {
+                    vec![]
+                }
+                else {
+                    unsafe { slice::from_raw_parts(regions, regions_count) }.iter()
+                        .map(DraggableRegion::from_raw)
+                        .collect()
+                };
+            handler.on_draggable_regions_changed(&browser, regions);
+        }
+    }
+
+    let mut raw: cef_drag_handler_t = unsafe { mem::zeroed() };
+    raw.on_drag_enter = Some(on_drag_enter);
+    raw.on_draggable_regions_changed = Some(on_draggable_regions_changed);
+    RefCounted::wrap(raw, handler)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use mock::{self, DragContents, Recorder, TestClient};
+    use super::*;
+
+    // Refuses the files, and keeps the last draggable regions.
+    #[derive(Default)]
+    struct Dragging {
+        recorder: Recorder,
+        regions: Mutex<Vec<DraggableRegion>>,
+    }
+
+    impl DragHandler for Dragging {
+        fn on_drag_enter(&self, _browser: &Browser, drag_data: &DragData, mask: DragOperations) -> bool {
+            let event =
+                if drag_data.is_link() {
+                    format!("link {} {}", drag_data.link_url(), drag_data.link_title())
+                }
+                else if drag_data.is_fragment() {
+                    format!("fragment {} {} {}", drag_data.fragment_text(), drag_data.fragment_html(),
+                        drag_data.fragment_base_url())
+                }
+                else {
+                    format!("files {:?}", drag_data.file_names())
+                };
+            self.recorder.push(format!("{} {:?}", event, mask));
+            drag_data.is_file()
+        }
+
+        fn on_draggable_regions_changed(&self, _browser: &Browser, regions: Vec<DraggableRegion>) {
+            *self.regions.lock().unwrap() = regions;
+        }
+    }
+
+    #[test]
+    fn drag_handler() {
+        mock::run(|| {
+            let dragging = Arc::new(Dragging::default());
+            let browser = mock::create_browser(TestClient::new().drag(dragging.clone()));
+            let id = browser.identifier();
+            assert!(!mock::drag_enter(id, &DragContents {
+                link_url: "https://example.com/".to_string(),
+                link_title: "Example".to_string(),
+                ..DragContents::default()
+            }, DragOperations::COPY | DragOperations::LINK));
+            assert!(!mock::drag_enter(id, &DragContents {
+                fragment_text: "text".to_string(),
+                fragment_html: "<b>text</b>".to_string(),
+                fragment_base_url: "https://example.com/".to_string(),
+                ..DragContents::default()
+            }, DragOperations::all()));
+            assert!(mock::drag_enter(id, &DragContents {
+                file_names: vec!["/tmp/a.txt".to_string(), "/tmp/b.txt".to_string()],
+                ..DragContents::default()
+            }, DragOperations::COPY));
+            assert_eq!(dragging.recorder.events(), [
+                "link https://example.com/ Example COPY | LINK",
+                "fragment text <b>text</b> https://example.com/ COPY | LINK | GENERIC | PRIVATE | MOVE | DELETE",
+                "files [\"/tmp/a.txt\", \"/tmp/b.txt\"] COPY",
+            ]);
+
+            // A title bar, except its close button.
+            let title_bar = DraggableRegion {
+                bounds: Rect::new(0, 0, 800, 30),
+                draggable: true,
+            };
+            let close_button = DraggableRegion {
+                bounds: Rect::new(770, 0, 30, 30),
+                draggable: false,
+            };
+            mock::draggable_regions_changed(id, &[title_bar, close_button]);
+            let regions = dragging.regions.lock().unwrap().clone();
+            assert_eq!(regions, [title_bar, close_button]);
+            assert!(DraggableRegion::hit_test(&regions, 10, 10));
+            assert!(!DraggableRegion::hit_test(&regions, 780, 10));
+            assert!(!DraggableRegion::hit_test(&regions, 10, 40));
+            mock::draggable_regions_changed(id, &[]);
+            assert!(dragging.regions.lock().unwrap().is_empty());
+        });
+    }
+}
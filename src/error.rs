@@ -0,0 +1,155 @@
+use std::error;
+use std::fmt;
+
+use sys::*;
+
+macro_rules! net_errors {
+    ($($name:ident = $code:ident, $message:expr;)*) => {
+        impl NetError {
+            $(pub const $name: NetError = NetError($code);)*
+
+            /// The name of the code in `cef_errorcode_t`, like `"ERR_ABORTED"`, if it is declared.
+            pub fn name(&self) -> Option<&'static str> {
+                match self.0 {
+                    $($code => Some(stringify!($code)),)*
+                    _ => None,
+                }
+            }
+
+            fn message(&self) -> Option<&'static str> {
+                match self.0 {
+                    $($code => Some($message),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+/// A network error code, received from CEF as a `cef_errorcode_t`.
+///
+/// Any code is kept as is, even the ones not declared in `cef_errorcode_t`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NetError(cef_errorcode_t);
+
+impl NetError {
+    /// Returns `None` for `ERR_NONE`.
+    pub fn from_raw(code: cef_errorcode_t) -> Option<Self> {
+        if code == ERR_NONE {
+            None
+        }
+        else {
+            Some(NetError(code))
+        }
+    }
+
+    pub fn code(&self) -> cef_errorcode_t {
+        self.0
+    }
+
+    /// From -100 to -199.
+    pub fn is_connection_error(&self) -> bool {
+        self.0 <= -100 && self.0 > -200
+    }
+
+    /// From -200 to -299.
+    pub fn is_certificate_error(&self) -> bool {
+        self.0 <= ERR_CERT_BEGIN && self.0 > -300
+    }
+
+    /// From -300 to -399, which are the errors of the URLs, the redirects and the HTTP responses.
+    pub fn is_url_error(&self) -> bool {
+        self.0 <= -300 && self.0 > -400
+    }
+}
+
+net_errors! {
+    FAILED = ERR_FAILED, "a generic failure occurred";
+    ABORTED = ERR_ABORTED, "the operation was aborted";
+    INVALID_ARGUMENT = ERR_INVALID_ARGUMENT, "an argument to the function is incorrect";
+    INVALID_HANDLE = ERR_INVALID_HANDLE, "the handle or file descriptor is invalid";
+    FILE_NOT_FOUND = ERR_FILE_NOT_FOUND, "the file or directory cannot be found";
+    TIMED_OUT = ERR_TIMED_OUT, "the operation timed out";
+    FILE_TOO_BIG = ERR_FILE_TOO_BIG, "the file is too large";
+    UNEXPECTED = ERR_UNEXPECTED, "an unexpected error occurred";
+    ACCESS_DENIED = ERR_ACCESS_DENIED, "permission to access a resource was denied";
+    NOT_IMPLEMENTED = ERR_NOT_IMPLEMENTED, "the operation is not implemented";
+    CONNECTION_CLOSED = ERR_CONNECTION_CLOSED, "the connection was closed";
+    CONNECTION_RESET = ERR_CONNECTION_RESET, "the connection was reset";
+    CONNECTION_REFUSED = ERR_CONNECTION_REFUSED, "the connection was refused";
+    CONNECTION_ABORTED = ERR_CONNECTION_ABORTED, "the connection was aborted because no acknowledgement was received";
+    CONNECTION_FAILED = ERR_CONNECTION_FAILED, "the connection attempt failed";
+    NAME_NOT_RESOLVED = ERR_NAME_NOT_RESOLVED, "the host name could not be resolved";
+    INTERNET_DISCONNECTED = ERR_INTERNET_DISCONNECTED, "the Internet connection has been lost";
+    SSL_PROTOCOL_ERROR = ERR_SSL_PROTOCOL_ERROR, "an SSL protocol error occurred";
+    ADDRESS_INVALID = ERR_ADDRESS_INVALID, "the IP address or port number is invalid";
+    ADDRESS_UNREACHABLE = ERR_ADDRESS_UNREACHABLE, "the IP address is unreachable";
+    SSL_CLIENT_AUTH_CERT_NEEDED = ERR_SSL_CLIENT_AUTH_CERT_NEEDED, "the server requested a client certificate";
+    TUNNEL_CONNECTION_FAILED = ERR_TUNNEL_CONNECTION_FAILED, "a tunnel connection through the proxy could not be established";
+    NO_SSL_VERSIONS_ENABLED = ERR_NO_SSL_VERSIONS_ENABLED, "no SSL protocol versions are enabled";
+    SSL_VERSION_OR_CIPHER_MISMATCH = ERR_SSL_VERSION_OR_CIPHER_MISMATCH, "the client and the server do not support a common SSL version or cipher suite";
+    SSL_RENEGOTIATION_REQUESTED = ERR_SSL_RENEGOTIATION_REQUESTED, "the server requested a renegotiation";
+    CERT_COMMON_NAME_INVALID = ERR_CERT_COMMON_NAME_INVALID, "the certificate does not match the host name";
+    CERT_DATE_INVALID = ERR_CERT_DATE_INVALID, "the certificate has expired or is not yet valid";
+    CERT_AUTHORITY_INVALID = ERR_CERT_AUTHORITY_INVALID, "the certificate is signed by an untrusted authority";
+    CERT_CONTAINS_ERRORS = ERR_CERT_CONTAINS_ERRORS, "the certificate contains errors";
+    CERT_NO_REVOCATION_MECHANISM = ERR_CERT_NO_REVOCATION_MECHANISM, "the revocation status of the certificate cannot be checked";
+    CERT_UNABLE_TO_CHECK_REVOCATION = ERR_CERT_UNABLE_TO_CHECK_REVOCATION, "the revocation information of the certificate is unavailable";
+    CERT_REVOKED = ERR_CERT_REVOKED, "the certificate has been revoked";
+    CERT_INVALID = ERR_CERT_INVALID, "the certificate is invalid";
+    CERT_WEAK_SIGNATURE_ALGORITHM = ERR_CERT_WEAK_SIGNATURE_ALGORITHM, "the certificate is signed with a weak algorithm";
+    CERT_NON_UNIQUE_NAME = ERR_CERT_NON_UNIQUE_NAME, "the certificate is for a name that is not unique";
+    CERT_WEAK_KEY = ERR_CERT_WEAK_KEY, "the certificate contains a weak key";
+    CERT_NAME_CONSTRAINT_VIOLATION = ERR_CERT_NAME_CONSTRAINT_VIOLATION, "the certificate violates the name constraints of its authority";
+    CERT_VALIDITY_TOO_LONG = ERR_CERT_VALIDITY_TOO_LONG, "the validity period of the certificate is too long";
+    INVALID_URL = ERR_INVALID_URL, "the URL is invalid";
+    DISALLOWED_URL_SCHEME = ERR_DISALLOWED_URL_SCHEME, "the scheme of the URL is disallowed";
+    UNKNOWN_URL_SCHEME = ERR_UNKNOWN_URL_SCHEME, "the scheme of the URL is unknown";
+    TOO_MANY_REDIRECTS = ERR_TOO_MANY_REDIRECTS, "there were too many redirects";
+    UNSAFE_REDIRECT = ERR_UNSAFE_REDIRECT, "the redirect is unsafe";
+    UNSAFE_PORT = ERR_UNSAFE_PORT, "the port is restricted";
+    INVALID_RESPONSE = ERR_INVALID_RESPONSE, "the server response is invalid";
+    INVALID_CHUNKED_ENCODING = ERR_INVALID_CHUNKED_ENCODING, "the chunked encoding of the response is invalid";
+    METHOD_NOT_SUPPORTED = ERR_METHOD_NOT_SUPPORTED, "the request method is not supported";
+    UNEXPECTED_PROXY_AUTH = ERR_UNEXPECTED_PROXY_AUTH, "a proxy authentication was unexpected";
+    EMPTY_RESPONSE = ERR_EMPTY_RESPONSE, "the server closed the connection without sending any data";
+    RESPONSE_HEADERS_TOO_BIG = ERR_RESPONSE_HEADERS_TOO_BIG, "the headers of the response are too big";
+    CACHE_MISS = ERR_CACHE_MISS, "the cache does not have the requested entry";
+    INSECURE_RESPONSE = ERR_INSECURE_RESPONSE, "the server's response was insecure";
+}
+
+impl fmt::Display for NetError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match (self.name(), self.message()) {
+            (Some(name), Some(message)) => write!(formatter, "{} ({})", message, name),
+            _ => write!(formatter, "network error {}", self.0),
+        }
+    }
+}
+
+impl error::Error for NetError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn net_errors() {
+        assert_eq!(NetError::from_raw(ERR_NONE), None);
+        let error = NetError::from_raw(ERR_NAME_NOT_RESOLVED).unwrap();
+        assert_eq!(error, NetError::NAME_NOT_RESOLVED);
+        assert_eq!(error.name(), Some("ERR_NAME_NOT_RESOLVED"));
+        assert_eq!(error.to_string(), "the host name could not be resolved (ERR_NAME_NOT_RESOLVED)");
+        assert!(error.is_connection_error());
+        assert!(NetError::CERT_REVOKED.is_certificate_error());
+        assert!(NetError::INVALID_URL.is_url_error());
+    }
+
+    #[test]
+    fn unknown_net_error() {
+        let error = NetError::from_raw(-12345).unwrap();
+        assert_eq!(error.code(), -12345);
+        assert_eq!(error.name(), None);
+        assert_eq!(error.to_string(), "network error -12345");
+    }
+}
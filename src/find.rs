@@ -0,0 +1,275 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::task::{Context, Poll, Waker};
+
+#[cfg(feature = "futures-core")]
+use futures_core::Stream;
+
+use browser::Browser;
+use geometry::Rect;
+use pending::locked;
+use rc::{CefRc, RefCounted};
+use sys::*;
+
+// The number of searches whose results are kept while they are not read.
+const MAX_QUEUED_RESULTS: usize = 32;
+
+// The identifiers of the searches must increase, so that CEF can tell the results of the last one.
+static NEXT_FIND_ID: AtomicI32 = AtomicI32::new(1);
+
+pub(crate) fn next_find_id() -> i32 {
+    NEXT_FIND_ID.fetch_add(1, Ordering::SeqCst)
+}
+
+/// The progress of a search started by `BrowserHost::find`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FindResult {
+    /// The identifier returned by `BrowserHost::find`.
+    pub identifier: i32,
+    /// The number of matches found so far.
+    pub count: i32,
+    /// The position of the active match in the view.
+    pub selection_rect: Rect,
+    /// The index of the active match, starting from 1, like 3 in "3 of 17".
+    pub active_match_ordinal: i32,
+    /// Whether this is the last result of the search, whose count is final.
+    pub final_update: bool,
+}
+
+/// Called with the results of the searches in the page, on the UI thread.
+pub trait FindHandler: Send + Sync {
+    /// Called several times per search, as more matches are found.
+    fn on_find_result(&self, _browser: &Browser, _result: &FindResult) {
+    }
+}
+
+pub(crate) struct DefaultFindHandler;
+
+impl FindHandler for DefaultFindHandler {}
+
+struct Queue {
+    browser_id: i32,
+    results: VecDeque<FindResult>,
+    waker: Option<Waker>,
+}
+
+impl Queue {
+    // A result replaces the previous result of the same search, and the oldest search is forgotten when there are
+    // too many of them.
+    fn push(&mut self, result: FindResult) {
+        match self.results.iter_mut().find(|queued| queued.identifier == result.identifier) {
+            Some(queued) => *queued = result,
+            None => {
+                if self.results.len() == MAX_QUEUED_RESULTS {
+                    self.results.pop_front();
+                }
+                self.results.push_back(result);
+            },
+        }
+    }
+}
+
+// The streams of results not dropped yet.
+static STREAMS: Mutex<Vec<Arc<Mutex<Queue>>>> = Mutex::new(Vec::new());
+
+fn dispatch(browser: &Browser, result: &FindResult) {
+    let browser_id = browser.identifier();
+    let mut wakers = vec![];
+    for queue in locked(&STREAMS).iter() {
+        let mut queue = locked(queue);
+        if queue.browser_id == browser_id {
+            queue.push(*result);
+            wakers.extend(queue.waker.take());
+        }
+    }
+    for waker in wakers {
+        waker.wake();
+    }
+}
+
+/// The results of the searches of a browser, received from the time `BrowserHost::find_results` is called.
+///
+/// Only the latest result of each search is kept until it is read, for the last 32 searches. Only the browsers
+/// whose client was created by `wrap_client` deliver results.
+///
+/// With the `futures-core` feature, `FindResults` is a `Stream`.
+pub struct FindResults {
+    queue: Arc<Mutex<Queue>>,
+}
+
+impl FindResults {
+    pub(crate) fn new(browser: &Browser) -> Self {
+        let queue = Arc::new(Mutex::new(Queue {
+            browser_id: browser.identifier(),
+            results: VecDeque::new(),
+            waker: None,
+        }));
+        locked(&STREAMS).push(queue.clone());
+        FindResults {
+            queue,
+        }
+    }
+
+    /// The next result already received, without waiting.
+    pub fn try_next(&mut self) -> Option<FindResult> {
+        locked(&self.queue).results.pop_front()
+    }
+
+    /// A future resolved with the next result.
+    pub fn next_result(&mut self) -> NextFindResult<'_> {
+        NextFindResult {
+            results: self,
+        }
+    }
+
+    /// Like `Stream::poll_next` of the futures crates. The stream never ends.
+    pub fn poll_next(self: Pin<&mut Self>, context: &mut Context) -> Poll<Option<FindResult>> {
+        let mut queue = locked(&self.queue);
+        match queue.results.pop_front() {
+            Some(result) => Poll::Ready(Some(result)),
+            None => {
+                queue.waker = Some(context.waker().clone());
+                Poll::Pending
+            },
+        }
+    }
+}
+
+impl Drop for FindResults {
+    fn drop(&mut self) {
+        locked(&STREAMS).retain(|queue| !Arc::ptr_eq(queue, &self.queue));
+    }
+}
+
+#[cfg(feature = "futures-core")]
+impl Stream for FindResults {
+    type Item = FindResult;
+
+    fn poll_next(self: Pin<&mut Self>, context: &mut Context) -> Poll<Option<FindResult>> {
+        FindResults::poll_next(self, context)
+    }
+}
+
+/// The future returned by `FindResults::next_result`.
+#[must_use = "the result is only received by awaiting the future"]
+pub struct NextFindResult<'a> {
+    results: &'a mut FindResults,
+}
+
+impl<'a> Future for NextFindResult<'a> {
+    type Output = FindResult;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<FindResult> {
+        match Pin::new(&mut *self.results).poll_next(context) {
+            Poll::Ready(Some(result)) => Poll::Ready(result),
+            _ => Poll::Pending,
+        }
+    }
+}
+
+type FindHandlerRc = RefCounted<cef_find_handler_t, Arc<dyn FindHandler>>;
+
+pub(crate) fn wrap_find_handler(handler: Arc<dyn FindHandler>) -> *mut cef_find_handler_t {
+    extern "C" fn on_find_result(self_: *mut cef_find_handler_t, browser: *mut cef_browser_t, identifier: i32,
+        count: i32, selection_rect: *const cef_rect_t, active_match_ordinal: i32, final_update: i32)
+    {
+        let handler = unsafe { FindHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            let result = FindResult {
+                identifier,
+                count,
+                selection_rect: if selection_rect.is_null() {
+                    Rect::default()
+                }
+                else {
+                    Rect::from_raw(unsafe { &*selection_rect })
+                },
+                active_match_ordinal,
+                final_update: final_update != 0,
+            };
+            handler.on_find_result(&browser, &result);
+            dispatch(&browser, &result);
+        }
+    }
+
+    let mut raw: cef_find_handler_t = unsafe { mem::zeroed() };
+    raw.on_find_result = Some(on_find_result);
+    RefCounted::wrap(raw, handler)
+}
+
+#[cfg(test)]
+mod tests {
+    use mock::{self, Recorder, TestClient};
+    use super::*;
+
+    impl FindHandler for Recorder {
+        fn on_find_result(&self, _browser: &Browser, result: &FindResult) {
+            self.push(format!("result {}", result.count));
+        }
+    }
+
+    fn result(identifier: i32, count: i32, final_update: bool) -> FindResult {
+        FindResult {
+            identifier,
+            count,
+            selection_rect: Rect::new(10, 20, 30, 40),
+            active_match_ordinal: 1,
+            final_update,
+        }
+    }
+
+    #[test]
+    fn find_results() {
+        mock::run(|| {
+            let recorder = Arc::new(Recorder::default());
+            let browser = mock::create_browser(TestClient::new().find(recorder.clone()));
+            let other = mock::create_browser(TestClient::new());
+            let id = browser.identifier();
+            let host = browser.host();
+            let mut results = host.find_results();
+            let identifier = host.find("text", true, false, false);
+            {
+                let mut next = results.next_result();
+                assert_eq!(mock::poll(&mut next), Poll::Pending);
+                mock::find_result(id, &result(identifier, 1, false));
+                assert_eq!(mock::poll(&mut next), Poll::Ready(result(identifier, 1, false)));
+            }
+            // The intermediate results are replaced by the later ones, and the other browsers have their own results.
+            mock::find_result(id, &result(identifier, 2, false));
+            mock::find_result(other.identifier(), &result(identifier, 7, true));
+            mock::find_result(id, &result(identifier, 3, true));
+            assert_eq!(results.try_next(), Some(result(identifier, 3, true)));
+            assert_eq!(results.try_next(), None);
+            // The handler of the client still receives every result.
+            assert_eq!(recorder.events(), ["result 1", "result 2", "result 3"]);
+
+            for identifier in 0..40 {
+                mock::find_result(id, &result(identifier, 1, true));
+            }
+            let identifiers: Vec<_> = std::iter::from_fn(|| results.try_next())
+                .map(|result| result.identifier)
+                .collect();
+            assert_eq!(identifiers, (8..40).collect::<Vec<_>>());
+            drop(results);
+            assert!(locked(&STREAMS).is_empty());
+        });
+    }
+
+    #[cfg(feature = "futures-core")]
+    #[test]
+    fn stream() {
+        mock::run(|| {
+            let browser = mock::create_browser(TestClient::new());
+            let host = browser.host();
+            let mut results = host.find_results();
+            let mut next = std::future::poll_fn(|context| Stream::poll_next(Pin::new(&mut results), context));
+            assert_eq!(mock::poll(&mut next), Poll::Pending);
+            mock::find_result(browser.identifier(), &result(1, 5, true));
+            assert_eq!(mock::poll(&mut next), Poll::Ready(Some(result(1, 5, true))));
+        });
+    }
+}
@@ -0,0 +1,124 @@
+use std::mem;
+use std::sync::Arc;
+
+use browser::Browser;
+use rc::{CefRc, RefCounted};
+use sys::*;
+
+/// Why a browser is about to receive the focus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FocusSource {
+    /// A navigation focuses the page, like when a URL is loaded.
+    Navigation,
+    /// The focus is requested by the system, like after a click in the browser.
+    System,
+}
+
+impl FocusSource {
+    pub fn from_raw(source: cef_focus_source_t) -> Self {
+        match source {
+            cef_focus_source_t::FOCUS_SOURCE_NAVIGATION => FocusSource::Navigation,
+            cef_focus_source_t::FOCUS_SOURCE_SYSTEM => FocusSource::System,
+        }
+    }
+
+    pub fn to_raw(self) -> cef_focus_source_t {
+        match self {
+            FocusSource::Navigation => cef_focus_source_t::FOCUS_SOURCE_NAVIGATION,
+            FocusSource::System => cef_focus_source_t::FOCUS_SOURCE_SYSTEM,
+        }
+    }
+}
+
+/// Called when the keyboard focus enters or leaves a browser, on the UI thread.
+pub trait FocusHandler: Send + Sync {
+    /// Called when the focus leaves the page by tabbing past its last element, with `next` true, or before its
+    /// first element, with `next` false (Shift+Tab). The application should focus its next or previous widget.
+    fn on_take_focus(&self, _browser: &Browser, _next: bool) {
+    }
+
+    /// Called when the browser is about to receive the focus. Returns true to keep the focus where it is.
+    fn on_set_focus(&self, _browser: &Browser, _source: FocusSource) -> bool {
+        false
+    }
+
+    /// Called when the browser received the focus.
+    fn on_got_focus(&self, _browser: &Browser) {
+    }
+}
+
+type FocusHandlerRc = RefCounted<cef_focus_handler_t, Arc<dyn FocusHandler>>;
+
+pub(crate) fn wrap_focus_handler(handler: Arc<dyn FocusHandler>) -> *mut cef_focus_handler_t {
+    extern "C" fn on_take_focus(self_: *mut cef_focus_handler_t, browser: *mut cef_browser_t, next: i32) {
+        let handler = unsafe { FocusHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            handler.on_take_focus(&browser, next != 0);
+        }
+    }
+
+    extern "C" fn on_set_focus(self_: *mut cef_focus_handler_t, browser: *mut cef_browser_t,
+        source: cef_focus_source_t) -> i32
+    {
+        let handler = unsafe { FocusHandlerRc::value(self_) };
+        match unsafe { CefRc::<Browser>::from_raw(browser) } {
+            Some(browser) => handler.on_set_focus(&browser, FocusSource::from_raw(source)) as i32,
+            None => 0,
+        }
+    }
+
+    extern "C" fn on_got_focus(self_: *mut cef_focus_handler_t, browser: *mut cef_browser_t) {
+        let handler = unsafe { FocusHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            handler.on_got_focus(&browser);
+        }
+    }
+
+    let mut raw: cef_focus_handler_t = unsafe { mem::zeroed() };
+    raw.on_take_focus = Some(on_take_focus);
+    raw.on_set_focus = Some(on_set_focus);
+    raw.on_got_focus = Some(on_got_focus);
+    RefCounted::wrap(raw, handler)
+}
+
+#[cfg(test)]
+mod tests {
+    use mock::{self, Recorder, TestClient};
+    use super::*;
+
+    // Keeps the focus away from the page while it navigates.
+    impl FocusHandler for Recorder {
+        fn on_take_focus(&self, browser: &Browser, next: bool) {
+            self.push(format!("take {} {}", browser.identifier(), next));
+        }
+
+        fn on_set_focus(&self, browser: &Browser, source: FocusSource) -> bool {
+            self.push(format!("set {} {:?}", browser.identifier(), source));
+            source == FocusSource::Navigation
+        }
+
+        fn on_got_focus(&self, browser: &Browser) {
+            self.push(format!("got {}", browser.identifier()));
+        }
+    }
+
+    #[test]
+    fn focus_handler() {
+        mock::run(|| {
+            let recorder = Arc::new(Recorder::default());
+            let browser = mock::create_browser(TestClient::new().focus(recorder.clone()));
+            let id = browser.identifier();
+            assert!(!mock::give_focus(id, FocusSource::Navigation));
+            assert!(mock::give_focus(id, FocusSource::System));
+            mock::take_focus(id, true);
+            mock::take_focus(id, false);
+            assert_eq!(recorder.events(), [
+                format!("set {} Navigation", id),
+                format!("set {} System", id),
+                format!("got {}", id),
+                format!("take {} true", id),
+                format!("take {} false", id),
+            ]);
+        });
+    }
+}
@@ -0,0 +1,70 @@
+use sys::{cef_point_t, cef_rect_t};
+
+/// A point, in view coordinates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Self {
+        Point {
+            x,
+            y,
+        }
+    }
+
+    pub fn from_raw(point: &cef_point_t) -> Self {
+        Point::new(point.x, point.y)
+    }
+
+    pub fn to_raw(&self) -> cef_point_t {
+        cef_point_t {
+            x: self.x,
+            y: self.y,
+        }
+    }
+}
+
+/// A rectangle, in view coordinates, whose origin is its top-left corner.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Rect {
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn from_raw(rect: &cef_rect_t) -> Self {
+        Rect::new(rect.x, rect.y, rect.width, rect.height)
+    }
+
+    pub fn to_raw(&self) -> cef_rect_t {
+        cef_rect_t {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+
+    /// The right and bottom edges are outside of the rectangle.
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && y >= self.y && x < self.x + self.width && y < self.y + self.height
+    }
+}
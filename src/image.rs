@@ -0,0 +1,60 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+use png::encode_png;
+
+/// An RGBA image, whose rows of `width * 4` bytes start from its top-left corner, without premultiplied alpha.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Convert a BGRA image with premultiplied alpha, like the buffers painted by a `RenderHandler`.
+    pub fn from_bgra(width: u32, height: u32, bgra: &[u8]) -> Self {
+        assert_eq!(bgra.len(), width as usize * height as usize * 4, "size of the BGRA image");
+        let mut pixels = Vec::with_capacity(bgra.len());
+        for pixel in bgra.chunks(4) {
+            let (blue, green, red, alpha) = (pixel[0], pixel[1], pixel[2], pixel[3]);
+            let unpremultiply = |channel: u8| {
+                if alpha == 0 || alpha == 255 {
+                    channel
+                }
+                else {
+                    (channel as u32 * 255 / alpha as u32).min(255) as u8
+                }
+            };
+            pixels.extend_from_slice(&[unpremultiply(red), unpremultiply(green), unpremultiply(blue), alpha]);
+        }
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// The RGBA bytes of the pixel at (x, y), or `None` if it is out of the image.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+        Some([self.pixels[index], self.pixels[index + 1], self.pixels[index + 2], self.pixels[index + 3]])
+    }
+
+    /// Panics if the image is empty, which PNG does not allow.
+    pub fn to_png(&self) -> Vec<u8> {
+        encode_png(self.width, self.height, &self.pixels)
+    }
+
+    pub fn write_png<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&self.to_png())
+    }
+
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_png(File::create(path)?)
+    }
+}
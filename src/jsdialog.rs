@@ -0,0 +1,411 @@
+use std::mem;
+use std::ptr;
+use std::sync::{Arc, Mutex};
+
+use browser::Browser;
+use rc::{CefRc, RefCounted};
+use string::{CefStr, CefString};
+use sys::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsDialogKind {
+    /// `alert()`
+    Alert,
+    /// `confirm()`
+    Confirm,
+    /// `prompt()`
+    Prompt,
+}
+
+impl JsDialogKind {
+    pub fn from_raw(dialog_type: cef_jsdialog_type_t) -> Self {
+        match dialog_type {
+            cef_jsdialog_type_t::JSDIALOGTYPE_ALERT => JsDialogKind::Alert,
+            cef_jsdialog_type_t::JSDIALOGTYPE_CONFIRM => JsDialogKind::Confirm,
+            cef_jsdialog_type_t::JSDIALOGTYPE_PROMPT => JsDialogKind::Prompt,
+        }
+    }
+
+    pub fn to_raw(self) -> cef_jsdialog_type_t {
+        match self {
+            JsDialogKind::Alert => cef_jsdialog_type_t::JSDIALOGTYPE_ALERT,
+            JsDialogKind::Confirm => cef_jsdialog_type_t::JSDIALOGTYPE_CONFIRM,
+            JsDialogKind::Prompt => cef_jsdialog_type_t::JSDIALOGTYPE_PROMPT,
+        }
+    }
+}
+
+/// A dialog opened by the JavaScript of a page.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsDialog {
+    pub kind: JsDialogKind,
+    /// The URL of the page which opened the dialog.
+    pub origin_url: String,
+    pub message: String,
+    /// The text initially shown in the field of a prompt, or empty.
+    pub default_prompt: String,
+}
+
+#[repr(transparent)]
+struct RawJsDialogCallback(cef_jsdialog_callback_t);
+
+cef_object!(RawJsDialogCallback, cef_jsdialog_callback_t);
+
+// The callback of a dialog, until it is answered.
+struct Pending {
+    raw: Option<CefRc<RawJsDialogCallback>>,
+    // Whether the handler answers the dialog, once it returned.
+    handled: Option<bool>,
+    dropped: bool,
+}
+
+impl Pending {
+    fn answer(&mut self, success: bool, text: Option<&str>) {
+        if let Some(raw) = self.raw.take() {
+            let text = text.map(CefString::new);
+            let text = text.as_ref().map(|text| text.as_raw()).unwrap_or(ptr::null());
+            call!(&raw.0, cont(success as i32, text));
+        }
+    }
+
+    // Called when the handler returned.
+    fn handler_returned(&mut self, handled: bool) {
+        self.handled = Some(handled);
+        if !handled {
+            // CEF shows its own dialog, so the callback must not be called.
+            self.raw = None;
+        }
+        else if self.dropped {
+            self.answer(false, None);
+        }
+    }
+}
+
+/// Answers a dialog which the page waits for; dropping it unanswered cancels the dialog if the handler returned true.
+pub struct JsDialogCallback {
+    pending: Arc<Mutex<Pending>>,
+}
+
+impl JsDialogCallback {
+    fn new(raw: CefRc<RawJsDialogCallback>) -> Self {
+        JsDialogCallback {
+            pending: Arc::new(Mutex::new(Pending {
+                raw: Some(raw),
+                handled: None,
+                dropped: false,
+            })),
+        }
+    }
+
+    /// Press OK, or leave the page for a before unload dialog.
+    pub fn accept(self) {
+        self.answer(true, None);
+    }
+
+    /// Press OK in a prompt, which returns `text` to the page.
+    pub fn accept_prompt(self, text: &str) {
+        self.answer(true, Some(text));
+    }
+
+    /// Press Cancel, or stay on the page for a before unload dialog.
+    pub fn cancel(self) {
+        self.answer(false, None);
+    }
+
+    fn answer(self, success: bool, text: Option<&str>) {
+        self.pending.lock().unwrap_or_else(|error| error.into_inner()).answer(success, text);
+    }
+}
+
+impl Drop for JsDialogCallback {
+    fn drop(&mut self) {
+        let mut pending = self.pending.lock().unwrap_or_else(|error| error.into_inner());
+        pending.dropped = true;
+        // Before the handler returns, it is not known yet whether the dialog must be cancelled.
+        if pending.handled == Some(true) {
+            pending.answer(false, None);
+        }
+    }
+}
+
+/// Called when a page opens a dialog with `alert()`, `confirm()` or `prompt()`, or asks to confirm leaving it, on
+/// the UI thread.
+///
+/// Without a handler, CEF shows its own dialogs when the browser has a window, and cancels them otherwise.
+pub trait JsDialogHandler: Send + Sync {
+    /// Return true to answer with `callback`, or false to show the default dialog, which `suppress_message`
+    /// cancels silently.
+    fn on_jsdialog(&self, _browser: &Browser, _dialog: &JsDialog, _callback: JsDialogCallback,
+        _suppress_message: &mut bool) -> bool
+    {
+        false
+    }
+
+    /// Called when the page asks to confirm leaving it, with `onbeforeunload`. `is_reload` is true when the page
+    /// is being reloaded. Returns true if the callback answers the dialog, like `on_jsdialog`.
+    fn on_before_unload_dialog(&self, _browser: &Browser, _message: &str, _is_reload: bool,
+        _callback: JsDialogCallback) -> bool
+    {
+        false
+    }
+
+    /// Called when the dialogs not answered yet must be closed, like when the page navigates away. Their
+    /// callbacks should be dropped.
+    fn on_reset_dialog_state(&self, _browser: &Browser) {
+    }
+
+    /// Called when the default dialog is closed.
+    fn on_dialog_closed(&self, _browser: &Browser) {
+    }
+}
+
+/// Accept every dialog, with the default text for prompts, and leave the pages asking for confirmation.
+pub struct AutoAccept;
+
+impl JsDialogHandler for AutoAccept {
+    fn on_jsdialog(&self, _browser: &Browser, dialog: &JsDialog, callback: JsDialogCallback,
+        _suppress_message: &mut bool) -> bool
+    {
+        match dialog.kind {
+            JsDialogKind::Prompt => callback.accept_prompt(&dialog.default_prompt),
+            _ => callback.accept(),
+        }
+        true
+    }
+
+    fn on_before_unload_dialog(&self, _browser: &Browser, _message: &str, _is_reload: bool,
+        callback: JsDialogCallback) -> bool
+    {
+        callback.accept();
+        true
+    }
+}
+
+/// Cancel every dialog, which makes `confirm()` return false and `prompt()` return null, and stay on the pages
+/// asking for confirmation.
+pub struct AutoDismiss;
+
+impl JsDialogHandler for AutoDismiss {
+    fn on_jsdialog(&self, _browser: &Browser, _dialog: &JsDialog, callback: JsDialogCallback,
+        _suppress_message: &mut bool) -> bool
+    {
+        callback.cancel();
+        true
+    }
+
+    fn on_before_unload_dialog(&self, _browser: &Browser, _message: &str, _is_reload: bool,
+        callback: JsDialogCallback) -> bool
+    {
+        callback.cancel();
+        true
+    }
+}
+
+/// Give a line describing each dialog to a closure, like `LogAndAccept(|line| eprintln!("{}", line))`, then
+/// accept the dialog like `AutoAccept`.
+pub struct LogAndAccept<F>(pub F);
+
+impl<F: Fn(&str) + Send + Sync> JsDialogHandler for LogAndAccept<F> {
+    fn on_jsdialog(&self, browser: &Browser, dialog: &JsDialog, callback: JsDialogCallback,
+        suppress_message: &mut bool) -> bool
+    {
+        (self.0)(&format!("{:?} from {}: {}", dialog.kind, dialog.origin_url, dialog.message));
+        AutoAccept.on_jsdialog(browser, dialog, callback, suppress_message)
+    }
+
+    fn on_before_unload_dialog(&self, browser: &Browser, message: &str, is_reload: bool,
+        callback: JsDialogCallback) -> bool
+    {
+        (self.0)(&format!("before unload: {}", message));
+        AutoAccept.on_before_unload_dialog(browser, message, is_reload, callback)
+    }
+}
+
+type JsDialogHandlerRc = RefCounted<cef_jsdialog_handler_t, Arc<dyn JsDialogHandler>>;
+
+pub(crate) fn wrap_jsdialog_handler(handler: Arc<dyn JsDialogHandler>) -> *mut cef_jsdialog_handler_t {
+    extern "C" fn on_jsdialog(self_: *mut cef_jsdialog_handler_t, browser: *mut cef_browser_t,
+        origin_url: *const cef_string_t, dialog_type: cef_jsdialog_type_t, message_text: *const cef_string_t,
+        default_prompt_text: *const cef_string_t, callback: *mut cef_jsdialog_callback_t,
+        suppress_message: *mut i32) -> i32
+    {
+        let handler = unsafe { JsDialogHandlerRc::value(self_) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        let callback = unsafe { CefRc::<RawJsDialogCallback>::from_raw(callback) };
+        let (browser, callback) = match (browser, callback) {
+            (Some(browser), Some(callback)) => (browser, callback),
+            _ => return 0,
+        };
+        let dialog = JsDialog {
+            kind: JsDialogKind::from_raw(dialog_type),
+            origin_url: unsafe { CefStr::from_ptr(origin_url) }.to_string(),
+            message: unsafe { CefStr::from_ptr(message_text) }.to_string(),
+            default_prompt: unsafe { CefStr::from_ptr(default_prompt_text) }.to_string(),
+        };
+        let mut suppress = false;
+        let callback = JsDialogCallback::new(callback);
+        let pending = callback.pending.clone();
+        let handled = handler.on_jsdialog(&browser, &dialog, callback, &mut suppress);
+        pending.lock().unwrap_or_else(|error| error.into_inner()).handler_returned(handled);
+        if !suppress_message.is_null() {
+            unsafe { *suppress_message = suppress as i32 };
+        }
+        handled as i32
+    }
+
+    extern "C" fn on_before_unload_dialog(self_: *mut cef_jsdialog_handler_t, browser: *mut cef_browser_t,
+        message_text: *const cef_string_t, is_reload: i32, callback: *mut cef_jsdialog_callback_t) -> i32
+    {
+        let handler = unsafe { JsDialogHandlerRc::value(self_) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        let callback = unsafe { CefRc::<RawJsDialogCallback>::from_raw(callback) };
+        match (browser, callback) {
+            (Some(browser), Some(callback)) => {
+                let message = unsafe { CefStr::from_ptr(message_text) }.to_string();
+                let callback = JsDialogCallback::new(callback);
+                let pending = callback.pending.clone();
+                let handled = handler.on_before_unload_dialog(&browser, &message, is_reload != 0, callback);
+                pending.lock().unwrap_or_else(|error| error.into_inner()).handler_returned(handled);
+                handled as i32
+            },
+            _ => 0,
+        }
+    }
+
+    extern "C" fn on_reset_dialog_state(self_: *mut cef_jsdialog_handler_t, browser: *mut cef_browser_t) {
+        let handler = unsafe { JsDialogHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            handler.on_reset_dialog_state(&browser);
+        }
+    }
+
+    extern "C" fn on_dialog_closed(self_: *mut cef_jsdialog_handler_t, browser: *mut cef_browser_t) {
+        let handler = unsafe { JsDialogHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            handler.on_dialog_closed(&browser);
+        }
+    }
+
+    let mut raw: cef_jsdialog_handler_t = unsafe { mem::zeroed() };
+    raw.on_jsdialog = Some(on_jsdialog);
+    raw.on_before_unload_dialog = Some(on_before_unload_dialog);
+    raw.on_reset_dialog_state = Some(on_reset_dialog_state);
+    raw.on_dialog_closed = Some(on_dialog_closed);
+    RefCounted::wrap(raw, handler)
+}
+
+#[cfg(test)]
+mod tests {
+    use mock::{self, TestClient};
+    use super::*;
+
+    // Answers the dialogs according to their message.
+    #[derive(Default)]
+    struct Scripted {
+        later: Mutex<Vec<JsDialogCallback>>,
+    }
+
+    impl JsDialogHandler for Scripted {
+        fn on_jsdialog(&self, _browser: &Browser, dialog: &JsDialog, callback: JsDialogCallback,
+            suppress_message: &mut bool) -> bool
+        {
+            match dialog.message.as_str() {
+                "answer" => callback.accept_prompt(&format!("{} {}", dialog.origin_url, dialog.default_prompt)),
+                "later" => self.later.lock().unwrap().push(callback),
+                "drop" => (),
+                _ => {
+                    *suppress_message = true;
+                    return false;
+                },
+            }
+            true
+        }
+
+        fn on_before_unload_dialog(&self, _browser: &Browser, message: &str, _is_reload: bool,
+            callback: JsDialogCallback) -> bool
+        {
+            if message == "later" {
+                self.later.lock().unwrap().push(callback);
+            }
+            message != "default"
+        }
+
+        fn on_reset_dialog_state(&self, _browser: &Browser) {
+            self.later.lock().unwrap().clear();
+        }
+    }
+
+    fn dialog(kind: JsDialogKind, message: &str) -> JsDialog {
+        JsDialog {
+            kind,
+            origin_url: "https://origin/".to_string(),
+            message: message.to_string(),
+            default_prompt: "prompt".to_string(),
+        }
+    }
+
+    fn answers() -> Vec<String> {
+        mock::calls().into_iter()
+            .filter(|call| call.starts_with("JsDialogCallback") || call == "suppress_message")
+            .collect()
+    }
+
+    #[test]
+    fn dropped_callbacks() {
+        mock::run(|| {
+            let handler = Arc::new(Scripted::default());
+            let browser = mock::create_browser(TestClient::new().jsdialog(handler.clone()));
+            let id = browser.identifier();
+            mock::clear_calls();
+            assert!(mock::jsdialog(id, &dialog(JsDialogKind::Prompt, "answer")));
+            assert_eq!(answers(), ["JsDialogCallback::cont true https://origin/ prompt"]);
+            mock::clear_calls();
+            // Without answer, the dialog is cancelled only if the handler returns true.
+            assert!(mock::jsdialog(id, &dialog(JsDialogKind::Confirm, "drop")));
+            assert!(!mock::jsdialog(id, &dialog(JsDialogKind::Alert, "default")));
+            assert!(!mock::before_unload_dialog(id, "default", false));
+            assert!(mock::before_unload_dialog(id, "drop", true));
+            assert_eq!(answers(), ["JsDialogCallback::cont false", "suppress_message", "JsDialogCallback::cont false"]);
+            mock::clear_calls();
+            assert!(mock::jsdialog(id, &dialog(JsDialogKind::Confirm, "later")));
+            assert!(mock::before_unload_dialog(id, "later", false));
+            assert!(answers().is_empty());
+            let mut later = mem::take(&mut *handler.later.lock().unwrap());
+            later.remove(0).accept();
+            drop(later);
+            assert_eq!(answers(), ["JsDialogCallback::cont true", "JsDialogCallback::cont false"]);
+            // The dialogs closed by CEF are cancelled when the handler drops their callbacks.
+            mock::clear_calls();
+            assert!(mock::jsdialog(id, &dialog(JsDialogKind::Prompt, "later")));
+            mock::reset_dialog_state(id);
+            assert!(handler.later.lock().unwrap().is_empty());
+            assert_eq!(answers(), ["JsDialogCallback::cont false"]);
+        });
+    }
+
+    #[test]
+    fn log_and_accept() {
+        mock::run(|| {
+            let lines = Arc::new(Mutex::new(vec![]));
+            let log = lines.clone();
+            let browser = mock::create_browser(TestClient::new().jsdialog(Arc::new(LogAndAccept(move |line: &str| {
+                log.lock().unwrap().push(line.to_string());
+            }))));
+            let id = browser.identifier();
+            mock::clear_calls();
+            assert!(mock::jsdialog(id, &dialog(JsDialogKind::Alert, "hello")));
+            assert!(mock::jsdialog(id, &dialog(JsDialogKind::Prompt, "name?")));
+            assert!(mock::before_unload_dialog(id, "unsaved changes", false));
+            assert_eq!(answers(), [
+                "JsDialogCallback::cont true",
+                "JsDialogCallback::cont true prompt",
+                "JsDialogCallback::cont true",
+            ]);
+            assert_eq!(*lines.lock().unwrap(), [
+                "Alert from https://origin/: hello",
+                "Prompt from https://origin/: name?",
+                "before unload: unsaved changes",
+            ]);
+        });
+    }
+}
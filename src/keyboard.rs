@@ -0,0 +1,263 @@
+use std::mem;
+use std::sync::Arc;
+
+use browser::Browser;
+use rc::{CefRc, RefCounted};
+use sys::*;
+
+bitflags! {
+    /// The keys and the mouse buttons held during an event.
+    pub struct Modifiers: cef_event_flags_t {
+        const CAPS_LOCK_ON = EVENTFLAG_CAPS_LOCK_ON;
+        const SHIFT = EVENTFLAG_SHIFT_DOWN;
+        const CONTROL = EVENTFLAG_CONTROL_DOWN;
+        const ALT = EVENTFLAG_ALT_DOWN;
+        const LEFT_MOUSE_BUTTON = EVENTFLAG_LEFT_MOUSE_BUTTON;
+        const MIDDLE_MOUSE_BUTTON = EVENTFLAG_MIDDLE_MOUSE_BUTTON;
+        const RIGHT_MOUSE_BUTTON = EVENTFLAG_RIGHT_MOUSE_BUTTON;
+        /// The command key of macOS.
+        const COMMAND = EVENTFLAG_COMMAND_DOWN;
+        const NUM_LOCK_ON = EVENTFLAG_NUM_LOCK_ON;
+        /// The key is on the keypad.
+        const IS_KEY_PAD = EVENTFLAG_IS_KEY_PAD;
+        /// The key is the left one of a pair, like the left shift key.
+        const IS_LEFT = EVENTFLAG_IS_LEFT;
+        const IS_RIGHT = EVENTFLAG_IS_RIGHT;
+    }
+}
+
+impl Modifiers {
+    /// The modifier keys used by shortcuts, without the locks, the mouse buttons and the location of the key.
+    pub fn keys(self) -> Self {
+        self & (Modifiers::SHIFT | Modifiers::CONTROL | Modifiers::ALT | Modifiers::COMMAND)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyEventType {
+    /// A key press, before it is translated to a character.
+    RawKeyDown,
+    KeyDown,
+    KeyUp,
+    /// A character typed, after the key down.
+    Char,
+}
+
+impl KeyEventType {
+    pub fn from_raw(event_type: cef_key_event_type_t) -> Self {
+        match event_type {
+            cef_key_event_type_t::KEYEVENT_RAWKEYDOWN => KeyEventType::RawKeyDown,
+            cef_key_event_type_t::KEYEVENT_KEYDOWN => KeyEventType::KeyDown,
+            cef_key_event_type_t::KEYEVENT_KEYUP => KeyEventType::KeyUp,
+            cef_key_event_type_t::KEYEVENT_CHAR => KeyEventType::Char,
+        }
+    }
+
+    pub fn to_raw(self) -> cef_key_event_type_t {
+        match self {
+            KeyEventType::RawKeyDown => cef_key_event_type_t::KEYEVENT_RAWKEYDOWN,
+            KeyEventType::KeyDown => cef_key_event_type_t::KEYEVENT_KEYDOWN,
+            KeyEventType::KeyUp => cef_key_event_type_t::KEYEVENT_KEYUP,
+            KeyEventType::Char => cef_key_event_type_t::KEYEVENT_CHAR,
+        }
+    }
+}
+
+/// A keyboard event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub event_type: KeyEventType,
+    pub modifiers: Modifiers,
+    /// The Windows virtual key code, on every platform, like `0x74` for F5 or `'L' as i32`.
+    pub windows_key_code: i32,
+    /// The key code of the platform.
+    pub native_key_code: i32,
+    /// Whether the key is a system key on Windows, like Alt+key.
+    pub is_system_key: bool,
+    /// The character produced by the key, or `None` when it is not in the Basic Multilingual Plane.
+    pub character: Option<char>,
+    /// The character produced by the key without the modifiers, except shift.
+    pub unmodified_character: Option<char>,
+    /// Whether the focused element of the page is editable, like a text field, in which case typing should
+    /// rarely trigger shortcuts.
+    pub focus_on_editable_field: bool,
+}
+
+impl KeyEvent {
+    pub fn from_raw(raw: &cef_key_event_t) -> Self {
+        KeyEvent {
+            event_type: KeyEventType::from_raw(raw.type_),
+            modifiers: Modifiers::from_bits_truncate(raw.modifiers),
+            windows_key_code: raw.windows_key_code,
+            native_key_code: raw.native_key_code,
+            is_system_key: raw.is_system_key != 0,
+            character: character(raw.character),
+            unmodified_character: character(raw.unmodified_character),
+            focus_on_editable_field: raw.focus_on_editable_field != 0,
+        }
+    }
+
+    pub fn to_raw(&self) -> cef_key_event_t {
+        cef_key_event_t {
+            type_: self.event_type.to_raw(),
+            modifiers: self.modifiers.bits(),
+            windows_key_code: self.windows_key_code,
+            native_key_code: self.native_key_code,
+            is_system_key: self.is_system_key as i32,
+            character: char16(self.character),
+            unmodified_character: char16(self.unmodified_character),
+            focus_on_editable_field: self.focus_on_editable_field as i32,
+        }
+    }
+
+    /// Whether this is the key press of `windows_key_code` with exactly the modifier keys `modifiers`, like
+    /// `event.is_shortcut(Modifiers::CONTROL, 'L' as i32)`.
+    pub fn is_shortcut(&self, modifiers: Modifiers, windows_key_code: i32) -> bool {
+        self.event_type == KeyEventType::RawKeyDown && self.windows_key_code == windows_key_code &&
+            self.modifiers.keys() == modifiers.keys()
+    }
+}
+
+fn character(character: char16) -> Option<char> {
+    match character {
+        0 => None,
+        character => ::std::char::from_u32(u32::from(character)),
+    }
+}
+
+fn char16(character: Option<char>) -> char16 {
+    let mut buffer = [0; 2];
+    match character {
+        Some(character) if character.len_utf16() == 1 => character.encode_utf16(&mut buffer)[0],
+        _ => 0,
+    }
+}
+
+/// Called for the keyboard events of a browser, on the UI thread.
+///
+/// The native event of the platform is not given to these callbacks.
+pub trait KeyboardHandler: Send + Sync {
+    /// Called before the page receives the event. Returns true if the event is handled, so the page does not
+    /// receive it.
+    ///
+    /// To only handle a shortcut when the page does not, set `is_keyboard_shortcut` to true and return false:
+    /// `on_key_event` is then called if the page does not handle the event.
+    fn on_pre_key_event(&self, _browser: &Browser, _event: &KeyEvent, _is_keyboard_shortcut: &mut bool) -> bool {
+        false
+    }
+
+    /// Called after the page handled the event, or not. Returns true if the event is handled.
+    fn on_key_event(&self, _browser: &Browser, _event: &KeyEvent) -> bool {
+        false
+    }
+}
+
+type KeyboardHandlerRc = RefCounted<cef_keyboard_handler_t, Arc<dyn KeyboardHandler>>;
+
+pub(crate) fn wrap_keyboard_handler(handler: Arc<dyn KeyboardHandler>) -> *mut cef_keyboard_handler_t {
+    extern "C" fn on_pre_key_event(self_: *mut cef_keyboard_handler_t, browser: *mut cef_browser_t,
+        event: *const cef_key_event_t, _os_event: cef_event_handle_t, is_keyboard_shortcut: *mut i32) -> i32
+    {
+        let handler = unsafe { KeyboardHandlerRc::value(self_) };
+        let browser = match unsafe { CefRc::<Browser>::from_raw(browser) } {
+            Some(browser) => browser,
+            None => return 0,
+        };
+        if event.is_null() {
+            return 0;
+        }
+        let event = KeyEvent::from_raw(unsafe { &*event });
+        let mut shortcut = !is_keyboard_shortcut.is_null() && unsafe { *is_keyboard_shortcut } != 0;
+        let handled = handler.on_pre_key_event(&browser, &event, &mut shortcut);
+        if !is_keyboard_shortcut.is_null() {
+            unsafe { *is_keyboard_shortcut = shortcut as i32 };
+        }
+        handled as i32
+    }
+
+    extern "C" fn on_key_event(self_: *mut cef_keyboard_handler_t, browser: *mut cef_browser_t,
+        event: *const cef_key_event_t, _os_event: cef_event_handle_t) -> i32
+    {
+        let handler = unsafe { KeyboardHandlerRc::value(self_) };
+        let browser = match unsafe { CefRc::<Browser>::from_raw(browser) } {
+            Some(browser) => browser,
+            None => return 0,
+        };
+        if event.is_null() {
+            return 0;
+        }
+        handler.on_key_event(&browser, &KeyEvent::from_raw(unsafe { &*event })) as i32
+    }
+
+    let mut raw: cef_keyboard_handler_t = unsafe { mem::zeroed() };
+    raw.on_pre_key_event = Some(on_pre_key_event);
+    raw.on_key_event = Some(on_key_event);
+    RefCounted::wrap(raw, handler)
+}
+
+#[cfg(test)]
+mod tests {
+    use mock::{self, Recorder, TestClient};
+    use super::*;
+
+    // Handles Ctrl+L before the page, and F5 after it.
+    impl KeyboardHandler for Recorder {
+        fn on_pre_key_event(&self, _browser: &Browser, event: &KeyEvent, is_keyboard_shortcut: &mut bool) -> bool {
+            self.push(format!("pre {:?} {:?} {:?}", event.event_type, event.modifiers,
+                event.character));
+            *is_keyboard_shortcut = event.is_shortcut(Modifiers::empty(), 0x74);
+            event.is_shortcut(Modifiers::CONTROL, 'L' as i32)
+        }
+
+        fn on_key_event(&self, _browser: &Browser, event: &KeyEvent) -> bool {
+            self.push(format!("key {}", event.windows_key_code));
+            event.is_shortcut(Modifiers::empty(), 0x74)
+        }
+    }
+
+    fn key_down(modifiers: Modifiers, windows_key_code: i32, character: Option<char>) -> KeyEvent {
+        KeyEvent {
+            event_type: KeyEventType::RawKeyDown,
+            modifiers,
+            windows_key_code,
+            native_key_code: 0,
+            is_system_key: false,
+            character,
+            unmodified_character: character,
+            focus_on_editable_field: false,
+        }
+    }
+
+    #[test]
+    fn keyboard_handler() {
+        mock::run(|| {
+            let shortcuts = Arc::new(Recorder::default());
+            let browser = mock::create_browser(TestClient::new().keyboard(shortcuts.clone()));
+            let id = browser.identifier();
+            assert!(mock::key_event(id, &key_down(Modifiers::CONTROL | Modifiers::NUM_LOCK_ON, 'L' as i32, Some('l'))));
+            assert!(mock::key_event(id, &key_down(Modifiers::empty(), 0x74, None)));
+            assert!(!mock::key_event(id, &key_down(Modifiers::SHIFT, 'A' as i32, Some('A'))));
+            assert_eq!(shortcuts.events(), [
+                "pre RawKeyDown CONTROL | NUM_LOCK_ON Some('l')",
+                "pre RawKeyDown (empty) None",
+                "key 116",
+                "pre RawKeyDown SHIFT Some('A')",
+                "key 65",
+            ]);
+        });
+    }
+
+    #[test]
+    fn raw_key_event() {
+        let event = KeyEvent {
+            event_type: KeyEventType::Char,
+            is_system_key: true,
+            focus_on_editable_field: true,
+            ..key_down(Modifiers::ALT | Modifiers::IS_LEFT, 'E' as i32, Some('é'))
+        };
+        assert_eq!(KeyEvent::from_raw(&event.to_raw()), event);
+        // The characters out of the Basic Multilingual Plane do not fit in a char16.
+        let emoji = key_down(Modifiers::empty(), 0, Some('🦀'));
+        assert_eq!(KeyEvent::from_raw(&emoji.to_raw()).character, None);
+    }
+}
@@ -1,3 +1,128 @@
 /*
  * TODO: use a plugin instead of webassembly for the DOM manipulation. Not sure.
  */
+
+#[macro_use]
+extern crate bitflags;
+#[cfg(feature = "futures-core")]
+extern crate futures_core;
+#[cfg(feature = "gtk")]
+extern crate gtk;
+
+#[macro_use]
+mod macros;
+
+mod app;
+mod browser;
+mod browser_settings;
+mod capture;
+mod client;
+mod command_line;
+mod context;
+mod context_menu;
+mod dialog;
+mod display;
+mod download;
+mod drag;
+mod error;
+mod find;
+mod focus;
+mod geometry;
+mod image;
+mod jsdialog;
+mod keyboard;
+mod life_span;
+mod load;
+mod menu_model;
+mod navigation;
+mod pending;
+mod png;
+mod process_message;
+mod rc;
+mod render;
+mod scheme;
+mod settings;
+mod string;
+mod task;
+mod transition;
+mod v8;
+mod window_info;
+#[cfg(test)]
+mod mock;
+pub mod sys;
+pub mod views;
+
+pub use app::{
+    App,
+    BrowserProcessHandler,
+    RenderProcessHandler,
+    ResourceBundleHandler,
+    wrap_app,
+};
+pub use browser::{Browser, BrowserHost, Frame};
+pub use browser_settings::{BrowserSettings, BrowserSettingsBuilder, Fonts};
+pub use capture::{Capture, CaptureError, CaptureOptions};
+pub use client::{Client, wrap_client};
+pub use command_line::CommandLine;
+pub use context::{Cef, MainArgs, StartError};
+pub use context_menu::{
+    ContextMenu,
+    ContextMenuHandler,
+    ContextMenuParams,
+    ContextMenuTypeFlags,
+    EditStateFlags,
+    MediaType,
+};
+#[cfg(feature = "gtk")]
+pub use dialog::GtkDialogHandler;
+pub use dialog::{
+    DialogHandler,
+    FileDialogCallback,
+    FileDialogMode,
+    FileDialogRequest,
+    ScriptedDialogHandler,
+};
+pub use display::{ConsoleMessage, DisplayHandler};
+pub use download::{
+    DownloadDecision,
+    DownloadDirectory,
+    DownloadHandler,
+    DownloadItem,
+    DownloadItemCallback,
+    DownloadState,
+};
+pub use drag::{DragData, DragHandler, DragOperations, DraggableRegion};
+pub use error::NetError;
+pub use find::{FindHandler, FindResult, FindResults, NextFindResult};
+pub use focus::{FocusHandler, FocusSource};
+pub use geometry::{Point, Rect};
+pub use image::Image;
+pub use jsdialog::{
+    AutoAccept,
+    AutoDismiss,
+    JsDialog,
+    JsDialogCallback,
+    JsDialogHandler,
+    JsDialogKind,
+    LogAndAccept,
+};
+pub use keyboard::{KeyEvent, KeyEventType, KeyboardHandler, Modifiers};
+pub use life_span::{
+    LifeSpanHandler,
+    PopupAction,
+    PopupFeatures,
+    PopupRequest,
+    WindowOpenDisposition,
+};
+pub use load::LoadHandler;
+pub use menu_model::{MenuItemType, MenuModel};
+pub use navigation::{HttpStatus, LoadError, Navigation};
+pub use png::encode_png;
+pub use process_message::{ProcessId, ProcessMessage, Value};
+pub use rc::{CefObject, CefRc, CefRefCounted, RefCounted};
+pub use render::{CursorType, CustomCursor, PaintElementType, RenderHandler, ScreenInfo};
+pub use scheme::{SchemeOptions, SchemeRegistrar};
+pub use settings::{Color, LogSeverity, Settings, SettingsBuilder, SettingsError};
+pub use string::{CefStr, CefString};
+pub use transition::{TransitionQualifiers, TransitionSource, TransitionType};
+pub use window_info::WindowInfo;
@@ -0,0 +1,326 @@
+use std::mem;
+use std::sync::Arc;
+
+use browser::{Browser, Frame};
+use browser_settings::BrowserSettings;
+use client::{Client, wrap_client};
+use navigation;
+use rc::{CefRc, RefCounted, release_base};
+use string::CefStr;
+use sys::*;
+use window_info::WindowInfo;
+
+/// Where the user wants a link to open.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowOpenDisposition {
+    CurrentTab,
+    SingletonTab,
+    NewForegroundTab,
+    NewBackgroundTab,
+    NewPopup,
+    NewWindow,
+    SaveToDisk,
+    OffTheRecord,
+    IgnoreAction,
+    /// `WOD_UNKNOWN`, or a disposition not known by this version of the bindings.
+    Unknown(i32),
+}
+
+impl WindowOpenDisposition {
+    pub fn from_raw(disposition: cef_window_open_disposition_t) -> Self {
+        match disposition {
+            WOD_CURRENT_TAB => WindowOpenDisposition::CurrentTab,
+            WOD_SINGLETON_TAB => WindowOpenDisposition::SingletonTab,
+            WOD_NEW_FOREGROUND_TAB => WindowOpenDisposition::NewForegroundTab,
+            WOD_NEW_BACKGROUND_TAB => WindowOpenDisposition::NewBackgroundTab,
+            WOD_NEW_POPUP => WindowOpenDisposition::NewPopup,
+            WOD_NEW_WINDOW => WindowOpenDisposition::NewWindow,
+            WOD_SAVE_TO_DISK => WindowOpenDisposition::SaveToDisk,
+            WOD_OFF_THE_RECORD => WindowOpenDisposition::OffTheRecord,
+            WOD_IGNORE_ACTION => WindowOpenDisposition::IgnoreAction,
+            disposition => WindowOpenDisposition::Unknown(disposition),
+        }
+    }
+}
+
+/// The features asked by `window.open()`. The unset sizes and positions are `None`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PopupFeatures {
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub menu_bar_visible: bool,
+    pub status_bar_visible: bool,
+    pub tool_bar_visible: bool,
+    pub scrollbars_visible: bool,
+}
+
+impl PopupFeatures {
+    pub fn from_raw(raw: &cef_popup_features_t) -> Self {
+        fn value(value: i32, set: i32) -> Option<i32> {
+            if set != 0 {
+                Some(value)
+            }
+            else {
+                None
+            }
+        }
+
+        PopupFeatures {
+            x: value(raw.x, raw.x_set),
+            y: value(raw.y, raw.y_set),
+            width: value(raw.width, raw.width_set),
+            height: value(raw.height, raw.height_set),
+            menu_bar_visible: raw.menu_bar_visible != 0,
+            status_bar_visible: raw.status_bar_visible != 0,
+            tool_bar_visible: raw.tool_bar_visible != 0,
+            scrollbars_visible: raw.scrollbars_visible != 0,
+        }
+    }
+}
+
+/// A popup that a page wants to open, with a link or `window.open()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PopupRequest {
+    pub target_url: String,
+    /// The name given to `window.open()`, or empty.
+    pub frame_name: String,
+    pub disposition: WindowOpenDisposition,
+    /// Whether the popup was opened by a click or a key press, rather than by a script.
+    pub user_gesture: bool,
+    pub features: PopupFeatures,
+}
+
+/// What to do with a popup.
+#[allow(clippy::large_enum_variant)]
+pub enum PopupAction {
+    Block,
+    /// Create the popup with the client, the settings and the window of the browser opening it.
+    Allow,
+    /// Create the popup, replacing what is not `None`.
+    AllowWith {
+        client: Option<Arc<dyn Client>>,
+        settings: Option<BrowserSettings>,
+        window_info: Option<WindowInfo>,
+    },
+    /// Block the popup and load its URL in the main frame of the browser opening it instead.
+    OpenInCurrent,
+}
+
+/// Called when a browser is created and destroyed, on the UI thread.
+pub trait LifeSpanHandler: Send + Sync {
+    /// Called on the UI thread before a popup browser is created. Allows it by default.
+    fn on_before_popup(&self, _browser: &Browser, _frame: &Frame, _request: &PopupRequest) -> PopupAction {
+        PopupAction::Allow
+    }
+
+    /// The first callback receiving the browser, which can be kept until `on_before_close`.
+    fn on_after_created(&self, _browser: &Browser) {
+    }
+
+    /// Called when the browser is asked to close, before its top-level window. Returns false to let CEF close
+    /// the window, or true if the application closes it itself.
+    fn do_close(&self, _browser: &Browser) -> bool {
+        false
+    }
+
+    /// The last callback receiving the browser: every reference to it must be released here.
+    fn on_before_close(&self, _browser: &Browser) {
+    }
+}
+
+/// Used when the client has no life span handler, since the navigations of `Browser::load_url` need to know when
+/// the browser is closed.
+pub(crate) struct DefaultLifeSpanHandler;
+
+impl LifeSpanHandler for DefaultLifeSpanHandler {}
+
+type LifeSpanHandlerRc = RefCounted<cef_life_span_handler_t, Arc<dyn LifeSpanHandler>>;
+
+pub(crate) fn wrap_life_span_handler(handler: Arc<dyn LifeSpanHandler>) -> *mut cef_life_span_handler_t {
+    extern "C" fn on_before_popup(self_: *mut cef_life_span_handler_t, browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t, target_url: *const cef_string_t, target_frame_name: *const cef_string_t,
+        target_disposition: cef_window_open_disposition_t, user_gesture: i32,
+        popup_features: *const cef_popup_features_t, window_info: *mut cef_window_info_t,
+        client: *mut *mut cef_client_t, settings: *mut cef_browser_settings_t, _no_javascript_access: *mut i32)
+        -> i32
+    {
+        let handler = unsafe { LifeSpanHandlerRc::value(self_) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        let frame = unsafe { CefRc::<Frame>::from_raw(frame) };
+        let (browser, frame) = match (browser, frame) {
+            (Some(browser), Some(frame)) => (browser, frame),
+            _ => return 0,
+        };
+        let request = PopupRequest {
+            target_url: unsafe { CefStr::from_ptr(target_url) }.to_string(),
+            frame_name: unsafe { CefStr::from_ptr(target_frame_name) }.to_string(),
+            disposition: WindowOpenDisposition::from_raw(target_disposition),
+            user_gesture: user_gesture != 0,
+            features:
+                if popup_features.is_null() {
+                    PopupFeatures::default()
+                }
+                else {
+                    PopupFeatures::from_raw(unsafe { &*popup_features })
+                },
+        };
+        match handler.on_before_popup(&browser, &frame, &request) {
+            PopupAction::Block => 1,
+            PopupAction::Allow => 0,
+            PopupAction::AllowWith { client: new_client, settings: new_settings, window_info: new_window_info } => {
+                unsafe {
+                    match new_client {
+                        Some(new_client) if !client.is_null() => {
+                            // The client of the popup owns a reference, given to CEF.
+                            if !(*client).is_null() {
+                                release_base(*client as *mut cef_base_ref_counted_t);
+                            }
+                            *client = wrap_client(new_client);
+                        },
+                        _ => (),
+                    }
+                    match new_settings {
+                        Some(new_settings) if !settings.is_null() => new_settings.copy_to(settings),
+                        _ => (),
+                    }
+                    match new_window_info {
+                        Some(new_window_info) if !window_info.is_null() => *window_info = new_window_info.to_raw(),
+                        _ => (),
+                    }
+                }
+                0
+            },
+            PopupAction::OpenInCurrent => {
+                if let Some(main_frame) = browser.main_frame() {
+                    main_frame.load_url(&request.target_url);
+                }
+                1
+            },
+        }
+    }
+
+    extern "C" fn on_after_created(self_: *mut cef_life_span_handler_t, browser: *mut cef_browser_t) {
+        let handler = unsafe { LifeSpanHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            handler.on_after_created(&browser);
+        }
+    }
+
+    extern "C" fn do_close(self_: *mut cef_life_span_handler_t, browser: *mut cef_browser_t) -> i32 {
+        let handler = unsafe { LifeSpanHandlerRc::value(self_) };
+        match unsafe { CefRc::<Browser>::from_raw(browser) } {
+            Some(browser) => handler.do_close(&browser) as i32,
+            None => 0,
+        }
+    }
+
+    extern "C" fn on_before_close(self_: *mut cef_life_span_handler_t, browser: *mut cef_browser_t) {
+        let handler = unsafe { LifeSpanHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            handler.on_before_close(&browser);
+            navigation::on_before_close(&browser);
+        }
+    }
+
+    let mut raw: cef_life_span_handler_t = unsafe { mem::zeroed() };
+    raw.on_before_popup = Some(on_before_popup);
+    raw.on_after_created = Some(on_after_created);
+    raw.do_close = Some(do_close);
+    raw.on_before_close = Some(on_before_close);
+    RefCounted::wrap(raw, handler)
+}
+
+#[cfg(test)]
+mod tests {
+    use mock::{self, Recorder, TestClient};
+    use super::*;
+
+    // Opens the popups with their own client, settings and window.
+    struct Replacing(Arc<Recorder>);
+
+    impl LifeSpanHandler for Replacing {
+        fn on_before_popup(&self, _browser: &Browser, _frame: &Frame, _request: &PopupRequest) -> PopupAction {
+            PopupAction::AllowWith {
+                client: Some(Arc::new(TestClient::new().life_span(self.0.clone()))),
+                settings: Some(BrowserSettings::builder().windowless_frame_rate(15).build()),
+                window_info: Some(WindowInfo::windowless(0)),
+            }
+        }
+    }
+
+    impl LifeSpanHandler for Recorder {
+        fn on_before_popup(&self, _browser: &Browser, _frame: &Frame, request: &PopupRequest) -> PopupAction {
+            self.push(format!("popup {} {:?} {}", request.target_url, request.disposition,
+                request.user_gesture));
+            match request.target_url.as_str() {
+                "https://block.com" => PopupAction::Block,
+                "https://current.com" => PopupAction::OpenInCurrent,
+                _ => PopupAction::Allow,
+            }
+        }
+
+        fn on_after_created(&self, browser: &Browser) {
+            let host = browser.host();
+            self.push(format!("created {} {} {}", browser.is_popup(), host.is_window_rendering_disabled(),
+                host.windowless_frame_rate()));
+        }
+
+        fn do_close(&self, _browser: &Browser) -> bool {
+            self.push("do_close");
+            false
+        }
+
+        fn on_before_close(&self, _browser: &Browser) {
+            self.push("before_close");
+        }
+    }
+
+    #[test]
+    fn popups() {
+        mock::run(|| {
+            let recorder = Arc::new(Recorder::default());
+            let browser = mock::create_browser(TestClient::new().life_span(recorder.clone()));
+            let id = browser.identifier();
+            assert_eq!(mock::popup(id, "https://block.com", WOD_NEW_POPUP), None);
+            assert_eq!(mock::popup(id, "https://current.com", WOD_NEW_FOREGROUND_TAB), None);
+            assert!(mock::calls().contains(&"Frame::load_url https://current.com".to_string()));
+            let popup = mock::popup(id, "https://allow.com", 42).unwrap();
+            assert_eq!(mock::browser_ids(), [id, popup]);
+            browser.host().close_browser(false);
+            mock::run_pending_tasks();
+            assert_eq!(mock::browser_ids(), [popup]);
+            assert_eq!(recorder.events(), [
+                "created false false 30",
+                "popup https://block.com NewPopup true",
+                "popup https://current.com NewForegroundTab true",
+                "popup https://allow.com Unknown(42) true",
+                "created true false 30",
+                "do_close",
+                "before_close",
+            ]);
+        });
+    }
+
+    #[test]
+    fn replaced_popup() {
+        mock::run(|| {
+            let recorder = Arc::new(Recorder::default());
+            let browser = mock::create_browser(TestClient::new().life_span(Arc::new(Replacing(recorder.clone()))));
+            let popup = mock::popup(browser.identifier(), "https://popup.com", WOD_NEW_POPUP).unwrap();
+            mock::close_window(popup);
+            assert_eq!(recorder.events(), ["created true true 15", "do_close", "before_close"]);
+            // The client of the closed popup is released, and the one of the browser opening it is kept.
+            assert_eq!(Arc::strong_count(&recorder), 2);
+            assert_eq!(mock::browser_ids(), [browser.identifier()]);
+        });
+    }
+
+    #[test]
+    fn dispositions() {
+        assert_eq!(WindowOpenDisposition::from_raw(WOD_NEW_WINDOW), WindowOpenDisposition::NewWindow);
+        assert_eq!(WindowOpenDisposition::from_raw(WOD_UNKNOWN), WindowOpenDisposition::Unknown(WOD_UNKNOWN));
+        assert_eq!(WindowOpenDisposition::from_raw(-1), WindowOpenDisposition::Unknown(-1));
+    }
+}
@@ -0,0 +1,150 @@
+use std::mem;
+use std::sync::Arc;
+
+use browser::{Browser, Frame};
+use error::NetError;
+use navigation::{self, HttpStatus};
+use rc::{CefRc, RefCounted};
+use string::CefStr;
+use sys::*;
+use transition::TransitionType;
+
+/// Called when the loading state of a browser changes.
+pub trait LoadHandler: Send + Sync {
+    /// Called when the loading starts and ends, whether it is initiated by the user or by the page.
+    fn on_loading_state_change(&self, _browser: &Browser, _is_loading: bool, _can_go_back: bool,
+        _can_go_forward: bool)
+    {
+    }
+
+    /// Called when a frame starts loading, once the navigation is committed. Each frame loads separately.
+    fn on_load_start(&self, _browser: &Browser, _frame: &Frame, _transition_type: TransitionType) {
+    }
+
+    /// Called when a frame is loaded, even if the page then keeps loading resources.
+    fn on_load_end(&self, _browser: &Browser, _frame: &Frame, _status: HttpStatus) {
+    }
+
+    /// Called when a navigation fails or is aborted, which can happen without `on_load_start`.
+    fn on_load_error(&self, _browser: &Browser, _frame: &Frame, _error: NetError, _error_text: &str,
+        _failed_url: &str)
+    {
+    }
+}
+
+/// Used when the client has no load handler, since the navigations of `Browser::load_url` need one.
+pub(crate) struct DefaultLoadHandler;
+
+impl LoadHandler for DefaultLoadHandler {}
+
+type LoadHandlerRc = RefCounted<cef_load_handler_t, Arc<dyn LoadHandler>>;
+
+pub(crate) fn wrap_load_handler(handler: Arc<dyn LoadHandler>) -> *mut cef_load_handler_t {
+    extern "C" fn on_loading_state_change(self_: *mut cef_load_handler_t, browser: *mut cef_browser_t,
+        is_loading: i32, can_go_back: i32, can_go_forward: i32)
+    {
+        let handler = unsafe { LoadHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            handler.on_loading_state_change(&browser, is_loading != 0, can_go_back != 0, can_go_forward != 0);
+        }
+    }
+
+    extern "C" fn on_load_start(self_: *mut cef_load_handler_t, browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t, transition_type: cef_transition_type_t)
+    {
+        let handler = unsafe { LoadHandlerRc::value(self_) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        let frame = unsafe { CefRc::<Frame>::from_raw(frame) };
+        if let (Some(browser), Some(frame)) = (browser, frame) {
+            handler.on_load_start(&browser, &frame, TransitionType::from_raw(transition_type));
+            navigation::on_load_start(&browser, &frame);
+        }
+    }
+
+    extern "C" fn on_load_end(self_: *mut cef_load_handler_t, browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t, http_status_code: i32)
+    {
+        let handler = unsafe { LoadHandlerRc::value(self_) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        let frame = unsafe { CefRc::<Frame>::from_raw(frame) };
+        if let (Some(browser), Some(frame)) = (browser, frame) {
+            let status = HttpStatus(http_status_code);
+            handler.on_load_end(&browser, &frame, status);
+            navigation::on_load_end(&browser, &frame, status);
+        }
+    }
+
+    extern "C" fn on_load_error(self_: *mut cef_load_handler_t, browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t, error_code: cef_errorcode_t, error_text: *const cef_string_t,
+        failed_url: *const cef_string_t)
+    {
+        let handler = unsafe { LoadHandlerRc::value(self_) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        let frame = unsafe { CefRc::<Frame>::from_raw(frame) };
+        if let (Some(browser), Some(frame)) = (browser, frame) {
+            let error = NetError::from_raw(error_code).unwrap_or(NetError::FAILED);
+            let error_text = unsafe { CefStr::from_ptr(error_text) }.to_string();
+            let failed_url = unsafe { CefStr::from_ptr(failed_url) }.to_string();
+            handler.on_load_error(&browser, &frame, error, &error_text, &failed_url);
+            navigation::on_load_error(&browser, &frame, error, &error_text, &failed_url);
+        }
+    }
+
+    let mut raw: cef_load_handler_t = unsafe { mem::zeroed() };
+    raw.on_loading_state_change = Some(on_loading_state_change);
+    raw.on_load_start = Some(on_load_start);
+    raw.on_load_end = Some(on_load_end);
+    raw.on_load_error = Some(on_load_error);
+    RefCounted::wrap(raw, handler)
+}
+
+#[cfg(test)]
+mod tests {
+    use mock::{self, Recorder, TestClient};
+    use super::*;
+
+    impl LoadHandler for Recorder {
+        fn on_loading_state_change(&self, _browser: &Browser, is_loading: bool, can_go_back: bool,
+            can_go_forward: bool)
+        {
+            self.push(format!("loading {} {} {}", is_loading, can_go_back, can_go_forward));
+        }
+
+        fn on_load_start(&self, _browser: &Browser, frame: &Frame, transition_type: TransitionType) {
+            self.push(format!("start {} {:?}", frame.is_main(), transition_type.source()));
+        }
+
+        fn on_load_end(&self, _browser: &Browser, frame: &Frame, status: HttpStatus) {
+            self.push(format!("end {} {}", frame.is_main(), status.0));
+        }
+
+        fn on_load_error(&self, _browser: &Browser, frame: &Frame, error: NetError, error_text: &str,
+            failed_url: &str)
+        {
+            self.push(format!("error {} {:?} {} {}", frame.is_main(), error.name(), error_text, failed_url));
+        }
+    }
+
+    #[test]
+    fn load_handler() {
+        mock::run(|| {
+            let recorder = Arc::new(Recorder::default());
+            let browser = mock::create_browser(TestClient::new().load(recorder.clone()));
+            let (id, main_frame) = (browser.identifier(), mock::main_frame_id(browser.identifier()).unwrap());
+            let subframe = mock::add_subframe(id, "child").unwrap();
+            mock::loading_state_change(id, true, true, false);
+            mock::load_start(id, main_frame, TT_LINK | TT_CHAIN_START_FLAG);
+            mock::load_start(id, subframe, TT_AUTO_SUBFRAME);
+            mock::load_error(id, subframe, ERR_CONNECTION_REFUSED, "net::ERR_CONNECTION_REFUSED", "https://a.com/");
+            mock::load_end(id, main_frame, 200);
+            assert_eq!(recorder.events(), [
+                "loading true true false",
+                "start true Link",
+                "start false AutoSubframe",
+                "error false Some(\"ERR_CONNECTION_REFUSED\") net::ERR_CONNECTION_REFUSED https://a.com/",
+                "end true 200",
+            ]);
+            assert!(browser.is_loading());
+        });
+    }
+}
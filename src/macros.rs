@@ -0,0 +1,17 @@
+/// Call a method of the vtable of a CEF object, passing the object itself as the `self` argument.
+macro_rules! call {
+    ($object:expr, $method:ident($($arg:expr),*)) => {{
+        let object = $object;
+        let method = object.$method.expect(concat!("CEF method ", stringify!($method), " is null"));
+        method(object as *const _ as *mut _ $(, $arg)*)
+    }};
+}
+
+/// Implement `CefObject` for a wrapper around a C struct.
+macro_rules! cef_object {
+    ($name:ident, $raw:ident) => {
+        unsafe impl ::rc::CefObject for $name {
+            type Raw = ::sys::$raw;
+        }
+    };
+}
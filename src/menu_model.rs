@@ -0,0 +1,155 @@
+use rc::CefRc;
+use string::CefString;
+use sys::{cef_menu_item_type_t, cef_menu_model_t};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuItemType {
+    /// There is no item at this index, or with this command id.
+    None,
+    Command,
+    Check,
+    Radio,
+    Separator,
+    SubMenu,
+}
+
+impl MenuItemType {
+    pub fn from_raw(item_type: cef_menu_item_type_t) -> Self {
+        match item_type {
+            cef_menu_item_type_t::MENUITEMTYPE_NONE => MenuItemType::None,
+            cef_menu_item_type_t::MENUITEMTYPE_COMMAND => MenuItemType::Command,
+            cef_menu_item_type_t::MENUITEMTYPE_CHECK => MenuItemType::Check,
+            cef_menu_item_type_t::MENUITEMTYPE_RADIO => MenuItemType::Radio,
+            cef_menu_item_type_t::MENUITEMTYPE_SEPARATOR => MenuItemType::Separator,
+            cef_menu_item_type_t::MENUITEMTYPE_SUBMENU => MenuItemType::SubMenu,
+        }
+    }
+
+    pub fn to_raw(self) -> cef_menu_item_type_t {
+        match self {
+            MenuItemType::None => cef_menu_item_type_t::MENUITEMTYPE_NONE,
+            MenuItemType::Command => cef_menu_item_type_t::MENUITEMTYPE_COMMAND,
+            MenuItemType::Check => cef_menu_item_type_t::MENUITEMTYPE_CHECK,
+            MenuItemType::Radio => cef_menu_item_type_t::MENUITEMTYPE_RADIO,
+            MenuItemType::Separator => cef_menu_item_type_t::MENUITEMTYPE_SEPARATOR,
+            MenuItemType::SubMenu => cef_menu_item_type_t::MENUITEMTYPE_SUBMENU,
+        }
+    }
+}
+
+/// The items of a menu, identified by their command id or by their index.
+///
+/// The methods changing the menu return false when the command id or the index is not found.
+#[repr(transparent)]
+pub struct MenuModel(cef_menu_model_t);
+
+cef_object!(MenuModel, cef_menu_model_t);
+
+impl MenuModel {
+    pub fn is_sub_menu(&self) -> bool {
+        call!(&self.0, is_sub_menu()) != 0
+    }
+
+    pub fn clear(&self) -> bool {
+        call!(&self.0, clear()) != 0
+    }
+
+    pub fn count(&self) -> usize {
+        call!(&self.0, get_count()) as usize
+    }
+
+    pub fn add_separator(&self) -> bool {
+        call!(&self.0, add_separator()) != 0
+    }
+
+    pub fn add_item(&self, command_id: i32, label: &str) -> bool {
+        let label = CefString::new(label);
+        call!(&self.0, add_item(command_id, label.as_raw())) != 0
+    }
+
+    pub fn add_check_item(&self, command_id: i32, label: &str) -> bool {
+        let label = CefString::new(label);
+        call!(&self.0, add_check_item(command_id, label.as_raw())) != 0
+    }
+
+    /// Checking a radio item unchecks the other items of its group.
+    pub fn add_radio_item(&self, command_id: i32, label: &str, group_id: i32) -> bool {
+        let label = CefString::new(label);
+        call!(&self.0, add_radio_item(command_id, label.as_raw(), group_id)) != 0
+    }
+
+    pub fn add_sub_menu(&self, command_id: i32, label: &str) -> Option<CefRc<MenuModel>> {
+        let label = CefString::new(label);
+        unsafe { CefRc::from_raw(call!(&self.0, add_sub_menu(command_id, label.as_raw()))) }
+    }
+
+    pub fn remove(&self, command_id: i32) -> bool {
+        call!(&self.0, remove(command_id)) != 0
+    }
+
+    pub fn remove_at(&self, index: usize) -> bool {
+        call!(&self.0, remove_at(index as i32)) != 0
+    }
+
+    pub fn index_of(&self, command_id: i32) -> Option<usize> {
+        match call!(&self.0, get_index_of(command_id)) {
+            index if index >= 0 => Some(index as usize),
+            _ => None,
+        }
+    }
+
+    /// The command id of a separator is -1.
+    pub fn command_id_at(&self, index: usize) -> i32 {
+        call!(&self.0, get_command_id_at(index as i32))
+    }
+
+    pub fn label(&self, command_id: i32) -> String {
+        unsafe { CefString::from_userfree(call!(&self.0, get_label(command_id))) }.to_string()
+    }
+
+    pub fn label_at(&self, index: usize) -> String {
+        unsafe { CefString::from_userfree(call!(&self.0, get_label_at(index as i32))) }.to_string()
+    }
+
+    pub fn set_label(&self, command_id: i32, label: &str) -> bool {
+        let label = CefString::new(label);
+        call!(&self.0, set_label(command_id, label.as_raw())) != 0
+    }
+
+    pub fn item_type(&self, command_id: i32) -> MenuItemType {
+        MenuItemType::from_raw(call!(&self.0, get_type(command_id)))
+    }
+
+    pub fn item_type_at(&self, index: usize) -> MenuItemType {
+        MenuItemType::from_raw(call!(&self.0, get_type_at(index as i32)))
+    }
+
+    pub fn sub_menu_at(&self, index: usize) -> Option<CefRc<MenuModel>> {
+        unsafe { CefRc::from_raw(call!(&self.0, get_sub_menu_at(index as i32))) }
+    }
+
+    pub fn is_visible(&self, command_id: i32) -> bool {
+        call!(&self.0, is_visible(command_id)) != 0
+    }
+
+    pub fn set_visible(&self, command_id: i32, visible: bool) -> bool {
+        call!(&self.0, set_visible(command_id, visible as i32)) != 0
+    }
+
+    pub fn is_enabled(&self, command_id: i32) -> bool {
+        call!(&self.0, is_enabled(command_id)) != 0
+    }
+
+    pub fn set_enabled(&self, command_id: i32, enabled: bool) -> bool {
+        call!(&self.0, set_enabled(command_id, enabled as i32)) != 0
+    }
+
+    /// Only for the check and radio items.
+    pub fn is_checked(&self, command_id: i32) -> bool {
+        call!(&self.0, is_checked(command_id)) != 0
+    }
+
+    pub fn set_checked(&self, command_id: i32, checked: bool) -> bool {
+        call!(&self.0, set_checked(command_id, checked as i32)) != 0
+    }
+}
@@ -0,0 +1,156 @@
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use string::{CefStr, CefString};
+use sys::*;
+use super::{Ptr, TASKS, browser, locked, post, record, release, run_pending_tasks, task, views};
+use super::command_line::{is_subprocess, process_command_line, set_global_args};
+
+static APP: Mutex<Option<Ptr<cef_app_t>>> = Mutex::new(None);
+static BROWSER_PROCESS_HANDLER: Mutex<Option<Ptr<cef_browser_process_handler_t>>> = Mutex::new(None);
+static QUIT: AtomicBool = AtomicBool::new(false);
+// Between cef_initialize and cef_shutdown, when the tasks can be posted.
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+pub(super) fn is_initialized() -> bool {
+    INITIALIZED.load(Ordering::SeqCst)
+}
+
+// Get a new reference on the render process handler of the app, like a render process would.
+pub(super) fn render_process_handler() -> *mut cef_render_process_handler_t {
+    let app = *locked(&APP);
+    match app {
+        Some(app) => unsafe {
+            match (*app.0).get_render_process_handler {
+                Some(get_render_process_handler) => get_render_process_handler(app.0),
+                None => ptr::null_mut(),
+            }
+        },
+        None => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn cef_execute_process(args: *const cef_main_args_t, application: *mut cef_app_t,
+    _windows_sandbox_info: *mut c_void) -> i32
+{
+    record("cef_execute_process");
+    unsafe {
+        release(application);
+        if is_subprocess(args) {
+            0
+        }
+        else {
+            -1
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn cef_initialize(args: *const cef_main_args_t, settings: *const cef_settings_t,
+    application: *mut cef_app_t, _windows_sandbox_info: *mut c_void) -> i32
+{
+    record("cef_initialize");
+    unsafe {
+        if settings.is_null() || (*settings).size != mem::size_of::<cef_settings_t>() {
+            release(application);
+            return 0;
+        }
+        set_global_args(args);
+        if !application.is_null() {
+            if let Some(on_before_command_line_processing) = (*application).on_before_command_line_processing {
+                let process_type = CefString::default();
+                on_before_command_line_processing(application, process_type.as_raw(), process_command_line());
+            }
+            if let Some(on_register_custom_schemes) = (*application).on_register_custom_schemes {
+                let mut registrar: cef_scheme_registrar_t = mem::zeroed();
+                registrar.base.size = mem::size_of::<cef_scheme_registrar_t>();
+                registrar.add_custom_scheme = Some(add_custom_scheme);
+                on_register_custom_schemes(application, &mut registrar);
+            }
+            if let Some(get_browser_process_handler) = (*application).get_browser_process_handler {
+                let handler = get_browser_process_handler(application);
+                if !handler.is_null() {
+                    let handler = Ptr(handler);
+                    *locked(&BROWSER_PROCESS_HANDLER) = Some(handler);
+                    post(move || {
+                        if let Some(on_context_initialized) = (*handler.0).on_context_initialized {
+                            on_context_initialized(handler.0);
+                        }
+                    });
+                }
+            }
+            *locked(&APP) = Some(Ptr(application));
+        }
+    }
+    INITIALIZED.store(true, Ordering::SeqCst);
+    1
+}
+
+extern "C" fn add_custom_scheme(_self: *mut cef_scheme_registrar_t, scheme_name: *const cef_string_t,
+    _is_standard: i32, _is_local: i32, _is_display_isolated: i32, _is_secure: i32, _is_cors_enabled: i32,
+    _is_csp_bypassing: i32, _is_fetch_enabled: i32) -> i32
+{
+    let name = unsafe { CefStr::from_ptr(scheme_name) };
+    record(format!("SchemeRegistrar::add_custom_scheme {}", name));
+    1
+}
+
+#[no_mangle]
+pub extern "C" fn cef_shutdown() {
+    record("cef_shutdown");
+    views::close_all();
+    browser::close_all();
+    INITIALIZED.store(false, Ordering::SeqCst);
+    // The pending tasks can own references.
+    let tasks = mem::take(&mut *locked(&TASKS));
+    drop(tasks);
+    task::clear();
+    let handler = locked(&BROWSER_PROCESS_HANDLER).take();
+    let app = locked(&APP).take();
+    unsafe {
+        release(handler.map(|handler| handler.0).unwrap_or(ptr::null_mut()));
+        release(app.map(|app| app.0).unwrap_or(ptr::null_mut()));
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn cef_do_message_loop_work() {
+    record("cef_do_message_loop_work");
+    run_pending_tasks();
+}
+
+/// Return once every task is done or when quit, since nothing else can happen in the mock.
+#[no_mangle]
+pub extern "C" fn cef_run_message_loop() {
+    record("cef_run_message_loop");
+    QUIT.store(false, Ordering::SeqCst);
+    run_tasks();
+}
+
+#[no_mangle]
+pub extern "C" fn cef_quit_message_loop() {
+    record("cef_quit_message_loop");
+    QUIT.store(true, Ordering::SeqCst);
+}
+
+#[no_mangle]
+pub extern "C" fn cef_set_osmodal_loop(_os_modal_loop: i32) {
+}
+
+#[no_mangle]
+pub extern "C" fn cef_enable_highdpi_support() {
+}
+
+fn run_tasks() {
+    while !QUIT.load(Ordering::SeqCst) {
+        let task = locked(&TASKS).pop_front();
+        match task {
+            Some(task) => task(),
+            None => break,
+        }
+    }
+}
@@ -0,0 +1,1250 @@
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicI32, AtomicI64, Ordering};
+
+use context_menu::ContextMenuParams;
+use dialog::FileDialogRequest;
+use download::DownloadItem;
+use drag::{DragOperations, DraggableRegion};
+use find::FindResult;
+use geometry::Rect;
+use focus::FocusSource;
+use jsdialog::JsDialog;
+use keyboard::KeyEvent;
+use process_message::{ProcessId, ProcessMessage};
+use rc::RefCounted;
+use render::{CursorType, PaintElementType, ScreenInfo};
+use string::{CefStr, CefString};
+use sys::*;
+use super::{Ptr, add_ref, app, locked, post, record, release};
+use super::callbacks::{
+    before_download_callback,
+    download_item_callback,
+    file_dialog_callback,
+    jsdialog_callback,
+};
+use super::context_menu::{default_menu, describe, new_params};
+use super::download::new_download_item;
+use super::drag::{DragContents, new_drag_data};
+use super::strings::userfree;
+use super::v8::new_v8context;
+
+// Call a method of a handler of the client of a browser, giving it new references on the arguments.
+macro_rules! notify {
+    ($browser:expr, $get_handler:ident, $method:ident($($arg:expr),*)) => {{
+        let client = value($browser).client.0;
+        let mut result = None;
+        if !client.is_null() {
+            let handler = match (*client).$get_handler {
+                Some(get_handler) => get_handler(client),
+                None => ptr::null_mut(),
+            };
+            if !handler.is_null() {
+                if let Some(method) = (*handler).$method {
+                    result = Some(method(handler, $($arg),*));
+                }
+                release(handler);
+            }
+        }
+        result
+    }};
+}
+
+// The browsers which are not closed yet, with a reference.
+static BROWSERS: Mutex<Vec<Ptr<cef_browser_t>>> = Mutex::new(Vec::new());
+static NEXT_BROWSER_ID: AtomicI32 = AtomicI32::new(1);
+static NEXT_FRAME_ID: AtomicI64 = AtomicI64::new(1);
+// The context menus shown by show_context_menu(), until an item is chosen or the menu is dismissed, at most one
+// by browser.
+static CONTEXT_MENUS: Mutex<Vec<ContextMenu>> = Mutex::new(Vec::new());
+
+struct Browser {
+    id: i32,
+    popup: bool,
+    windowless: bool,
+    has_view: bool,
+    client: Ptr<cef_client_t>,
+    host: Ptr<cef_browser_host_t>,
+    // The main frame is the first one.
+    frames: Vec<Ptr<cef_frame_t>>,
+    subframes: Mutex<Vec<Ptr<cef_frame_t>>>,
+    state: Mutex<State>,
+}
+
+impl Drop for Browser {
+    fn drop(&mut self) {
+        unsafe {
+            release(self.client.0);
+            release(self.host.0);
+            for frame in self.frames.iter().chain(locked(&self.subframes).iter()) {
+                release(frame.0);
+            }
+        }
+    }
+}
+
+struct ContextMenu {
+    browser_id: i32,
+    params: Ptr<cef_context_menu_params_t>,
+    model: Ptr<cef_menu_model_t>,
+}
+
+struct State {
+    is_loading: bool,
+    can_go_back: bool,
+    can_go_forward: bool,
+    zoom_level: f64,
+    frame_rate: i32,
+}
+
+struct Host {
+    browser_id: i32,
+}
+
+struct Frame {
+    browser_id: i32,
+    id: i64,
+    name: String,
+    parent: Option<i64>,
+    url: Mutex<String>,
+}
+
+unsafe fn value<'a>(browser: *mut cef_browser_t) -> &'a Browser {
+    RefCounted::<_, Browser>::value(browser)
+}
+
+fn frame_value<'a>(frame: *mut cef_frame_t) -> &'a Frame {
+    unsafe { RefCounted::<_, Frame>::value(frame) }
+}
+
+fn host_browser(host: *mut cef_browser_host_t) -> *mut cef_browser_t {
+    find(unsafe { RefCounted::<_, Host>::value(host) }.browser_id)
+}
+
+/// Get a new reference on an open browser.
+fn find(browser_id: i32) -> *mut cef_browser_t {
+    let browsers = locked(&BROWSERS);
+    let browser = browsers.iter()
+        .find(|browser| unsafe { value(browser.0) }.id == browser_id)
+        .map(|browser| browser.0)
+        .unwrap_or(ptr::null_mut());
+    unsafe { add_ref(browser) }
+}
+
+/// Get a new reference on a frame of a browser.
+unsafe fn find_frame(browser: *mut cef_browser_t, predicate: &dyn Fn(&Frame) -> bool) -> *mut cef_frame_t {
+    let value = value(browser);
+    let subframes = locked(&value.subframes);
+    let frame = value.frames.iter().chain(subframes.iter())
+        .find(|frame| predicate(frame_value(frame.0)))
+        .map(|frame| frame.0)
+        .unwrap_or(ptr::null_mut());
+    add_ref(frame)
+}
+
+fn create_frame(browser_id: i32, name: &str, parent: Option<i64>, url: &str) -> *mut cef_frame_t {
+    let mut raw: cef_frame_t = unsafe { mem::zeroed() };
+    raw.is_valid = Some(frame_is_valid);
+    raw.undo = Some(undo);
+    raw.redo = Some(redo);
+    raw.cut = Some(cut);
+    raw.copy = Some(copy);
+    raw.paste = Some(paste);
+    raw.del = Some(del);
+    raw.select_all = Some(select_all);
+    raw.view_source = Some(view_source);
+    raw.load_url = Some(load_url);
+    raw.load_string = Some(load_string);
+    raw.execute_java_script = Some(execute_java_script);
+    raw.is_main = Some(is_main);
+    raw.is_focused = Some(is_focused);
+    raw.get_name = Some(get_name);
+    raw.get_identifier = Some(get_identifier);
+    raw.get_parent = Some(get_parent);
+    raw.get_url = Some(get_url);
+    raw.get_browser = Some(frame_get_browser);
+    raw.get_v8context = Some(get_v8context);
+    RefCounted::wrap(raw, Frame {
+        browser_id,
+        id: NEXT_FRAME_ID.fetch_add(1, Ordering::SeqCst),
+        name: name.to_string(),
+        parent,
+        url: Mutex::new(url.to_string()),
+    })
+}
+
+fn create_host(browser_id: i32) -> *mut cef_browser_host_t {
+    let mut raw: cef_browser_host_t = unsafe { mem::zeroed() };
+    raw.get_browser = Some(host_get_browser);
+    raw.close_browser = Some(close_browser);
+    raw.try_close_browser = Some(try_close_browser);
+    raw.set_focus = Some(set_focus);
+    raw.get_window_handle = Some(get_window_handle);
+    raw.get_opener_window_handle = Some(get_window_handle);
+    raw.has_view = Some(has_view);
+    raw.get_client = Some(get_client);
+    raw.get_zoom_level = Some(get_zoom_level);
+    raw.set_zoom_level = Some(set_zoom_level);
+    raw.start_download = Some(start_download);
+    raw.print = Some(print);
+    raw.find = Some(find_text);
+    raw.stop_finding = Some(stop_finding);
+    raw.close_dev_tools = Some(close_dev_tools);
+    raw.has_dev_tools = Some(has_dev_tools);
+    raw.is_window_rendering_disabled = Some(is_window_rendering_disabled);
+    raw.was_resized = Some(was_resized);
+    raw.invalidate = Some(invalidate);
+    raw.was_hidden = Some(was_hidden);
+    raw.notify_screen_info_changed = Some(notify_screen_info_changed);
+    raw.get_windowless_frame_rate = Some(get_windowless_frame_rate);
+    raw.set_windowless_frame_rate = Some(set_windowless_frame_rate);
+    RefCounted::wrap(raw, Host {
+        browser_id,
+    })
+}
+
+/// Create a browser, taking the reference of the client, and return it with a reference for the caller.
+pub(super) unsafe fn create(client: *mut cef_client_t, url: *const cef_string_t,
+    settings: *const cef_browser_settings_t, windowless: bool, has_view: bool, popup: bool) -> *mut cef_browser_t
+{
+    let id = NEXT_BROWSER_ID.fetch_add(1, Ordering::SeqCst);
+    let url = CefStr::from_ptr(url).to_string();
+    record(format!("create_browser {} {}", id, url));
+
+    let frame_rate =
+        if settings.is_null() || (*settings).windowless_frame_rate == 0 {
+            30
+        }
+        else {
+            (*settings).windowless_frame_rate
+        };
+
+    let mut raw: cef_browser_t = mem::zeroed();
+    raw.get_host = Some(get_host);
+    raw.can_go_back = Some(can_go_back);
+    raw.go_back = Some(go_back);
+    raw.can_go_forward = Some(can_go_forward);
+    raw.go_forward = Some(go_forward);
+    raw.is_loading = Some(is_loading);
+    raw.reload = Some(reload);
+    raw.reload_ignore_cache = Some(reload_ignore_cache);
+    raw.stop_load = Some(stop_load);
+    raw.get_identifier = Some(get_browser_identifier);
+    raw.is_same = Some(is_same);
+    raw.is_popup = Some(is_popup);
+    raw.has_document = Some(has_document);
+    raw.get_main_frame = Some(get_main_frame);
+    raw.get_focused_frame = Some(get_main_frame);
+    raw.get_frame_byident = Some(get_frame_byident);
+    raw.get_frame = Some(get_frame);
+    raw.get_frame_count = Some(get_frame_count);
+    raw.get_frame_identifiers = Some(get_frame_identifiers);
+    raw.get_frame_names = Some(get_frame_names);
+    raw.send_process_message = Some(send_process_message);
+    let browser = RefCounted::wrap(raw, Browser {
+        id,
+        popup,
+        windowless,
+        has_view,
+        client: Ptr(client),
+        host: Ptr(create_host(id)),
+        frames: vec![Ptr(create_frame(id, "", None, &url))],
+        subframes: Mutex::new(vec![]),
+        state: Mutex::new(State {
+            is_loading: false,
+            can_go_back: false,
+            can_go_forward: false,
+            zoom_level: 0.0,
+            frame_rate,
+        }),
+    });
+    locked(&BROWSERS).push(Ptr(add_ref(browser)));
+
+    notify!(browser, get_life_span_handler, on_after_created(add_ref(browser)));
+    browser
+}
+
+pub(super) unsafe fn close(browser_id: i32, force: bool) {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return;
+    }
+    record(format!("close_browser {}", browser_id));
+    if !force {
+        notify!(browser, get_life_span_handler, do_close(add_ref(browser)));
+    }
+    notify!(browser, get_life_span_handler, on_before_close(add_ref(browser)));
+    let closed = {
+        let mut browsers = locked(&BROWSERS);
+        let index = browsers.iter().position(|open_browser| open_browser.0 == browser);
+        index.map(|index| browsers.remove(index))
+    };
+    if let Some(closed) = closed {
+        release(closed.0);
+    }
+    release(browser);
+}
+
+/// Close the browsers left open at shutdown.
+pub(super) fn close_all() {
+    let menus: Vec<_> = locked(&CONTEXT_MENUS).iter().map(|menu| menu.browser_id).collect();
+    for browser_id in menus {
+        dismiss_context_menu(browser_id);
+    }
+    for browser_id in browser_ids() {
+        unsafe { close(browser_id, true) };
+    }
+}
+
+/// Ask a browser to close, like the user closing its window.
+pub fn close_window(browser_id: i32) {
+    unsafe { close(browser_id, false) };
+}
+
+/// The identifiers of the browsers which are not closed yet, in creation order.
+pub fn browser_ids() -> Vec<i32> {
+    locked(&BROWSERS).iter()
+        .map(|browser| unsafe { value(browser.0) }.id)
+        .collect()
+}
+
+pub fn main_frame_id(browser_id: i32) -> Option<i64> {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return None;
+    }
+    unsafe {
+        let id = frame_value(value(browser).frames[0].0).id;
+        release(browser);
+        Some(id)
+    }
+}
+
+/// Add a frame to the main frame of a browser and return its identifier.
+pub fn add_subframe(browser_id: i32, name: &str) -> Option<i64> {
+    let parent = main_frame_id(browser_id)?;
+    let browser = find(browser_id);
+    unsafe {
+        let frame = create_frame(browser_id, name, Some(parent), "");
+        let id = frame_value(frame).id;
+        locked(&value(browser).subframes).push(Ptr(frame));
+        release(browser);
+        Some(id)
+    }
+}
+
+// Call f with a browser and one of its frames, if they exist.
+fn with_frame<F: FnOnce(*mut cef_browser_t, *mut cef_frame_t)>(browser_id: i32, frame_id: i64, f: F) {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return;
+    }
+    unsafe {
+        let frame = find_frame(browser, &|frame| frame.id == frame_id);
+        if !frame.is_null() {
+            f(browser, frame);
+            release(frame);
+        }
+        release(browser);
+    }
+}
+
+pub fn load_start(browser_id: i32, frame_id: i64, transition_type: cef_transition_type_t) {
+    with_frame(browser_id, frame_id, |browser, frame| unsafe {
+        notify!(browser, get_load_handler, on_load_start(add_ref(browser), add_ref(frame), transition_type));
+    });
+}
+
+pub fn load_end(browser_id: i32, frame_id: i64, http_status_code: i32) {
+    with_frame(browser_id, frame_id, |browser, frame| unsafe {
+        notify!(browser, get_load_handler, on_load_end(add_ref(browser), add_ref(frame), http_status_code));
+    });
+}
+
+pub fn load_error(browser_id: i32, frame_id: i64, error_code: cef_errorcode_t, error_text: &str, failed_url: &str) {
+    let error_text = CefString::new(error_text);
+    let failed_url = CefString::new(failed_url);
+    with_frame(browser_id, frame_id, |browser, frame| unsafe {
+        notify!(browser, get_load_handler, on_load_error(add_ref(browser), add_ref(frame), error_code,
+            error_text.as_raw(), failed_url.as_raw()));
+    });
+}
+
+pub fn loading_state_change(browser_id: i32, is_loading: bool, can_go_back: bool, can_go_forward: bool) {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return;
+    }
+    unsafe {
+        {
+            let mut state = locked(&value(browser).state);
+            state.is_loading = is_loading;
+            state.can_go_back = can_go_back;
+            state.can_go_forward = can_go_forward;
+        }
+        notify!(browser, get_load_handler, on_loading_state_change(add_ref(browser), is_loading as i32,
+            can_go_back as i32, can_go_forward as i32));
+        release(browser);
+    }
+}
+
+pub fn title_change(browser_id: i32, title: &str) {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return;
+    }
+    let title = CefString::new(title);
+    unsafe {
+        notify!(browser, get_display_handler, on_title_change(add_ref(browser), title.as_raw()));
+        release(browser);
+    }
+}
+
+/// Write a message to the console of a browser, and return whether the display handler suppressed it.
+pub fn console_message(browser_id: i32, level: cef_log_severity_t, message: &str, source: &str, line: i32) -> bool {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return false;
+    }
+    let message = CefString::new(message);
+    let source = CefString::new(source);
+    unsafe {
+        let suppressed = notify!(browser, get_display_handler, on_console_message(add_ref(browser), level,
+            message.as_raw(), source.as_raw(), line));
+        release(browser);
+        suppressed == Some(1)
+    }
+}
+
+pub fn favicon_url_change(browser_id: i32, icon_urls: &[&str]) {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return;
+    }
+    unsafe {
+        let list = cef_string_list_alloc();
+        for url in icon_urls {
+            cef_string_list_append(list, CefString::new(url).as_raw());
+        }
+        notify!(browser, get_display_handler, on_favicon_urlchange(add_ref(browser), list));
+        cef_string_list_free(list);
+        release(browser);
+    }
+}
+
+pub fn loading_progress_change(browser_id: i32, progress: f64) {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return;
+    }
+    unsafe {
+        notify!(browser, get_display_handler, on_loading_progress_change(add_ref(browser), progress));
+        release(browser);
+    }
+}
+
+pub fn address_change(browser_id: i32, frame_id: i64, url: &str) {
+    let cef_url = CefString::new(url);
+    with_frame(browser_id, frame_id, |browser, frame| unsafe {
+        *locked(&frame_value(frame).url) = url.to_string();
+        notify!(browser, get_display_handler, on_address_change(add_ref(browser), add_ref(frame), cef_url.as_raw()));
+    });
+}
+
+/// Ask the life span handler to open a popup from the main frame of a browser, and return the identifier of the
+/// popup if it is allowed.
+pub fn popup(browser_id: i32, target_url: &str, disposition: cef_window_open_disposition_t) -> Option<i32> {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return None;
+    }
+    record(format!("popup {} {}", browser_id, target_url));
+    unsafe {
+        let frame = add_ref(value(browser).frames[0].0);
+        let url = CefString::new(target_url);
+        let frame_name = CefString::default();
+        let features: cef_popup_features_t = mem::zeroed();
+        let mut window_info: cef_window_info_t = mem::zeroed();
+        // The client of the popup, which the handler can replace, owns a reference.
+        let mut client = add_ref(value(browser).client.0);
+        let mut settings: cef_browser_settings_t = mem::zeroed();
+        settings.size = mem::size_of::<cef_browser_settings_t>();
+        let mut no_javascript_access = 0;
+        let cancel = notify!(browser, get_life_span_handler, on_before_popup(add_ref(browser), frame,
+            url.as_raw(), frame_name.as_raw(), disposition, 1, &features,
+            &mut window_info, &mut client, &mut settings, &mut no_javascript_access));
+        release(browser);
+        if cancel == Some(1) {
+            release(client);
+            clear_settings(&mut settings);
+            return None;
+        }
+        let popup = create(client, url.as_raw(), &settings, window_info.windowless_rendering_enabled != 0, false,
+            true);
+        clear_settings(&mut settings);
+        let id = value(popup).id;
+        release(popup);
+        Some(id)
+    }
+}
+
+// The strings of the settings of a popup are owned by CEF, once the handler has set them.
+unsafe fn clear_settings(settings: &mut cef_browser_settings_t) {
+    for string in &mut [&mut settings.standard_font_family, &mut settings.fixed_font_family,
+        &mut settings.serif_font_family, &mut settings.sans_serif_font_family, &mut settings.cursive_font_family,
+        &mut settings.fantasy_font_family, &mut settings.default_encoding, &mut settings.accept_language_list]
+    {
+        cef_string_utf16_clear(*string);
+    }
+}
+
+/// Tab out of the page of a browser, forward when `next` is true.
+pub fn take_focus(browser_id: i32, next: bool) {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return;
+    }
+    record(format!("take_focus {} {}", browser_id, next));
+    unsafe {
+        notify!(browser, get_focus_handler, on_take_focus(add_ref(browser), next as i32));
+        release(browser);
+    }
+}
+
+/// Give the focus to a browser, unless its focus handler cancels it, and return whether it got the focus.
+pub fn give_focus(browser_id: i32, source: FocusSource) -> bool {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return false;
+    }
+    record(format!("give_focus {} {:?}", browser_id, source));
+    unsafe {
+        let cancel = notify!(browser, get_focus_handler, on_set_focus(add_ref(browser), source.to_raw()));
+        if cancel != Some(1) {
+            notify!(browser, get_focus_handler, on_got_focus(add_ref(browser)));
+        }
+        release(browser);
+        cancel != Some(1)
+    }
+}
+
+/// Send a key event to a browser whose page does not handle it, and return whether the keyboard handler did.
+pub fn key_event(browser_id: i32, event: &KeyEvent) -> bool {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return false;
+    }
+    record(format!("key_event {} {}", browser_id, event.windows_key_code));
+    let event = event.to_raw();
+    unsafe {
+        let mut is_keyboard_shortcut = 0;
+        let handled = notify!(browser, get_keyboard_handler, on_pre_key_event(add_ref(browser), &event,
+            ptr::null_mut(), &mut is_keyboard_shortcut)) == Some(1) ||
+            notify!(browser, get_keyboard_handler, on_key_event(add_ref(browser), &event, ptr::null_mut())) == Some(1);
+        release(browser);
+        handled
+    }
+}
+
+/// Right-click in the main frame of a browser, and return the items of the menu shown, like `"26500 Open"`,
+/// `"-"` for a separator, `"26501 [x] Check"` or `"26502 Sub menu >"` followed by its indented items.
+///
+/// The default menu has the items 100 Back, 101 Forward, a separator and 132 View source. It is dismissed right
+/// away when the handler removes every item.
+pub fn show_context_menu(browser_id: i32, params: &ContextMenuParams) -> Vec<String> {
+    dismiss_context_menu(browser_id);
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return vec![];
+    }
+    record(format!("show_context_menu {}", browser_id));
+    let params = new_params(params);
+    let model = default_menu();
+    unsafe {
+        let frame = find_frame(browser, &|frame| frame.parent.is_none());
+        notify!(browser, get_context_menu_handler, on_before_context_menu(add_ref(browser), add_ref(frame),
+            add_ref(params), add_ref(model)));
+        release(frame);
+        release(browser);
+    }
+    let items = describe(model);
+    locked(&CONTEXT_MENUS).push(ContextMenu {
+        browser_id,
+        params: Ptr(params),
+        model: Ptr(model),
+    });
+    if items.is_empty() {
+        dismiss_context_menu(browser_id);
+    }
+    items
+}
+
+/// Choose an item of the context menu shown in a browser, and return whether the handler handled its command.
+pub fn choose_context_menu_item(browser_id: i32, command_id: i32) -> bool {
+    let params = match locked(&CONTEXT_MENUS).iter().find(|menu| menu.browser_id == browser_id) {
+        Some(menu) => menu.params,
+        None => return false,
+    };
+    record(format!("choose_context_menu_item {} {}", browser_id, command_id));
+    let browser = find(browser_id);
+    let mut handled = false;
+    if !browser.is_null() {
+        unsafe {
+            let frame = find_frame(browser, &|frame| frame.parent.is_none());
+            handled = notify!(browser, get_context_menu_handler, on_context_menu_command(add_ref(browser),
+                add_ref(frame), add_ref(params.0), command_id, EVENTFLAG_NONE)) == Some(1);
+            release(frame);
+            release(browser);
+        }
+    }
+    dismiss_context_menu(browser_id);
+    handled
+}
+
+/// Close the context menu shown in a browser without choosing an item.
+pub fn dismiss_context_menu(browser_id: i32) {
+    let menu = {
+        let mut menus = locked(&CONTEXT_MENUS);
+        match menus.iter().position(|menu| menu.browser_id == browser_id) {
+            Some(index) => menus.remove(index),
+            None => return,
+        }
+    };
+    let browser = find(menu.browser_id);
+    unsafe {
+        if !browser.is_null() {
+            let frame = find_frame(browser, &|frame| frame.parent.is_none());
+            notify!(browser, get_context_menu_handler, on_context_menu_dismissed(add_ref(browser), add_ref(frame)));
+            release(frame);
+            release(browser);
+        }
+        release(menu.params.0);
+        release(menu.model.0);
+    }
+}
+
+/// Open a JavaScript dialog in a browser, and return whether its handler answers it.
+///
+/// The answer is recorded as a call, like `"JsDialogCallback::cont true text"`, and a suppressed message as
+/// `"suppress_message"`.
+pub fn jsdialog(browser_id: i32, dialog: &JsDialog) -> bool {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return false;
+    }
+    record(format!("jsdialog {} {:?}", browser_id, dialog.kind));
+    let origin_url = CefString::new(&dialog.origin_url);
+    let message = CefString::new(&dialog.message);
+    let default_prompt = CefString::new(&dialog.default_prompt);
+    unsafe {
+        let mut suppress_message = 0;
+        let handled = notify!(browser, get_jsdialog_handler, on_jsdialog(add_ref(browser), origin_url.as_raw(),
+            dialog.kind.to_raw(), message.as_raw(), default_prompt.as_raw(), jsdialog_callback(),
+            &mut suppress_message)) == Some(1);
+        if !handled && suppress_message != 0 {
+            record("suppress_message");
+        }
+        release(browser);
+        handled
+    }
+}
+
+/// Ask to confirm leaving the page of a browser, and return whether its handler answers it.
+pub fn before_unload_dialog(browser_id: i32, message: &str, is_reload: bool) -> bool {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return false;
+    }
+    record(format!("before_unload_dialog {}", browser_id));
+    let message = CefString::new(message);
+    unsafe {
+        let handled = notify!(browser, get_jsdialog_handler, on_before_unload_dialog(add_ref(browser),
+            message.as_raw(), is_reload as i32, jsdialog_callback())) == Some(1);
+        release(browser);
+        handled
+    }
+}
+
+/// Close the dialogs of a browser not answered yet, like when its page navigates away.
+pub fn reset_dialog_state(browser_id: i32) {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return;
+    }
+    unsafe {
+        notify!(browser, get_jsdialog_handler, on_reset_dialog_state(add_ref(browser)));
+        release(browser);
+    }
+}
+
+/// Start a download in a browser. The decision of its handler is recorded as a call, like
+/// `"BeforeDownloadCallback::cont false /tmp/file.txt"`, and nothing is recorded when it is canceled.
+pub fn before_download(browser_id: i32, item: &DownloadItem) {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return;
+    }
+    record(format!("before_download {} {}", browser_id, item.id));
+    let suggested_name = CefString::new(&item.suggested_file_name);
+    unsafe {
+        notify!(browser, get_download_handler, on_before_download(add_ref(browser), new_download_item(item),
+            suggested_name.as_raw(), before_download_callback()));
+        release(browser);
+    }
+}
+
+/// Report the progress of a download to the handler of a browser. The calls to its callback are recorded, like
+/// `"DownloadItemCallback::pause 1"`.
+pub fn download_updated(browser_id: i32, item: &DownloadItem) {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return;
+    }
+    unsafe {
+        notify!(browser, get_download_handler, on_download_updated(add_ref(browser), new_download_item(item),
+            download_item_callback(item.id)));
+        release(browser);
+    }
+}
+
+/// Open a file chooser in a browser, and return whether its handler answers it.
+///
+/// The answer is recorded as a call, like `"FileDialogCallback::cont 0 /tmp/a.txt;/tmp/b.txt"` or
+/// `"FileDialogCallback::cancel"`.
+pub fn file_dialog(browser_id: i32, request: &FileDialogRequest) -> bool {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return false;
+    }
+    record(format!("file_dialog {} {:?}", browser_id, request.mode));
+    let mut mode = request.mode.to_raw();
+    if request.overwrite_prompt {
+        mode |= FILE_DIALOG_OVERWRITEPROMPT_FLAG;
+    }
+    if request.hide_read_only {
+        mode |= FILE_DIALOG_HIDEREADONLY_FLAG;
+    }
+    let title = CefString::new(&request.title);
+    let default_path = request.default_path.as_ref().map(|path| path.to_string_lossy().into_owned());
+    let default_path = CefString::new(&default_path.unwrap_or_default());
+    unsafe {
+        let accept_filters = cef_string_list_alloc();
+        for filter in &request.accept_filters {
+            cef_string_list_append(accept_filters, CefString::new(filter).as_raw());
+        }
+        let handled = notify!(browser, get_dialog_handler, on_file_dialog(add_ref(browser), mode, title.as_raw(),
+            default_path.as_raw(), accept_filters, request.selected_accept_filter as i32, file_dialog_callback()))
+            == Some(1);
+        cef_string_list_free(accept_filters);
+        release(browser);
+        handled
+    }
+}
+
+/// Drag something into a browser, and return whether its drag handler refused it.
+pub fn drag_enter(browser_id: i32, contents: &DragContents, mask: DragOperations) -> bool {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return false;
+    }
+    record(format!("drag_enter {}", browser_id));
+    unsafe {
+        let refused = notify!(browser, get_drag_handler, on_drag_enter(add_ref(browser), new_drag_data(contents),
+            mask.bits())) == Some(1);
+        release(browser);
+        refused
+    }
+}
+
+pub fn draggable_regions_changed(browser_id: i32, regions: &[DraggableRegion]) {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return;
+    }
+    let regions: Vec<_> = regions.iter().map(DraggableRegion::to_raw).collect();
+    unsafe {
+        notify!(browser, get_drag_handler, on_draggable_regions_changed(add_ref(browser), regions.len(),
+            regions.as_ptr()));
+        release(browser);
+    }
+}
+
+/// Report the result of a search to the find handler of a browser.
+pub fn find_result(browser_id: i32, result: &FindResult) {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return;
+    }
+    let selection_rect = result.selection_rect.to_raw();
+    unsafe {
+        notify!(browser, get_find_handler, on_find_result(add_ref(browser), result.identifier, result.count,
+            &selection_rect, result.active_match_ordinal, result.final_update as i32));
+        release(browser);
+    }
+}
+
+/// Ask the render handler of a browser for the bounds of its view, like CEF does before painting.
+pub fn view_rect(browser_id: i32) -> Option<Rect> {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return None;
+    }
+    let mut rect = Rect::default().to_raw();
+    unsafe {
+        let result = notify!(browser, get_render_handler, get_view_rect(add_ref(browser), &mut rect));
+        release(browser);
+        result.map(|()| Rect::from_raw(&rect))
+    }
+}
+
+/// Ask the render handler of a browser to describe its screen, and return `None` if it keeps the default values.
+pub fn screen_info(browser_id: i32) -> Option<ScreenInfo> {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return None;
+    }
+    let mut info = ScreenInfo::default().to_raw();
+    unsafe {
+        let result = notify!(browser, get_render_handler, get_screen_info(add_ref(browser), &mut info));
+        release(browser);
+        if result == Some(1) { Some(ScreenInfo::from_raw(&info)) } else { None }
+    }
+}
+
+/// Paint the view or the popup widget of a browser with a BGRA `buffer` of `width * height * 4` bytes.
+pub fn paint(browser_id: i32, element_type: PaintElementType, dirty_rects: &[Rect], buffer: &[u8], width: i32,
+    height: i32)
+{
+    assert_eq!(buffer.len(), width.max(0) as usize * height.max(0) as usize * 4, "size of the paint buffer");
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return;
+    }
+    record(format!("paint {} {:?} {}x{}", browser_id, element_type, width, height));
+    let dirty_rects: Vec<_> = dirty_rects.iter().map(Rect::to_raw).collect();
+    unsafe {
+        notify!(browser, get_render_handler, on_paint(add_ref(browser), element_type.to_raw(), dirty_rects.len(),
+            dirty_rects.as_ptr(), buffer.as_ptr() as *const c_void, width, height));
+        release(browser);
+    }
+}
+
+pub fn popup_show(browser_id: i32, show: bool) {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return;
+    }
+    unsafe {
+        notify!(browser, get_render_handler, on_popup_show(add_ref(browser), show as i32));
+        release(browser);
+    }
+}
+
+pub fn popup_size(browser_id: i32, rect: Rect) {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return;
+    }
+    let rect = rect.to_raw();
+    unsafe {
+        notify!(browser, get_render_handler, on_popup_size(add_ref(browser), &rect));
+        release(browser);
+    }
+}
+
+/// Change the cursor over a browser to one of the standard cursors.
+pub fn cursor_change(browser_id: i32, cursor_type: CursorType) {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return;
+    }
+    unsafe {
+        notify!(browser, get_render_handler, on_cursor_change(add_ref(browser), 0, cursor_type.to_raw(),
+            ptr::null()));
+        release(browser);
+    }
+}
+
+/// Deliver a message sent by the render process to the client of a browser, and return whether it was handled.
+pub fn receive_process_message(browser_id: i32, message: &ProcessMessage) -> bool {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return false;
+    }
+    record(format!("receive_process_message {} {}", browser_id, message.name));
+    unsafe {
+        let client = value(browser).client.0;
+        let mut handled = false;
+        if !client.is_null() {
+            if let Some(on_process_message_received) = (*client).on_process_message_received {
+                handled = on_process_message_received(client, add_ref(browser), cef_process_id_t::PID_RENDERER,
+                    message.to_raw()) != 0;
+            }
+        }
+        release(browser);
+        handled
+    }
+}
+
+/// Deliver a message sent by the browser process to the render process handler of the `App`, and return whether
+/// it was handled.
+pub fn render_process_message(browser_id: i32, message: &ProcessMessage) -> bool {
+    let browser = find(browser_id);
+    if browser.is_null() {
+        return false;
+    }
+    record(format!("render_process_message {} {}", browser_id, message.name));
+    unsafe {
+        let handler = app::render_process_handler();
+        let mut handled = false;
+        if !handler.is_null() {
+            if let Some(on_process_message_received) = (*handler).on_process_message_received {
+                handled = on_process_message_received(handler, add_ref(browser), cef_process_id_t::PID_BROWSER,
+                    message.to_raw()) != 0;
+            }
+            release(handler);
+        }
+        release(browser);
+        handled
+    }
+}
+
+extern "C" fn get_host(self_: *mut cef_browser_t) -> *mut cef_browser_host_t {
+    unsafe { add_ref(value(self_).host.0) }
+}
+
+extern "C" fn can_go_back(self_: *mut cef_browser_t) -> i32 {
+    unsafe { locked(&value(self_).state).can_go_back as i32 }
+}
+
+extern "C" fn go_back(_self: *mut cef_browser_t) {
+    record("Browser::go_back");
+}
+
+extern "C" fn can_go_forward(self_: *mut cef_browser_t) -> i32 {
+    unsafe { locked(&value(self_).state).can_go_forward as i32 }
+}
+
+extern "C" fn go_forward(_self: *mut cef_browser_t) {
+    record("Browser::go_forward");
+}
+
+extern "C" fn is_loading(self_: *mut cef_browser_t) -> i32 {
+    unsafe { locked(&value(self_).state).is_loading as i32 }
+}
+
+extern "C" fn reload(_self: *mut cef_browser_t) {
+    record("Browser::reload");
+}
+
+extern "C" fn reload_ignore_cache(_self: *mut cef_browser_t) {
+    record("Browser::reload_ignore_cache");
+}
+
+extern "C" fn stop_load(_self: *mut cef_browser_t) {
+    record("Browser::stop_load");
+}
+
+extern "C" fn get_browser_identifier(self_: *mut cef_browser_t) -> i32 {
+    unsafe { value(self_).id }
+}
+
+extern "C" fn is_same(self_: *mut cef_browser_t, that: *mut cef_browser_t) -> i32 {
+    unsafe {
+        let same = !that.is_null() && value(self_).id == value(that).id;
+        release(that);
+        same as i32
+    }
+}
+
+extern "C" fn is_popup(self_: *mut cef_browser_t) -> i32 {
+    unsafe { value(self_).popup as i32 }
+}
+
+extern "C" fn has_document(self_: *mut cef_browser_t) -> i32 {
+    unsafe { !locked(&frame_value(value(self_).frames[0].0).url).is_empty() as i32 }
+}
+
+extern "C" fn get_main_frame(self_: *mut cef_browser_t) -> *mut cef_frame_t {
+    unsafe { add_ref(value(self_).frames[0].0) }
+}
+
+extern "C" fn get_frame_byident(self_: *mut cef_browser_t, identifier: i64) -> *mut cef_frame_t {
+    unsafe { find_frame(self_, &|frame| frame.id == identifier) }
+}
+
+extern "C" fn get_frame(self_: *mut cef_browser_t, name: *const cef_string_t) -> *mut cef_frame_t {
+    let name = unsafe { CefStr::from_ptr(name) }.to_string();
+    unsafe { find_frame(self_, &|frame| frame.name == name) }
+}
+
+extern "C" fn get_frame_count(self_: *mut cef_browser_t) -> usize {
+    unsafe { value(self_).frames.len() + locked(&value(self_).subframes).len() }
+}
+
+extern "C" fn get_frame_identifiers(self_: *mut cef_browser_t, identifiers_count: *mut usize, identifiers: *mut i64) {
+    unsafe {
+        let value = value(self_);
+        let subframes = locked(&value.subframes);
+        let ids: Vec<_> = value.frames.iter().chain(subframes.iter())
+            .map(|frame| frame_value(frame.0).id)
+            .collect();
+        let count = ids.len().min(*identifiers_count);
+        ptr::copy_nonoverlapping(ids.as_ptr(), identifiers, count);
+        *identifiers_count = count;
+    }
+}
+
+extern "C" fn get_frame_names(self_: *mut cef_browser_t, names: cef_string_list_t) {
+    unsafe {
+        let value = value(self_);
+        let subframes = locked(&value.subframes);
+        for frame in value.frames.iter().chain(subframes.iter()) {
+            let name = CefString::new(&frame_value(frame.0).name);
+            cef_string_list_append(names, name.as_raw());
+        }
+    }
+}
+
+extern "C" fn send_process_message(_self: *mut cef_browser_t, target_process: cef_process_id_t,
+    message: *mut cef_process_message_t) -> i32
+{
+    unsafe {
+        let message_value = ProcessMessage::from_raw(message);
+        record(format!("Browser::send_process_message {:?} {} {:?}", ProcessId::from_raw(target_process),
+            message_value.name, message_value.arguments));
+        release(message);
+    }
+    1
+}
+
+extern "C" fn host_get_browser(self_: *mut cef_browser_host_t) -> *mut cef_browser_t {
+    host_browser(self_)
+}
+
+extern "C" fn close_browser(self_: *mut cef_browser_host_t, force_close: i32) {
+    let browser_id = unsafe { RefCounted::<_, Host>::value(self_) }.browser_id;
+    record(format!("BrowserHost::close_browser {}", force_close != 0));
+    post(move || unsafe { close(browser_id, force_close != 0) });
+}
+
+extern "C" fn try_close_browser(self_: *mut cef_browser_host_t) -> i32 {
+    let browser_id = unsafe { RefCounted::<_, Host>::value(self_) }.browser_id;
+    record("BrowserHost::try_close_browser");
+    post(move || unsafe { close(browser_id, false) });
+    0
+}
+
+extern "C" fn set_focus(_self: *mut cef_browser_host_t, focus: i32) {
+    record(format!("BrowserHost::set_focus {}", focus != 0));
+}
+
+extern "C" fn get_window_handle(_self: *mut cef_browser_host_t) -> cef_window_handle_t {
+    0
+}
+
+// The functions reading the state of the browser do nothing once it is closed.
+fn with_browser<T, F: FnOnce(&Browser) -> T>(host: *mut cef_browser_host_t, default: T, f: F) -> T {
+    let browser = host_browser(host);
+    if browser.is_null() {
+        return default;
+    }
+    unsafe {
+        let result = f(value(browser));
+        release(browser);
+        result
+    }
+}
+
+extern "C" fn has_view(self_: *mut cef_browser_host_t) -> i32 {
+    with_browser(self_, 0, |browser| browser.has_view as i32)
+}
+
+extern "C" fn get_client(self_: *mut cef_browser_host_t) -> *mut cef_client_t {
+    with_browser(self_, ptr::null_mut(), |browser| unsafe { add_ref(browser.client.0) })
+}
+
+extern "C" fn get_zoom_level(self_: *mut cef_browser_host_t) -> f64 {
+    with_browser(self_, 0.0, |browser| locked(&browser.state).zoom_level)
+}
+
+extern "C" fn set_zoom_level(self_: *mut cef_browser_host_t, zoom_level: f64) {
+    with_browser(self_, (), |browser| locked(&browser.state).zoom_level = zoom_level)
+}
+
+extern "C" fn start_download(_self: *mut cef_browser_host_t, url: *const cef_string_t) {
+    record(format!("BrowserHost::start_download {}", unsafe { CefStr::from_ptr(url) }));
+}
+
+extern "C" fn print(_self: *mut cef_browser_host_t) {
+    record("BrowserHost::print");
+}
+
+extern "C" fn find_text(_self: *mut cef_browser_host_t, identifier: i32, search_text: *const cef_string_t,
+    forward: i32, match_case: i32, find_next: i32)
+{
+    record(format!("BrowserHost::find {} {} {} {} {}", identifier, unsafe { CefStr::from_ptr(search_text) },
+        forward != 0, match_case != 0, find_next != 0));
+}
+
+extern "C" fn stop_finding(_self: *mut cef_browser_host_t, clear_selection: i32) {
+    record(format!("BrowserHost::stop_finding {}", clear_selection != 0));
+}
+
+extern "C" fn close_dev_tools(_self: *mut cef_browser_host_t) {
+    record("BrowserHost::close_dev_tools");
+}
+
+extern "C" fn has_dev_tools(_self: *mut cef_browser_host_t) -> i32 {
+    0
+}
+
+extern "C" fn is_window_rendering_disabled(self_: *mut cef_browser_host_t) -> i32 {
+    with_browser(self_, 0, |browser| browser.windowless as i32)
+}
+
+extern "C" fn was_resized(_self: *mut cef_browser_host_t) {
+    record("BrowserHost::was_resized");
+}
+
+extern "C" fn invalidate(_self: *mut cef_browser_host_t, type_: cef_paint_element_type_t) {
+    record(format!("BrowserHost::invalidate {:?}", PaintElementType::from_raw(type_)));
+}
+
+extern "C" fn was_hidden(_self: *mut cef_browser_host_t, hidden: i32) {
+    record(format!("BrowserHost::was_hidden {}", hidden != 0));
+}
+
+extern "C" fn notify_screen_info_changed(_self: *mut cef_browser_host_t) {
+    record("BrowserHost::notify_screen_info_changed");
+}
+
+extern "C" fn get_windowless_frame_rate(self_: *mut cef_browser_host_t) -> i32 {
+    with_browser(self_, 0, |browser| locked(&browser.state).frame_rate)
+}
+
+extern "C" fn set_windowless_frame_rate(self_: *mut cef_browser_host_t, frame_rate: i32) {
+    with_browser(self_, (), |browser| locked(&browser.state).frame_rate = frame_rate)
+}
+
+extern "C" fn frame_is_valid(self_: *mut cef_frame_t) -> i32 {
+    let browser = find(frame_value(self_).browser_id);
+    unsafe { release(browser) };
+    !browser.is_null() as i32
+}
+
+macro_rules! frame_commands {
+    ($($name:ident),*) => {
+        $(
+            extern "C" fn $name(_self: *mut cef_frame_t) {
+                record(concat!("Frame::", stringify!($name)));
+            }
+        )*
+    };
+}
+
+frame_commands!(undo, redo, cut, copy, paste, del, select_all, view_source);
+
+extern "C" fn load_url(self_: *mut cef_frame_t, url: *const cef_string_t) {
+    let url = unsafe { CefStr::from_ptr(url) }.to_string();
+    record(format!("Frame::load_url {}", url));
+    *locked(&frame_value(self_).url) = url;
+}
+
+extern "C" fn load_string(self_: *mut cef_frame_t, _string_val: *const cef_string_t, url: *const cef_string_t) {
+    let url = unsafe { CefStr::from_ptr(url) }.to_string();
+    record(format!("Frame::load_string {}", url));
+    *locked(&frame_value(self_).url) = url;
+}
+
+extern "C" fn execute_java_script(_self: *mut cef_frame_t, code: *const cef_string_t,
+    _script_url: *const cef_string_t, _start_line: i32)
+{
+    record(format!("Frame::execute_java_script {}", unsafe { CefStr::from_ptr(code) }));
+}
+
+extern "C" fn is_main(self_: *mut cef_frame_t) -> i32 {
+    frame_value(self_).parent.is_none() as i32
+}
+
+extern "C" fn is_focused(self_: *mut cef_frame_t) -> i32 {
+    is_main(self_)
+}
+
+extern "C" fn get_name(self_: *mut cef_frame_t) -> cef_string_userfree_t {
+    userfree(&frame_value(self_).name)
+}
+
+extern "C" fn get_identifier(self_: *mut cef_frame_t) -> i64 {
+    frame_value(self_).id
+}
+
+extern "C" fn get_parent(self_: *mut cef_frame_t) -> *mut cef_frame_t {
+    let frame = frame_value(self_);
+    let parent = match frame.parent {
+        Some(parent) => parent,
+        None => return ptr::null_mut(),
+    };
+    let browser = find(frame.browser_id);
+    if browser.is_null() {
+        return ptr::null_mut();
+    }
+    unsafe {
+        let parent = find_frame(browser, &|frame| frame.id == parent);
+        release(browser);
+        parent
+    }
+}
+
+extern "C" fn get_url(self_: *mut cef_frame_t) -> cef_string_userfree_t {
+    userfree(&locked(&frame_value(self_).url))
+}
+
+extern "C" fn frame_get_browser(self_: *mut cef_frame_t) -> *mut cef_browser_t {
+    find(frame_value(self_).browser_id)
+}
+
+extern "C" fn get_v8context(_self: *mut cef_frame_t) -> *mut cef_v8context_t {
+    new_v8context()
+}
+
+#[no_mangle]
+pub extern "C" fn cef_browser_host_create_browser(window_info: *const cef_window_info_t, client: *mut cef_client_t,
+    url: *const cef_string_t, settings: *const cef_browser_settings_t, request_context: *mut cef_request_context_t)
+    -> i32
+{
+    record("cef_browser_host_create_browser");
+    if !app::is_initialized() {
+        unsafe {
+            release(client);
+            release(request_context);
+        }
+        return 0;
+    }
+    let windowless = !window_info.is_null() && unsafe { (*window_info).windowless_rendering_enabled } != 0;
+    let client = Ptr(client);
+    let url = CefString::from(unsafe { CefStr::from_ptr(url) });
+    let settings_size = if settings.is_null() { 0 } else { unsafe { (*settings).size } };
+    unsafe { release(request_context) };
+    post(move || unsafe {
+        let mut settings: cef_browser_settings_t = mem::zeroed();
+        settings.size = settings_size;
+        release(create(client.0, url.as_raw(), &settings, windowless, false, false));
+    });
+    1
+}
+
+#[no_mangle]
+pub extern "C" fn cef_browser_host_create_browser_sync(window_info: *const cef_window_info_t,
+    client: *mut cef_client_t, url: *const cef_string_t, settings: *const cef_browser_settings_t,
+    request_context: *mut cef_request_context_t) -> *mut cef_browser_t
+{
+    record("cef_browser_host_create_browser_sync");
+    let windowless = !window_info.is_null() && unsafe { (*window_info).windowless_rendering_enabled } != 0;
+    unsafe {
+        release(request_context);
+        create(client, url, settings, windowless, false, false)
+    }
+}
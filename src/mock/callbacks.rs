@@ -0,0 +1,75 @@
+// The callbacks given to the handlers, which record how they are answered.
+
+use std::mem;
+
+use rc::RefCounted;
+use string::{CefStr, string_list};
+use sys::*;
+use super::record;
+
+pub(super) fn jsdialog_callback() -> *mut cef_jsdialog_callback_t {
+    extern "C" fn cont(_self: *mut cef_jsdialog_callback_t, success: i32, user_input: *const cef_string_t) {
+        let user_input = unsafe { CefStr::from_ptr(user_input) };
+        record(format!("JsDialogCallback::cont {} {}", success != 0, user_input).trim_end().to_string());
+    }
+
+    let mut raw: cef_jsdialog_callback_t = unsafe { mem::zeroed() };
+    raw.cont = Some(cont);
+    RefCounted::wrap(raw, ())
+}
+
+pub(super) fn before_download_callback() -> *mut cef_before_download_callback_t {
+    extern "C" fn cont(_self: *mut cef_before_download_callback_t, download_path: *const cef_string_t,
+        show_dialog: i32)
+    {
+        let download_path = unsafe { CefStr::from_ptr(download_path) };
+        record(format!("BeforeDownloadCallback::cont {} {}", show_dialog != 0, download_path).trim_end().to_string());
+    }
+
+    let mut raw: cef_before_download_callback_t = unsafe { mem::zeroed() };
+    raw.cont = Some(cont);
+    RefCounted::wrap(raw, ())
+}
+
+pub(super) fn download_item_callback(id: u32) -> *mut cef_download_item_callback_t {
+    fn id_of(callback: *mut cef_download_item_callback_t) -> u32 {
+        *unsafe { RefCounted::<_, u32>::value(callback) }
+    }
+
+    extern "C" fn cancel(self_: *mut cef_download_item_callback_t) {
+        record(format!("DownloadItemCallback::cancel {}", id_of(self_)));
+    }
+
+    extern "C" fn pause(self_: *mut cef_download_item_callback_t) {
+        record(format!("DownloadItemCallback::pause {}", id_of(self_)));
+    }
+
+    extern "C" fn resume(self_: *mut cef_download_item_callback_t) {
+        record(format!("DownloadItemCallback::resume {}", id_of(self_)));
+    }
+
+    let mut raw: cef_download_item_callback_t = unsafe { mem::zeroed() };
+    raw.cancel = Some(cancel);
+    raw.pause = Some(pause);
+    raw.resume = Some(resume);
+    RefCounted::wrap(raw, id)
+}
+
+pub(super) fn file_dialog_callback() -> *mut cef_file_dialog_callback_t {
+    extern "C" fn cont(_self: *mut cef_file_dialog_callback_t, selected_accept_filter: i32,
+        file_paths: cef_string_list_t)
+    {
+        let file_paths = unsafe { string_list(file_paths) };
+        record(format!("FileDialogCallback::cont {} {}", selected_accept_filter, file_paths.join(";")).trim_end()
+            .to_string());
+    }
+
+    extern "C" fn cancel(_self: *mut cef_file_dialog_callback_t) {
+        record("FileDialogCallback::cancel");
+    }
+
+    let mut raw: cef_file_dialog_callback_t = unsafe { mem::zeroed() };
+    raw.cont = Some(cont);
+    raw.cancel = Some(cancel);
+    RefCounted::wrap(raw, ())
+}
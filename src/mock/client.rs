@@ -0,0 +1,76 @@
+use std::sync::{Arc, Mutex};
+
+use client::Client;
+use context_menu::ContextMenuHandler;
+use dialog::DialogHandler;
+use display::DisplayHandler;
+use download::DownloadHandler;
+use drag::DragHandler;
+use find::FindHandler;
+use focus::FocusHandler;
+use jsdialog::JsDialogHandler;
+use keyboard::KeyboardHandler;
+use life_span::LifeSpanHandler;
+use load::LoadHandler;
+use render::RenderHandler;
+use super::locked;
+
+/// The events of the handlers of a test, in the order of the callbacks.
+#[derive(Default)]
+pub struct Recorder(Mutex<Vec<String>>);
+
+impl Recorder {
+    pub fn push<S: Into<String>>(&self, event: S) {
+        locked(&self.0).push(event.into());
+    }
+
+    pub fn events(&self) -> Vec<String> {
+        locked(&self.0).clone()
+    }
+}
+
+macro_rules! test_client {
+    ($($field:ident: $handler:ident => $getter:ident,)*) => {
+        /// A client with the handlers set by a test, like `TestClient::new().display(recorder.clone())`.
+        #[derive(Default)]
+        pub struct TestClient {
+            $($field: Option<Arc<dyn $handler>>,)*
+        }
+
+        impl TestClient {
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            $(
+                pub fn $field(mut self, handler: Arc<dyn $handler>) -> Self {
+                    self.$field = Some(handler);
+                    self
+                }
+            )*
+        }
+
+        impl Client for TestClient {
+            $(
+                fn $getter(&self) -> Option<Arc<dyn $handler>> {
+                    self.$field.clone()
+                }
+            )*
+        }
+    };
+}
+
+test_client! {
+    context_menu: ContextMenuHandler => context_menu_handler,
+    dialog: DialogHandler => dialog_handler,
+    display: DisplayHandler => display_handler,
+    download: DownloadHandler => download_handler,
+    drag: DragHandler => drag_handler,
+    find: FindHandler => find_handler,
+    focus: FocusHandler => focus_handler,
+    jsdialog: JsDialogHandler => jsdialog_handler,
+    keyboard: KeyboardHandler => keyboard_handler,
+    life_span: LifeSpanHandler => life_span_handler,
+    load: LoadHandler => load_handler,
+    render: RenderHandler => render_handler,
+}
@@ -0,0 +1,240 @@
+use std::ffi::CStr;
+use std::mem;
+use std::os::raw::c_char;
+use std::ptr;
+use std::sync::Mutex;
+
+use rc::RefCounted;
+use string::CefStr;
+use sys::*;
+use super::{locked, record};
+use super::strings::userfree;
+
+static GLOBAL_ARGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct CommandLine {
+    read_only: bool,
+    state: Mutex<State>,
+}
+
+#[derive(Clone, Default)]
+struct State {
+    program: String,
+    switches: Vec<(String, String)>,
+    arguments: Vec<String>,
+}
+
+impl State {
+    fn parse(&mut self, args: &[String]) {
+        let mut args = args.iter();
+        self.program = args.next().cloned().unwrap_or_default();
+        for arg in args {
+            if let Some(switch) = arg.strip_prefix("--") {
+                let mut parts = switch.splitn(2, '=');
+                let name = parts.next().unwrap_or_default().to_string();
+                let value = parts.next().unwrap_or_default().to_string();
+                self.switches.push((name, value));
+            }
+            else {
+                self.arguments.push(arg.clone());
+            }
+        }
+    }
+
+    fn command_line_string(&self) -> String {
+        let mut parts = vec![self.program.clone()];
+        for (name, value) in &self.switches {
+            if value.is_empty() {
+                parts.push(format!("--{}", name));
+            }
+            else {
+                parts.push(format!("--{}={}", name, value));
+            }
+        }
+        parts.extend(self.arguments.iter().cloned());
+        parts.join(" ")
+    }
+}
+
+/// Remember the arguments of cef_initialize, for the global command line.
+pub(super) unsafe fn set_global_args(args: *const cef_main_args_t) {
+    let mut global_args = locked(&GLOBAL_ARGS);
+    global_args.clear();
+    if args.is_null() {
+        return;
+    }
+    for index in 0..(*args).argc as isize {
+        let arg = *(*args).argv.offset(index);
+        global_args.push(CStr::from_ptr(arg).to_string_lossy().into_owned());
+    }
+}
+
+/// A writable copy of the global command line, as given to the App before it is parsed.
+pub(super) fn process_command_line() -> *mut cef_command_line_t {
+    let mut state = State::default();
+    state.parse(&locked(&GLOBAL_ARGS));
+    create(false, state)
+}
+
+/// Whether the arguments are the ones of a subprocess, which have a type switch.
+pub(super) unsafe fn is_subprocess(args: *const cef_main_args_t) -> bool {
+    if args.is_null() {
+        return false;
+    }
+    (1..(*args).argc as isize)
+        .map(|index| CStr::from_ptr(*(*args).argv.offset(index)))
+        .any(|arg| arg.to_bytes().starts_with(b"--type="))
+}
+
+fn create(read_only: bool, state: State) -> *mut cef_command_line_t {
+    let mut raw: cef_command_line_t = unsafe { mem::zeroed() };
+    raw.is_valid = Some(is_valid);
+    raw.is_read_only = Some(is_read_only);
+    raw.copy = Some(copy);
+    raw.init_from_argv = Some(init_from_argv);
+    raw.reset = Some(reset);
+    raw.get_command_line_string = Some(get_command_line_string);
+    raw.get_program = Some(get_program);
+    raw.set_program = Some(set_program);
+    raw.has_switches = Some(has_switches);
+    raw.has_switch = Some(has_switch);
+    raw.get_switch_value = Some(get_switch_value);
+    raw.append_switch = Some(append_switch);
+    raw.append_switch_with_value = Some(append_switch_with_value);
+    raw.has_arguments = Some(has_arguments);
+    raw.append_argument = Some(append_argument);
+    raw.prepend_wrapper = Some(prepend_wrapper);
+    RefCounted::wrap(raw, CommandLine {
+        read_only,
+        state: Mutex::new(state),
+    })
+}
+
+fn state<'a>(command_line: *mut cef_command_line_t) -> &'a Mutex<State> {
+    unsafe { &RefCounted::<_, CommandLine>::value(command_line).state }
+}
+
+fn writable<'a>(command_line: *mut cef_command_line_t) -> Option<&'a Mutex<State>> {
+    let value = unsafe { RefCounted::<_, CommandLine>::value(command_line) };
+    if value.read_only {
+        None
+    }
+    else {
+        Some(&value.state)
+    }
+}
+
+fn string(string: *const cef_string_t) -> String {
+    unsafe { CefStr::from_ptr(string) }.to_string()
+}
+
+#[no_mangle]
+pub extern "C" fn cef_command_line_create() -> *mut cef_command_line_t {
+    record("cef_command_line_create");
+    create(false, State::default())
+}
+
+#[no_mangle]
+pub extern "C" fn cef_command_line_get_global() -> *mut cef_command_line_t {
+    record("cef_command_line_get_global");
+    let mut state = State::default();
+    state.parse(&locked(&GLOBAL_ARGS));
+    create(true, state)
+}
+
+extern "C" fn is_valid(_self: *mut cef_command_line_t) -> i32 {
+    1
+}
+
+extern "C" fn is_read_only(self_: *mut cef_command_line_t) -> i32 {
+    unsafe { RefCounted::<_, CommandLine>::value(self_).read_only as i32 }
+}
+
+extern "C" fn copy(self_: *mut cef_command_line_t) -> *mut cef_command_line_t {
+    let state = locked(state(self_)).clone();
+    create(false, state)
+}
+
+extern "C" fn init_from_argv(self_: *mut cef_command_line_t, argc: i32, argv: *const *const c_char) {
+    if let Some(state) = writable(self_) {
+        let args: Vec<_> = (0..argc as isize)
+            .map(|index| unsafe { CStr::from_ptr(*argv.offset(index)) }.to_string_lossy().into_owned())
+            .collect();
+        let mut state = locked(state);
+        *state = State::default();
+        state.parse(&args);
+    }
+}
+
+extern "C" fn reset(self_: *mut cef_command_line_t) {
+    if let Some(state) = writable(self_) {
+        *locked(state) = State::default();
+    }
+}
+
+extern "C" fn get_command_line_string(self_: *mut cef_command_line_t) -> cef_string_userfree_t {
+    userfree(&locked(state(self_)).command_line_string())
+}
+
+extern "C" fn get_program(self_: *mut cef_command_line_t) -> cef_string_userfree_t {
+    userfree(&locked(state(self_)).program)
+}
+
+extern "C" fn set_program(self_: *mut cef_command_line_t, program: *const cef_string_t) {
+    if let Some(state) = writable(self_) {
+        locked(state).program = string(program);
+    }
+}
+
+extern "C" fn has_switches(self_: *mut cef_command_line_t) -> i32 {
+    !locked(state(self_)).switches.is_empty() as i32
+}
+
+extern "C" fn has_switch(self_: *mut cef_command_line_t, name: *const cef_string_t) -> i32 {
+    let name = string(name);
+    locked(state(self_)).switches.iter().any(|(switch, _)| *switch == name) as i32
+}
+
+extern "C" fn get_switch_value(self_: *mut cef_command_line_t, name: *const cef_string_t) -> cef_string_userfree_t {
+    let name = string(name);
+    let state = locked(state(self_));
+    let value = state.switches.iter()
+        .find(|&(switch, _)| *switch == name)
+        .map(|(_, value)| value.as_str())
+        .unwrap_or_default();
+    userfree(value)
+}
+
+extern "C" fn append_switch(self_: *mut cef_command_line_t, name: *const cef_string_t) {
+    append_switch_with_value(self_, name, ptr::null());
+}
+
+extern "C" fn append_switch_with_value(self_: *mut cef_command_line_t, name: *const cef_string_t,
+    value: *const cef_string_t)
+{
+    let name = string(name);
+    let value = string(value);
+    record(format!("CommandLine::append_switch {} {}", name, value).trim_end().to_string());
+    if let Some(state) = writable(self_) {
+        locked(state).switches.push((name, value));
+    }
+}
+
+extern "C" fn has_arguments(self_: *mut cef_command_line_t) -> i32 {
+    !locked(state(self_)).arguments.is_empty() as i32
+}
+
+extern "C" fn append_argument(self_: *mut cef_command_line_t, argument: *const cef_string_t) {
+    if let Some(state) = writable(self_) {
+        locked(state).arguments.push(string(argument));
+    }
+}
+
+extern "C" fn prepend_wrapper(self_: *mut cef_command_line_t, wrapper: *const cef_string_t) {
+    if let Some(state) = writable(self_) {
+        let mut state = locked(state);
+        let program = string(wrapper);
+        let previous = mem::replace(&mut state.program, program);
+        state.arguments.insert(0, previous);
+    }
+}
@@ -0,0 +1,380 @@
+use std::mem;
+use std::sync::Mutex;
+
+use context_menu::ContextMenuParams;
+use menu_model::MenuItemType;
+use rc::RefCounted;
+use string::{CefStr, CefString};
+use sys::*;
+use super::{Ptr, add_ref, locked, release};
+use super::strings::userfree;
+
+struct Item {
+    command_id: i32,
+    label: String,
+    item_type: MenuItemType,
+    group_id: i32,
+    visible: bool,
+    enabled: bool,
+    checked: bool,
+    sub_menu: Option<Ptr<cef_menu_model_t>>,
+}
+
+impl Drop for Item {
+    fn drop(&mut self) {
+        if let Some(sub_menu) = self.sub_menu {
+            unsafe { release(sub_menu.0) };
+        }
+    }
+}
+
+struct Menu {
+    is_sub_menu: bool,
+    items: Mutex<Vec<Item>>,
+}
+
+fn items<'a>(model: *mut cef_menu_model_t) -> &'a Mutex<Vec<Item>> {
+    unsafe { &RefCounted::<_, Menu>::value(model).items }
+}
+
+fn index_of(model: *mut cef_menu_model_t, command_id: i32) -> i32 {
+    locked(items(model)).iter()
+        .position(|item| item.command_id == command_id)
+        .map(|index| index as i32)
+        .unwrap_or(-1)
+}
+
+// Call `f` with the item at `index`, or return the default value when there is none.
+fn with_item_at<T: Default, F: FnOnce(&mut Item) -> T>(model: *mut cef_menu_model_t, index: i32, f: F) -> T {
+    let mut items = locked(items(model));
+    match items.get_mut(index as usize) {
+        Some(item) if index >= 0 => f(item),
+        _ => T::default(),
+    }
+}
+
+fn add(model: *mut cef_menu_model_t, command_id: i32, label: *const cef_string_t, item_type: MenuItemType,
+    group_id: i32) -> i32
+{
+    locked(items(model)).push(Item {
+        command_id,
+        label: unsafe { CefStr::from_ptr(label) }.to_string(),
+        item_type,
+        group_id,
+        visible: true,
+        enabled: true,
+        checked: false,
+        sub_menu: None,
+    });
+    1
+}
+
+fn new_menu(is_sub_menu: bool) -> *mut cef_menu_model_t {
+    let mut raw: cef_menu_model_t = unsafe { mem::zeroed() };
+    raw.is_sub_menu = Some(is_sub_menu_);
+    raw.clear = Some(clear);
+    raw.get_count = Some(get_count);
+    raw.add_separator = Some(add_separator);
+    raw.add_item = Some(add_item);
+    raw.add_check_item = Some(add_check_item);
+    raw.add_radio_item = Some(add_radio_item);
+    raw.add_sub_menu = Some(add_sub_menu);
+    raw.remove = Some(remove);
+    raw.remove_at = Some(remove_at);
+    raw.get_index_of = Some(get_index_of);
+    raw.get_command_id_at = Some(get_command_id_at);
+    raw.get_label = Some(get_label);
+    raw.get_label_at = Some(get_label_at);
+    raw.set_label = Some(set_label);
+    raw.get_type = Some(get_type);
+    raw.get_type_at = Some(get_type_at);
+    raw.get_sub_menu_at = Some(get_sub_menu_at);
+    raw.is_visible = Some(is_visible);
+    raw.set_visible = Some(set_visible);
+    raw.is_enabled = Some(is_enabled);
+    raw.set_enabled = Some(set_enabled);
+    raw.is_checked = Some(is_checked);
+    raw.set_checked = Some(set_checked);
+    RefCounted::wrap(raw, Menu {
+        is_sub_menu,
+        items: Mutex::new(vec![]),
+    })
+}
+
+extern "C" fn is_sub_menu_(self_: *mut cef_menu_model_t) -> i32 {
+    unsafe { RefCounted::<_, Menu>::value(self_) }.is_sub_menu as i32
+}
+
+extern "C" fn clear(self_: *mut cef_menu_model_t) -> i32 {
+    let removed = mem::take(&mut *locked(items(self_)));
+    drop(removed);
+    1
+}
+
+extern "C" fn get_count(self_: *mut cef_menu_model_t) -> i32 {
+    locked(items(self_)).len() as i32
+}
+
+extern "C" fn add_separator(self_: *mut cef_menu_model_t) -> i32 {
+    add(self_, -1, ::std::ptr::null(), MenuItemType::Separator, -1)
+}
+
+extern "C" fn add_item(self_: *mut cef_menu_model_t, command_id: i32, label: *const cef_string_t) -> i32 {
+    add(self_, command_id, label, MenuItemType::Command, -1)
+}
+
+extern "C" fn add_check_item(self_: *mut cef_menu_model_t, command_id: i32, label: *const cef_string_t) -> i32 {
+    add(self_, command_id, label, MenuItemType::Check, -1)
+}
+
+extern "C" fn add_radio_item(self_: *mut cef_menu_model_t, command_id: i32, label: *const cef_string_t,
+    group_id: i32) -> i32
+{
+    add(self_, command_id, label, MenuItemType::Radio, group_id)
+}
+
+extern "C" fn add_sub_menu(self_: *mut cef_menu_model_t, command_id: i32, label: *const cef_string_t)
+    -> *mut cef_menu_model_t
+{
+    add(self_, command_id, label, MenuItemType::SubMenu, -1);
+    let sub_menu = new_menu(true);
+    if let Some(item) = locked(items(self_)).last_mut() {
+        item.sub_menu = Some(Ptr(sub_menu));
+    }
+    unsafe { add_ref(sub_menu) }
+}
+
+extern "C" fn remove(self_: *mut cef_menu_model_t, command_id: i32) -> i32 {
+    remove_at(self_, index_of(self_, command_id))
+}
+
+extern "C" fn remove_at(self_: *mut cef_menu_model_t, index: i32) -> i32 {
+    let mut items = locked(items(self_));
+    if index < 0 || index as usize >= items.len() {
+        return 0;
+    }
+    let removed = items.remove(index as usize);
+    drop(items);
+    drop(removed);
+    1
+}
+
+extern "C" fn get_index_of(self_: *mut cef_menu_model_t, command_id: i32) -> i32 {
+    index_of(self_, command_id)
+}
+
+extern "C" fn get_command_id_at(self_: *mut cef_menu_model_t, index: i32) -> i32 {
+    with_item_at(self_, index, |item| Some(item.command_id)).unwrap_or(-1)
+}
+
+extern "C" fn get_label(self_: *mut cef_menu_model_t, command_id: i32) -> cef_string_userfree_t {
+    get_label_at(self_, index_of(self_, command_id))
+}
+
+extern "C" fn get_label_at(self_: *mut cef_menu_model_t, index: i32) -> cef_string_userfree_t {
+    userfree(&with_item_at(self_, index, |item| item.label.clone()))
+}
+
+extern "C" fn set_label(self_: *mut cef_menu_model_t, command_id: i32, label: *const cef_string_t) -> i32 {
+    let label = unsafe { CefStr::from_ptr(label) }.to_string();
+    with_item_at(self_, index_of(self_, command_id), |item| {
+        item.label = label;
+        1
+    })
+}
+
+extern "C" fn get_type(self_: *mut cef_menu_model_t, command_id: i32) -> cef_menu_item_type_t {
+    get_type_at(self_, index_of(self_, command_id))
+}
+
+extern "C" fn get_type_at(self_: *mut cef_menu_model_t, index: i32) -> cef_menu_item_type_t {
+    with_item_at(self_, index, |item| Some(item.item_type))
+        .unwrap_or(MenuItemType::None)
+        .to_raw()
+}
+
+extern "C" fn get_sub_menu_at(self_: *mut cef_menu_model_t, index: i32) -> *mut cef_menu_model_t {
+    with_item_at(self_, index, |item| item.sub_menu.map(|sub_menu| unsafe { add_ref(sub_menu.0) }))
+        .unwrap_or(::std::ptr::null_mut())
+}
+
+extern "C" fn is_visible(self_: *mut cef_menu_model_t, command_id: i32) -> i32 {
+    with_item_at(self_, index_of(self_, command_id), |item| item.visible as i32)
+}
+
+extern "C" fn set_visible(self_: *mut cef_menu_model_t, command_id: i32, visible: i32) -> i32 {
+    with_item_at(self_, index_of(self_, command_id), |item| {
+        item.visible = visible != 0;
+        1
+    })
+}
+
+extern "C" fn is_enabled(self_: *mut cef_menu_model_t, command_id: i32) -> i32 {
+    with_item_at(self_, index_of(self_, command_id), |item| item.enabled as i32)
+}
+
+extern "C" fn set_enabled(self_: *mut cef_menu_model_t, command_id: i32, enabled: i32) -> i32 {
+    with_item_at(self_, index_of(self_, command_id), |item| {
+        item.enabled = enabled != 0;
+        1
+    })
+}
+
+extern "C" fn is_checked(self_: *mut cef_menu_model_t, command_id: i32) -> i32 {
+    with_item_at(self_, index_of(self_, command_id), |item| item.checked as i32)
+}
+
+extern "C" fn set_checked(self_: *mut cef_menu_model_t, command_id: i32, checked: i32) -> i32 {
+    let mut items = locked(items(self_));
+    let group_id = match items.iter().find(|item| item.command_id == command_id) {
+        Some(item) if item.item_type == MenuItemType::Check || item.item_type == MenuItemType::Radio =>
+            item.group_id,
+        _ => return 0,
+    };
+    for item in items.iter_mut() {
+        if item.command_id == command_id {
+            item.checked = checked != 0;
+        }
+        else if checked != 0 && item.item_type == MenuItemType::Radio && item.group_id == group_id {
+            item.checked = false;
+        }
+    }
+    1
+}
+
+pub(super) fn new_params(params: &ContextMenuParams) -> *mut cef_context_menu_params_t {
+    fn value<'a>(params: *mut cef_context_menu_params_t) -> &'a ContextMenuParams {
+        unsafe { RefCounted::<_, ContextMenuParams>::value(params) }
+    }
+
+    extern "C" fn get_xcoord(self_: *mut cef_context_menu_params_t) -> i32 {
+        value(self_).x
+    }
+
+    extern "C" fn get_ycoord(self_: *mut cef_context_menu_params_t) -> i32 {
+        value(self_).y
+    }
+
+    extern "C" fn get_type_flags(self_: *mut cef_context_menu_params_t) -> cef_context_menu_type_flags_t {
+        value(self_).type_flags.bits()
+    }
+
+    extern "C" fn get_link_url(self_: *mut cef_context_menu_params_t) -> cef_string_userfree_t {
+        userfree(&value(self_).link_url)
+    }
+
+    extern "C" fn get_unfiltered_link_url(self_: *mut cef_context_menu_params_t) -> cef_string_userfree_t {
+        userfree(&value(self_).unfiltered_link_url)
+    }
+
+    extern "C" fn get_source_url(self_: *mut cef_context_menu_params_t) -> cef_string_userfree_t {
+        userfree(&value(self_).source_url)
+    }
+
+    extern "C" fn has_image_contents(self_: *mut cef_context_menu_params_t) -> i32 {
+        value(self_).has_image_contents as i32
+    }
+
+    extern "C" fn get_title_text(self_: *mut cef_context_menu_params_t) -> cef_string_userfree_t {
+        userfree(&value(self_).title_text)
+    }
+
+    extern "C" fn get_page_url(self_: *mut cef_context_menu_params_t) -> cef_string_userfree_t {
+        userfree(&value(self_).page_url)
+    }
+
+    extern "C" fn get_frame_url(self_: *mut cef_context_menu_params_t) -> cef_string_userfree_t {
+        userfree(&value(self_).frame_url)
+    }
+
+    extern "C" fn get_media_type(self_: *mut cef_context_menu_params_t) -> cef_context_menu_media_type_t {
+        value(self_).media_type.to_raw()
+    }
+
+    extern "C" fn get_selection_text(self_: *mut cef_context_menu_params_t) -> cef_string_userfree_t {
+        userfree(&value(self_).selection_text)
+    }
+
+    extern "C" fn get_misspelled_word(self_: *mut cef_context_menu_params_t) -> cef_string_userfree_t {
+        userfree(&value(self_).misspelled_word)
+    }
+
+    extern "C" fn get_dictionary_suggestions(self_: *mut cef_context_menu_params_t,
+        suggestions: cef_string_list_t) -> i32
+    {
+        for suggestion in &value(self_).dictionary_suggestions {
+            let suggestion = CefString::new(suggestion);
+            unsafe { cef_string_list_append(suggestions, suggestion.as_raw()) };
+        }
+        !value(self_).dictionary_suggestions.is_empty() as i32
+    }
+
+    extern "C" fn is_editable(self_: *mut cef_context_menu_params_t) -> i32 {
+        value(self_).is_editable as i32
+    }
+
+    extern "C" fn get_edit_state_flags(self_: *mut cef_context_menu_params_t)
+        -> cef_context_menu_edit_state_flags_t
+    {
+        value(self_).edit_state_flags.bits()
+    }
+
+    let mut raw: cef_context_menu_params_t = unsafe { mem::zeroed() };
+    raw.get_xcoord = Some(get_xcoord);
+    raw.get_ycoord = Some(get_ycoord);
+    raw.get_type_flags = Some(get_type_flags);
+    raw.get_link_url = Some(get_link_url);
+    raw.get_unfiltered_link_url = Some(get_unfiltered_link_url);
+    raw.get_source_url = Some(get_source_url);
+    raw.has_image_contents = Some(has_image_contents);
+    raw.get_title_text = Some(get_title_text);
+    raw.get_page_url = Some(get_page_url);
+    raw.get_frame_url = Some(get_frame_url);
+    raw.get_media_type = Some(get_media_type);
+    raw.get_selection_text = Some(get_selection_text);
+    raw.get_misspelled_word = Some(get_misspelled_word);
+    raw.get_dictionary_suggestions = Some(get_dictionary_suggestions);
+    raw.is_editable = Some(is_editable);
+    raw.get_edit_state_flags = Some(get_edit_state_flags);
+    RefCounted::wrap(raw, params.clone())
+}
+
+// Describe the items of a menu, one line per item, with the items of the submenus indented.
+fn describe_items(model: *mut cef_menu_model_t, indent: &str, lines: &mut Vec<String>) {
+    let items = locked(items(model));
+    for item in items.iter().filter(|item| item.visible) {
+        let check = if item.checked { "x" } else { " " };
+        let line = match item.item_type {
+            MenuItemType::Separator => "-".to_string(),
+            MenuItemType::Check => format!("{} [{}] {}", item.command_id, check, item.label),
+            MenuItemType::Radio => format!("{} ({}) {}", item.command_id, check, item.label),
+            MenuItemType::SubMenu => format!("{} {} >", item.command_id, item.label),
+            _ => format!("{} {}", item.command_id, item.label),
+        };
+        lines.push(format!("{}{}{}", indent, line, if item.enabled { "" } else { " (disabled)" }));
+        if let Some(sub_menu) = item.sub_menu {
+            describe_items(sub_menu.0, &format!("{}  ", indent), lines);
+        }
+    }
+}
+
+pub(super) fn describe(model: *mut cef_menu_model_t) -> Vec<String> {
+    let mut lines = vec![];
+    describe_items(model, "", &mut lines);
+    lines
+}
+
+/// The menu of a page before the handler changes it: 100 Back, 101 Forward, a separator and 132 View source.
+pub(super) fn default_menu() -> *mut cef_menu_model_t {
+    let model = new_menu(false);
+    for &(command_id, label) in &[(100, "Back"), (101, "Forward"), (-1, ""), (132, "View source")] {
+        if command_id == -1 {
+            add_separator(model);
+        }
+        else {
+            let label = CefString::new(label);
+            add_item(model, command_id, label.as_raw());
+        }
+    }
+    model
+}
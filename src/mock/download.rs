@@ -0,0 +1,91 @@
+use std::mem;
+
+use download::{DownloadItem, DownloadState};
+use rc::RefCounted;
+use sys::*;
+use super::strings::userfree;
+
+pub(super) fn new_download_item(item: &DownloadItem) -> *mut cef_download_item_t {
+    fn value<'a>(item: *mut cef_download_item_t) -> &'a DownloadItem {
+        unsafe { RefCounted::<_, DownloadItem>::value(item) }
+    }
+
+    extern "C" fn is_valid(_self: *mut cef_download_item_t) -> i32 {
+        1
+    }
+
+    extern "C" fn is_in_progress(self_: *mut cef_download_item_t) -> i32 {
+        (value(self_).state == DownloadState::InProgress) as i32
+    }
+
+    extern "C" fn is_complete(self_: *mut cef_download_item_t) -> i32 {
+        (value(self_).state == DownloadState::Complete) as i32
+    }
+
+    extern "C" fn is_canceled(self_: *mut cef_download_item_t) -> i32 {
+        (value(self_).state == DownloadState::Canceled) as i32
+    }
+
+    extern "C" fn get_current_speed(self_: *mut cef_download_item_t) -> i64 {
+        value(self_).current_speed as i64
+    }
+
+    extern "C" fn get_percent_complete(self_: *mut cef_download_item_t) -> i32 {
+        value(self_).percent_complete.map(i32::from).unwrap_or(-1)
+    }
+
+    extern "C" fn get_total_bytes(self_: *mut cef_download_item_t) -> i64 {
+        value(self_).total_bytes.map(|total_bytes| total_bytes as i64).unwrap_or(-1)
+    }
+
+    extern "C" fn get_received_bytes(self_: *mut cef_download_item_t) -> i64 {
+        value(self_).received_bytes as i64
+    }
+
+    extern "C" fn get_full_path(self_: *mut cef_download_item_t) -> cef_string_userfree_t {
+        let full_path = value(self_).full_path.as_ref().map(|path| path.to_string_lossy().into_owned());
+        userfree(&full_path.unwrap_or_default())
+    }
+
+    extern "C" fn get_id(self_: *mut cef_download_item_t) -> u32 {
+        value(self_).id
+    }
+
+    extern "C" fn get_url(self_: *mut cef_download_item_t) -> cef_string_userfree_t {
+        userfree(&value(self_).url)
+    }
+
+    extern "C" fn get_original_url(self_: *mut cef_download_item_t) -> cef_string_userfree_t {
+        userfree(&value(self_).original_url)
+    }
+
+    extern "C" fn get_suggested_file_name(self_: *mut cef_download_item_t) -> cef_string_userfree_t {
+        userfree(&value(self_).suggested_file_name)
+    }
+
+    extern "C" fn get_content_disposition(self_: *mut cef_download_item_t) -> cef_string_userfree_t {
+        userfree(&value(self_).content_disposition)
+    }
+
+    extern "C" fn get_mime_type(self_: *mut cef_download_item_t) -> cef_string_userfree_t {
+        userfree(&value(self_).mime_type)
+    }
+
+    let mut raw: cef_download_item_t = unsafe { mem::zeroed() };
+    raw.is_valid = Some(is_valid);
+    raw.is_in_progress = Some(is_in_progress);
+    raw.is_complete = Some(is_complete);
+    raw.is_canceled = Some(is_canceled);
+    raw.get_current_speed = Some(get_current_speed);
+    raw.get_percent_complete = Some(get_percent_complete);
+    raw.get_total_bytes = Some(get_total_bytes);
+    raw.get_received_bytes = Some(get_received_bytes);
+    raw.get_full_path = Some(get_full_path);
+    raw.get_id = Some(get_id);
+    raw.get_url = Some(get_url);
+    raw.get_original_url = Some(get_original_url);
+    raw.get_suggested_file_name = Some(get_suggested_file_name);
+    raw.get_content_disposition = Some(get_content_disposition);
+    raw.get_mime_type = Some(get_mime_type);
+    RefCounted::wrap(raw, item.clone())
+}
@@ -0,0 +1,96 @@
+use std::mem;
+
+use rc::RefCounted;
+use string::CefString;
+use sys::*;
+use super::strings::userfree;
+
+/// What is dragged by `drag_enter`, which is a link, a fragment or files when the matching fields are not
+/// empty.
+#[derive(Clone, Debug, Default)]
+pub struct DragContents {
+    pub link_url: String,
+    pub link_title: String,
+    pub fragment_text: String,
+    pub fragment_html: String,
+    pub fragment_base_url: String,
+    pub file_names: Vec<String>,
+}
+
+pub(super) fn new_drag_data(contents: &DragContents) -> *mut cef_drag_data_t {
+    fn value<'a>(drag_data: *mut cef_drag_data_t) -> &'a DragContents {
+        unsafe { RefCounted::<_, DragContents>::value(drag_data) }
+    }
+
+    extern "C" fn is_read_only(_self: *mut cef_drag_data_t) -> i32 {
+        1
+    }
+
+    extern "C" fn is_link(self_: *mut cef_drag_data_t) -> i32 {
+        !value(self_).link_url.is_empty() as i32
+    }
+
+    extern "C" fn is_fragment(self_: *mut cef_drag_data_t) -> i32 {
+        let contents = value(self_);
+        (!contents.fragment_text.is_empty() || !contents.fragment_html.is_empty()) as i32
+    }
+
+    extern "C" fn is_file(self_: *mut cef_drag_data_t) -> i32 {
+        !value(self_).file_names.is_empty() as i32
+    }
+
+    extern "C" fn get_link_url(self_: *mut cef_drag_data_t) -> cef_string_userfree_t {
+        userfree(&value(self_).link_url)
+    }
+
+    extern "C" fn get_link_title(self_: *mut cef_drag_data_t) -> cef_string_userfree_t {
+        userfree(&value(self_).link_title)
+    }
+
+    extern "C" fn get_link_metadata(_self: *mut cef_drag_data_t) -> cef_string_userfree_t {
+        userfree("")
+    }
+
+    extern "C" fn get_fragment_text(self_: *mut cef_drag_data_t) -> cef_string_userfree_t {
+        userfree(&value(self_).fragment_text)
+    }
+
+    extern "C" fn get_fragment_html(self_: *mut cef_drag_data_t) -> cef_string_userfree_t {
+        userfree(&value(self_).fragment_html)
+    }
+
+    extern "C" fn get_fragment_base_url(self_: *mut cef_drag_data_t) -> cef_string_userfree_t {
+        userfree(&value(self_).fragment_base_url)
+    }
+
+    extern "C" fn get_file_name(_self: *mut cef_drag_data_t) -> cef_string_userfree_t {
+        userfree("")
+    }
+
+    extern "C" fn get_file_names(self_: *mut cef_drag_data_t, names: cef_string_list_t) -> i32 {
+        for name in &value(self_).file_names {
+            unsafe { cef_string_list_append(names, CefString::new(name).as_raw()) };
+        }
+        !value(self_).file_names.is_empty() as i32
+    }
+
+    extern "C" fn has_image(_self: *mut cef_drag_data_t) -> i32 {
+        0
+    }
+
+    let mut raw: cef_drag_data_t = unsafe { mem::zeroed() };
+    raw.is_read_only = Some(is_read_only);
+    raw.is_link = Some(is_link);
+    raw.is_fragment = Some(is_fragment);
+    raw.is_file = Some(is_file);
+    raw.get_link_url = Some(get_link_url);
+    raw.get_link_title = Some(get_link_title);
+    raw.get_link_metadata = Some(get_link_metadata);
+    raw.get_fragment_text = Some(get_fragment_text);
+    raw.get_fragment_html = Some(get_fragment_html);
+    raw.get_fragment_base_url = Some(get_fragment_base_url);
+    raw.get_file_name = Some(get_file_name);
+    raw.get_file_names = Some(get_file_names);
+    raw.has_image = Some(has_image);
+    RefCounted::wrap(raw, contents.clone())
+}
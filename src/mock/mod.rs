@@ -0,0 +1,235 @@
+/*
+ * A fake libcef, replacing libcef in the unit tests, to test the bindings without a CEF binary distribution.
+ *
+ * It exports the C functions declared in the sys module, records the calls made to CEF, lets the tests drive
+ * the handler callbacks with the functions of this module and counts the objects implemented by the
+ * RefCounted wrapper to check that every reference is released.
+ *
+ * The state is global, like in libcef, so the tests must hold the guard returned by lock().
+ */
+
+mod app;
+mod browser;
+mod callbacks;
+mod client;
+mod command_line;
+mod context_menu;
+mod download;
+mod drag;
+mod strings;
+mod task;
+mod v8;
+mod values;
+mod views;
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::ptr;
+use std::sync::{Mutex, MutexGuard};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+use app::App;
+use browser::Browser;
+use browser_settings::BrowserSettings;
+use client::{Client, wrap_client};
+use context::{self, Cef, MainArgs};
+use rc::{CefRc, CefRefCounted, add_base_ref, release_base};
+use settings::Settings;
+use string::CefString;
+use sys::{cef_base_ref_counted_t, cef_browser_host_create_browser_sync};
+use window_info::WindowInfo;
+
+pub use self::browser::{
+    add_subframe,
+    address_change,
+    before_download,
+    before_unload_dialog,
+    browser_ids,
+    choose_context_menu_item,
+    close_window,
+    console_message,
+    cursor_change,
+    dismiss_context_menu,
+    download_updated,
+    drag_enter,
+    draggable_regions_changed,
+    favicon_url_change,
+    file_dialog,
+    find_result,
+    give_focus,
+    jsdialog,
+    key_event,
+    load_end,
+    load_error,
+    load_start,
+    loading_progress_change,
+    loading_state_change,
+    main_frame_id,
+    paint,
+    popup,
+    popup_show,
+    popup_size,
+    receive_process_message,
+    render_process_message,
+    reset_dialog_state,
+    screen_info,
+    show_context_menu,
+    take_focus,
+    title_change,
+    view_rect,
+};
+pub use self::client::{Recorder, TestClient};
+pub use self::drag::DragContents;
+pub use self::task::advance_time;
+pub use self::v8::set_script_result;
+pub use self::views::window_count;
+
+type Task = Box<dyn FnOnce() + Send>;
+
+static CALLS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static LIVE_OBJECTS: AtomicUsize = AtomicUsize::new(0);
+static TASKS: Mutex<VecDeque<Task>> = Mutex::new(VecDeque::new());
+static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+/// A pointer stored in the global state of the mock, which is only used on the thread running the test.
+struct Ptr<T>(*mut T);
+
+unsafe impl<T> Send for Ptr<T> {}
+
+unsafe impl<T> Sync for Ptr<T> {}
+
+impl<T> Clone for Ptr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Ptr<T> {}
+
+/// Serialize the tests using the mock, clear the calls and the pending tasks of the previous test and allow CEF to
+/// be started again.
+pub fn lock() -> MutexGuard<'static, ()> {
+    let guard = TEST_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+    context::reset_state();
+    clear_calls();
+    locked(&TASKS).clear();
+    task::clear();
+    v8::set_script_result(None);
+    guard
+}
+
+struct NoApp;
+
+impl App for NoApp {}
+
+/// Start CEF in the browser process, with the default settings.
+pub fn start() -> Cef {
+    let args = MainArgs::new(vec!["test"]).expect("arguments");
+    Cef::start(args, &Settings::default(), NoApp).expect("start CEF")
+}
+
+/// Run a test with CEF started, then check that every reference is released once CEF is shut down.
+pub fn run<F: FnOnce()>(test: F) {
+    let _guard = lock();
+    let cef = start();
+    test();
+    drop(cef);
+    assert_eq!(live_objects(), 0, "live objects");
+}
+
+/// Create a browser showing https://example.com with `client`.
+pub fn create_browser<C: Client + 'static>(client: C) -> CefRc<Browser> {
+    create(None, client)
+}
+
+/// Create a browser rendering https://example.com with the `RenderHandler` of `client`.
+pub fn create_windowless_browser<C: Client + 'static>(client: C) -> CefRc<Browser> {
+    create(Some(WindowInfo::windowless(0)), client)
+}
+
+fn create<C: Client + 'static>(window_info: Option<WindowInfo>, client: C) -> CefRc<Browser> {
+    let window_info = window_info.map(|window_info| window_info.to_raw());
+    let window_info = window_info.as_ref().map_or(ptr::null(), |window_info| window_info as *const _);
+    let url = CefString::new("https://example.com");
+    unsafe {
+        let browser = cef_browser_host_create_browser_sync(window_info, wrap_client(client), url.as_raw(),
+            BrowserSettings::default().as_raw(), ptr::null_mut());
+        CefRc::from_raw(browser).expect("browser")
+    }
+}
+
+/// Poll a future once, like an executor woken by the tasks of the mock would.
+pub fn poll<F: Future + Unpin>(future: &mut F) -> Poll<F::Output> {
+    fn clone(_data: *const ()) -> RawWaker {
+        RawWaker::new(ptr::null(), &VTABLE)
+    }
+
+    fn noop(_data: *const ()) {
+    }
+
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    let waker = unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) };
+    Pin::new(future).poll(&mut Context::from_waker(&waker))
+}
+
+/// The calls made to CEF, like `"cef_initialize"` or `"Frame::load_url https://example.com"`.
+pub fn calls() -> Vec<String> {
+    locked(&CALLS).clone()
+}
+
+pub fn clear_calls() {
+    locked(&CALLS).clear();
+}
+
+/// The number of objects created by `RefCounted::wrap` that are still alive: it is 0 once every reference is
+/// released.
+pub fn live_objects() -> usize {
+    LIVE_OBJECTS.load(Ordering::SeqCst)
+}
+
+/// Run the tasks posted to the UI thread, like the loop of `cef_run_message_loop` would.
+pub fn run_pending_tasks() {
+    loop {
+        let task = locked(&TASKS).pop_front();
+        match task {
+            Some(task) => task(),
+            None => break,
+        }
+    }
+}
+
+pub(crate) fn object_created() {
+    LIVE_OBJECTS.fetch_add(1, Ordering::SeqCst);
+}
+
+pub(crate) fn object_destroyed() {
+    LIVE_OBJECTS.fetch_sub(1, Ordering::SeqCst);
+}
+
+fn record<S: Into<String>>(call: S) {
+    locked(&CALLS).push(call.into());
+}
+
+fn post<F: FnOnce() + Send + 'static>(task: F) {
+    locked(&TASKS).push_back(Box::new(task));
+}
+
+// A test failing while holding a lock must not make the next tests fail.
+fn locked<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+unsafe fn add_ref<T: CefRefCounted>(object: *mut T) -> *mut T {
+    if !object.is_null() {
+        add_base_ref(object as *mut cef_base_ref_counted_t);
+    }
+    object
+}
+
+unsafe fn release<T: CefRefCounted>(object: *mut T) {
+    if !object.is_null() {
+        release_base(object as *mut cef_base_ref_counted_t);
+    }
+}
@@ -0,0 +1,275 @@
+use std::alloc::{Layout, alloc, dealloc, handle_alloc_error};
+use std::mem;
+use std::os::raw::{c_char, c_void};
+use std::ptr;
+use std::slice;
+
+use sys::*;
+
+const HEADER_SIZE: usize = mem::size_of::<usize>();
+
+fn layout<T>(length: usize) -> Layout {
+    Layout::from_size_align(HEADER_SIZE + length * mem::size_of::<T>(), mem::align_of::<usize>())
+        .expect("mock string layout")
+}
+
+unsafe fn alloc_chars<T: Copy>(src: *const T, length: usize) -> *mut T {
+    let layout = layout::<T>(length);
+    let block = alloc(layout);
+    if block.is_null() {
+        handle_alloc_error(layout);
+    }
+    *(block as *mut usize) = length;
+    let str = block.add(HEADER_SIZE) as *mut T;
+    if length > 0 {
+        ptr::copy_nonoverlapping(src, str, length);
+    }
+    str
+}
+
+unsafe fn free_chars<T>(str: *mut T) {
+    let block = (str as *mut u8).sub(HEADER_SIZE);
+    let length = *(block as *const usize);
+    dealloc(block, layout::<T>(length));
+}
+
+unsafe fn chars<'a, T>(src: *const T, length: usize) -> &'a [T] {
+    if src.is_null() {
+        &[]
+    }
+    else {
+        slice::from_raw_parts(src, length)
+    }
+}
+
+macro_rules! string_functions {
+    ($string:ident, $char:ty, $set:ident, $clear:ident, $userfree_alloc:ident, $userfree_free:ident, $dtor:ident) => {
+        extern "C" fn $dtor(str: *mut $char) {
+            unsafe { free_chars(str) }
+        }
+
+        #[no_mangle]
+        pub extern "C" fn $set(src: *const $char, src_len: usize, output: *mut $string, copy: i32) -> i32 {
+            unsafe {
+                $clear(output);
+                if copy != 0 {
+                    (*output).str = alloc_chars(src, src_len);
+                    (*output).dtor = Some($dtor);
+                }
+                else {
+                    (*output).str = src as *mut $char;
+                    (*output).dtor = None;
+                }
+                (*output).length = src_len;
+            }
+            1
+        }
+
+        #[no_mangle]
+        pub extern "C" fn $clear(str: *mut $string) {
+            unsafe {
+                if let Some(dtor) = (*str).dtor {
+                    if !(*str).str.is_null() {
+                        dtor((*str).str);
+                    }
+                }
+                (*str).str = ptr::null_mut();
+                (*str).length = 0;
+                (*str).dtor = None;
+            }
+        }
+
+        #[no_mangle]
+        pub extern "C" fn $userfree_alloc() -> *mut $string {
+            Box::into_raw(Box::new($string {
+                str: ptr::null_mut(),
+                length: 0,
+                dtor: None,
+            }))
+        }
+
+        #[no_mangle]
+        pub extern "C" fn $userfree_free(str: *mut $string) {
+            if !str.is_null() {
+                $clear(str);
+                unsafe { drop(Box::from_raw(str)) };
+            }
+        }
+    };
+}
+
+string_functions!(cef_string_wide_t, i32, cef_string_wide_set, cef_string_wide_clear, cef_string_userfree_wide_alloc,
+    cef_string_userfree_wide_free, wide_dtor);
+string_functions!(cef_string_utf8_t, c_char, cef_string_utf8_set, cef_string_utf8_clear,
+    cef_string_userfree_utf8_alloc, cef_string_userfree_utf8_free, utf8_dtor);
+string_functions!(cef_string_utf16_t, char16, cef_string_utf16_set, cef_string_utf16_clear,
+    cef_string_userfree_utf16_alloc, cef_string_userfree_utf16_free, utf16_dtor);
+
+#[no_mangle]
+pub extern "C" fn cef_string_utf8_to_utf16(src: *const c_char, src_len: usize, output: *mut cef_string_utf16_t)
+    -> i32
+{
+    let bytes = unsafe { chars(src as *const u8, src_len) };
+    let utf16: Vec<char16> = String::from_utf8_lossy(bytes).encode_utf16().collect();
+    cef_string_utf16_set(utf16.as_ptr(), utf16.len(), output, 1)
+}
+
+#[no_mangle]
+pub extern "C" fn cef_string_utf16_to_utf8(src: *const char16, src_len: usize, output: *mut cef_string_utf8_t)
+    -> i32
+{
+    let utf8 = String::from_utf16_lossy(unsafe { chars(src, src_len) });
+    cef_string_utf8_set(utf8.as_ptr() as *const c_char, utf8.len(), output, 1)
+}
+
+/// Copy a string into a new userfree string, like the strings returned by the CEF methods.
+pub(super) fn userfree(string: &str) -> cef_string_userfree_t {
+    let utf16: Vec<char16> = string.encode_utf16().collect();
+    let result = cef_string_userfree_utf16_alloc();
+    cef_string_utf16_set(utf16.as_ptr(), utf16.len(), result, 1);
+    result
+}
+
+/// Copy a string into an output argument.
+pub(super) unsafe fn set(output: *mut cef_string_t, chars: &[char16]) {
+    cef_string_utf16_set(chars.as_ptr(), chars.len(), output, 1);
+}
+
+unsafe fn utf16(string: *const cef_string_t) -> Vec<char16> {
+    if string.is_null() {
+        vec![]
+    }
+    else {
+        chars((*string).str, (*string).length).to_vec()
+    }
+}
+
+type List = Vec<Vec<char16>>;
+
+unsafe fn list<'a>(list: cef_string_list_t) -> &'a mut List {
+    &mut *(list as *mut List)
+}
+
+#[no_mangle]
+pub extern "C" fn cef_string_list_alloc() -> cef_string_list_t {
+    Box::into_raw(Box::new(List::new())) as *mut c_void
+}
+
+#[no_mangle]
+pub extern "C" fn cef_string_list_size(list: cef_string_list_t) -> usize {
+    unsafe { self::list(list).len() }
+}
+
+#[no_mangle]
+pub extern "C" fn cef_string_list_value(list: cef_string_list_t, index: usize, value: *mut cef_string_t) -> i32 {
+    unsafe {
+        match self::list(list).get(index) {
+            Some(string) => {
+                set(value, string);
+                1
+            },
+            None => 0,
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn cef_string_list_append(list: cef_string_list_t, value: *const cef_string_t) {
+    unsafe { self::list(list).push(utf16(value)) };
+}
+
+#[no_mangle]
+pub extern "C" fn cef_string_list_clear(list: cef_string_list_t) {
+    unsafe { self::list(list).clear() };
+}
+
+#[no_mangle]
+pub extern "C" fn cef_string_list_free(list: cef_string_list_t) {
+    if !list.is_null() {
+        unsafe { drop(Box::from_raw(list as *mut List)) };
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn cef_string_list_copy(list: cef_string_list_t) -> cef_string_list_t {
+    let copy = unsafe { self::list(list).clone() };
+    Box::into_raw(Box::new(copy)) as *mut c_void
+}
+
+type Map = Vec<(Vec<char16>, Vec<char16>)>;
+
+unsafe fn map<'a>(map: cef_string_map_t) -> &'a mut Map {
+    &mut *(map as *mut Map)
+}
+
+#[no_mangle]
+pub extern "C" fn cef_string_map_alloc() -> cef_string_map_t {
+    Box::into_raw(Box::new(Map::new())) as *mut c_void
+}
+
+#[no_mangle]
+pub extern "C" fn cef_string_map_size(map: cef_string_map_t) -> usize {
+    unsafe { self::map(map).len() }
+}
+
+#[no_mangle]
+pub extern "C" fn cef_string_map_find(map: cef_string_map_t, key: *const cef_string_t, value: *mut cef_string_t)
+    -> i32
+{
+    unsafe {
+        let key = utf16(key);
+        match self::map(map).iter().find(|&(map_key, _)| *map_key == key) {
+            Some((_, map_value)) => {
+                set(value, map_value);
+                1
+            },
+            None => 0,
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn cef_string_map_key(map: cef_string_map_t, index: usize, key: *mut cef_string_t) -> i32 {
+    unsafe {
+        match self::map(map).get(index) {
+            Some((map_key, _)) => {
+                set(key, map_key);
+                1
+            },
+            None => 0,
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn cef_string_map_value(map: cef_string_map_t, index: usize, value: *mut cef_string_t) -> i32 {
+    unsafe {
+        match self::map(map).get(index) {
+            Some((_, map_value)) => {
+                set(value, map_value);
+                1
+            },
+            None => 0,
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn cef_string_map_append(map: cef_string_map_t, key: *const cef_string_t,
+    value: *const cef_string_t) -> i32
+{
+    unsafe { self::map(map).push((utf16(key), utf16(value))) };
+    1
+}
+
+#[no_mangle]
+pub extern "C" fn cef_string_map_clear(map: cef_string_map_t) {
+    unsafe { self::map(map).clear() };
+}
+
+#[no_mangle]
+pub extern "C" fn cef_string_map_free(map: cef_string_map_t) {
+    if !map.is_null() {
+        unsafe { drop(Box::from_raw(map as *mut Map)) };
+    }
+}
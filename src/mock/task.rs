@@ -0,0 +1,92 @@
+use std::mem;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use sys::*;
+use super::{Ptr, locked, post, record, release, run_pending_tasks};
+use super::app::is_initialized;
+
+// The delayed tasks, with the time in milliseconds at which they are due.
+static DELAYED_TASKS: Mutex<Vec<(i64, PostedTask)>> = Mutex::new(Vec::new());
+// The time of the mock, which only moves with advance_time().
+static NOW: AtomicI64 = AtomicI64::new(0);
+
+// A task owns its reference, even when it is dropped without running.
+struct PostedTask(Ptr<cef_task_t>);
+
+impl PostedTask {
+    fn run(self) {
+        let task = (self.0).0;
+        unsafe {
+            if let Some(execute) = (*task).execute {
+                execute(task);
+            }
+        }
+    }
+}
+
+impl Drop for PostedTask {
+    fn drop(&mut self) {
+        unsafe { release((self.0).0) };
+    }
+}
+
+/// Only the UI thread exists in the mock.
+#[no_mangle]
+pub extern "C" fn cef_currently_on(thread_id: cef_thread_id_t) -> i32 {
+    match thread_id {
+        cef_thread_id_t::TID_UI => 1,
+        _ => 0,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn cef_post_task(thread_id: cef_thread_id_t, task: *mut cef_task_t) -> i32 {
+    cef_post_delayed_task(thread_id, task, 0)
+}
+
+/// Every task runs on the UI thread, with the other pending tasks. Like libcef, the tasks cannot be posted when
+/// CEF is not running.
+#[no_mangle]
+pub extern "C" fn cef_post_delayed_task(_thread_id: cef_thread_id_t, task: *mut cef_task_t, delay_ms: i64) -> i32 {
+    record(format!("cef_post_delayed_task {}", delay_ms));
+    if task.is_null() {
+        return 0;
+    }
+    let task = PostedTask(Ptr(task));
+    if !is_initialized() {
+        return 0;
+    }
+    if delay_ms <= 0 {
+        post(move || task.run());
+    }
+    else {
+        let due = NOW.load(Ordering::SeqCst).saturating_add(delay_ms);
+        locked(&DELAYED_TASKS).push((due, task));
+    }
+    1
+}
+
+/// Move the time of the mock forward, and run the pending tasks, including the delayed tasks that are now due.
+pub fn advance_time(milliseconds: i64) {
+    let now = NOW.fetch_add(milliseconds, Ordering::SeqCst) + milliseconds;
+    let mut due = {
+        let mut delayed = locked(&DELAYED_TASKS);
+        let (due, delayed_later) = mem::take(&mut *delayed).into_iter()
+            .partition::<Vec<_>, _>(|&(time, _)| time <= now);
+        *delayed = delayed_later;
+        due
+    };
+    due.sort_by_key(|&(time, _)| time);
+    for (_, task) in due {
+        post(move || task.run());
+    }
+    run_pending_tasks();
+}
+
+// Drop the delayed tasks, releasing them, when CEF shuts down or when a test starts.
+pub(super) fn clear() {
+    let delayed = mem::take(&mut *locked(&DELAYED_TASKS));
+    drop(delayed);
+    NOW.store(0, Ordering::SeqCst);
+}
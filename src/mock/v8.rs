@@ -0,0 +1,49 @@
+use std::mem;
+use std::ptr;
+use std::sync::Mutex;
+
+use rc::RefCounted;
+use sys::*;
+use super::{locked, record};
+
+// The value of the scripts evaluated by the V8 contexts, or None to throw an exception.
+static SCRIPT_RESULT: Mutex<Option<i32>> = Mutex::new(None);
+
+/// Set the integer returned by the scripts evaluated in the frames, or `None` to make them throw.
+pub fn set_script_result(result: Option<i32>) {
+    *locked(&SCRIPT_RESULT) = result;
+}
+
+pub(super) fn new_v8context() -> *mut cef_v8context_t {
+    // The exceptions are not created, since they are only released by the bindings.
+    extern "C" fn eval(_self: *mut cef_v8context_t, _code: *const cef_string_t, _script_url: *const cef_string_t,
+        _start_line: i32, retval: *mut *mut cef_v8value_t, exception: *mut *mut cef_v8exception_t) -> i32
+    {
+        record("V8Context::eval");
+        let result = *locked(&SCRIPT_RESULT);
+        unsafe {
+            *exception = ptr::null_mut();
+            *retval = result.map(new_int_value).unwrap_or(ptr::null_mut());
+        }
+        result.is_some() as i32
+    }
+
+    let mut raw: cef_v8context_t = unsafe { mem::zeroed() };
+    raw.eval = Some(eval);
+    RefCounted::wrap(raw, ())
+}
+
+fn new_int_value(value: i32) -> *mut cef_v8value_t {
+    extern "C" fn is_int(_self: *mut cef_v8value_t) -> i32 {
+        1
+    }
+
+    extern "C" fn get_int_value(self_: *mut cef_v8value_t) -> i32 {
+        *unsafe { RefCounted::<_, i32>::value(self_) }
+    }
+
+    let mut raw: cef_v8value_t = unsafe { mem::zeroed() };
+    raw.is_int = Some(is_int);
+    raw.get_int_value = Some(get_int_value);
+    RefCounted::wrap(raw, value)
+}
@@ -0,0 +1,238 @@
+use std::mem;
+use std::ptr;
+use std::sync::Mutex;
+
+use rc::RefCounted;
+use string::CefStr;
+use sys::*;
+use super::{Ptr, add_ref, locked, record, release};
+use super::strings::userfree;
+
+enum Value {
+    Null,
+    Bool(bool),
+    Int(i32),
+    Double(f64),
+    String(String),
+    List(Ptr<cef_list_value_t>),
+}
+
+impl Drop for Value {
+    fn drop(&mut self) {
+        if let Value::List(list) = *self {
+            unsafe { release(list.0) };
+        }
+    }
+}
+
+struct List {
+    values: Mutex<Vec<Value>>,
+}
+
+#[no_mangle]
+pub extern "C" fn cef_list_value_create() -> *mut cef_list_value_t {
+    record("cef_list_value_create");
+    create_list()
+}
+
+fn create_list() -> *mut cef_list_value_t {
+    let mut raw: cef_list_value_t = unsafe { mem::zeroed() };
+    raw.is_valid = Some(is_valid);
+    raw.is_owned = Some(is_owned);
+    raw.is_read_only = Some(is_read_only);
+    raw.set_size = Some(set_size);
+    raw.get_size = Some(get_size);
+    raw.clear = Some(clear);
+    raw.remove = Some(remove);
+    raw.get_type = Some(get_type);
+    raw.get_bool = Some(get_bool);
+    raw.get_int = Some(get_int);
+    raw.get_double = Some(get_double);
+    raw.get_string = Some(get_string);
+    raw.get_list = Some(get_list);
+    raw.set_null = Some(set_null);
+    raw.set_bool = Some(set_bool);
+    raw.set_int = Some(set_int);
+    raw.set_double = Some(set_double);
+    raw.set_string = Some(set_string);
+    raw.set_list = Some(set_list);
+    RefCounted::wrap(raw, List {
+        values: Mutex::new(vec![]),
+    })
+}
+
+fn values<'a>(list: *mut cef_list_value_t) -> &'a Mutex<Vec<Value>> {
+    unsafe { &RefCounted::<_, List>::value(list).values }
+}
+
+fn set(list: *mut cef_list_value_t, index: usize, value: Value) -> i32 {
+    let mut values = locked(values(list));
+    while values.len() <= index {
+        values.push(Value::Null);
+    }
+    values[index] = value;
+    1
+}
+
+extern "C" fn is_valid(_self: *mut cef_list_value_t) -> i32 {
+    1
+}
+
+extern "C" fn is_owned(_self: *mut cef_list_value_t) -> i32 {
+    0
+}
+
+extern "C" fn is_read_only(_self: *mut cef_list_value_t) -> i32 {
+    0
+}
+
+extern "C" fn set_size(self_: *mut cef_list_value_t, size: usize) -> i32 {
+    let mut values = locked(values(self_));
+    values.truncate(size);
+    while values.len() < size {
+        values.push(Value::Null);
+    }
+    1
+}
+
+extern "C" fn get_size(self_: *mut cef_list_value_t) -> usize {
+    locked(values(self_)).len()
+}
+
+extern "C" fn clear(self_: *mut cef_list_value_t) -> i32 {
+    locked(values(self_)).clear();
+    1
+}
+
+extern "C" fn remove(self_: *mut cef_list_value_t, index: usize) -> i32 {
+    let mut values = locked(values(self_));
+    if index < values.len() {
+        values.remove(index);
+        1
+    }
+    else {
+        0
+    }
+}
+
+extern "C" fn get_type(self_: *mut cef_list_value_t, index: usize) -> cef_value_type_t {
+    match locked(values(self_)).get(index) {
+        Some(&Value::Null) => cef_value_type_t::VTYPE_NULL,
+        Some(&Value::Bool(_)) => cef_value_type_t::VTYPE_BOOL,
+        Some(&Value::Int(_)) => cef_value_type_t::VTYPE_INT,
+        Some(&Value::Double(_)) => cef_value_type_t::VTYPE_DOUBLE,
+        Some(&Value::String(_)) => cef_value_type_t::VTYPE_STRING,
+        Some(&Value::List(_)) => cef_value_type_t::VTYPE_LIST,
+        None => cef_value_type_t::VTYPE_INVALID,
+    }
+}
+
+extern "C" fn get_bool(self_: *mut cef_list_value_t, index: usize) -> i32 {
+    match locked(values(self_)).get(index) {
+        Some(&Value::Bool(value)) => value as i32,
+        _ => 0,
+    }
+}
+
+extern "C" fn get_int(self_: *mut cef_list_value_t, index: usize) -> i32 {
+    match locked(values(self_)).get(index) {
+        Some(&Value::Int(value)) => value,
+        _ => 0,
+    }
+}
+
+extern "C" fn get_double(self_: *mut cef_list_value_t, index: usize) -> f64 {
+    match locked(values(self_)).get(index) {
+        Some(&Value::Double(value)) => value,
+        Some(&Value::Int(value)) => value as f64,
+        _ => 0.0,
+    }
+}
+
+extern "C" fn get_string(self_: *mut cef_list_value_t, index: usize) -> cef_string_userfree_t {
+    match locked(values(self_)).get(index) {
+        Some(Value::String(value)) => userfree(value),
+        _ => userfree(""),
+    }
+}
+
+extern "C" fn get_list(self_: *mut cef_list_value_t, index: usize) -> *mut cef_list_value_t {
+    match locked(values(self_)).get(index) {
+        Some(&Value::List(list)) => unsafe { add_ref(list.0) },
+        _ => ptr::null_mut(),
+    }
+}
+
+extern "C" fn set_null(self_: *mut cef_list_value_t, index: usize) -> i32 {
+    set(self_, index, Value::Null)
+}
+
+extern "C" fn set_bool(self_: *mut cef_list_value_t, index: usize, value: i32) -> i32 {
+    set(self_, index, Value::Bool(value != 0))
+}
+
+extern "C" fn set_int(self_: *mut cef_list_value_t, index: usize, value: i32) -> i32 {
+    set(self_, index, Value::Int(value))
+}
+
+extern "C" fn set_double(self_: *mut cef_list_value_t, index: usize, value: f64) -> i32 {
+    set(self_, index, Value::Double(value))
+}
+
+extern "C" fn set_string(self_: *mut cef_list_value_t, index: usize, value: *const cef_string_t) -> i32 {
+    set(self_, index, Value::String(unsafe { CefStr::from_ptr(value) }.to_string()))
+}
+
+// Takes the reference of the argument.
+extern "C" fn set_list(self_: *mut cef_list_value_t, index: usize, value: *mut cef_list_value_t) -> i32 {
+    if value.is_null() {
+        return 0;
+    }
+    set(self_, index, Value::List(Ptr(value)))
+}
+
+struct ProcessMessage {
+    name: String,
+    arguments: Ptr<cef_list_value_t>,
+}
+
+impl Drop for ProcessMessage {
+    fn drop(&mut self) {
+        unsafe { release(self.arguments.0) };
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn cef_process_message_create(name: *const cef_string_t) -> *mut cef_process_message_t {
+    let name = unsafe { CefStr::from_ptr(name) }.to_string();
+    record(format!("cef_process_message_create {}", name));
+    create_process_message(name)
+}
+
+fn create_process_message(name: String) -> *mut cef_process_message_t {
+    extern "C" fn is_valid(_self: *mut cef_process_message_t) -> i32 {
+        1
+    }
+
+    extern "C" fn is_read_only(_self: *mut cef_process_message_t) -> i32 {
+        0
+    }
+
+    extern "C" fn get_name(self_: *mut cef_process_message_t) -> cef_string_userfree_t {
+        userfree(&unsafe { RefCounted::<_, ProcessMessage>::value(self_) }.name)
+    }
+
+    extern "C" fn get_argument_list(self_: *mut cef_process_message_t) -> *mut cef_list_value_t {
+        unsafe { add_ref(RefCounted::<_, ProcessMessage>::value(self_).arguments.0) }
+    }
+
+    let mut raw: cef_process_message_t = unsafe { mem::zeroed() };
+    raw.is_valid = Some(is_valid);
+    raw.is_read_only = Some(is_read_only);
+    raw.get_name = Some(get_name);
+    raw.get_argument_list = Some(get_argument_list);
+    RefCounted::wrap(raw, ProcessMessage {
+        name,
+        arguments: Ptr(create_list()),
+    })
+}
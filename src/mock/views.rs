@@ -0,0 +1,514 @@
+use std::mem;
+use std::ptr;
+use std::sync::Mutex;
+
+use rc::RefCounted;
+use string::{CefStr, CefString};
+use sys::*;
+use super::{Ptr, add_ref, browser, locked, post, record, release};
+use super::strings::userfree;
+
+// The top-level windows which are not destroyed yet, with a reference.
+static WINDOWS: Mutex<Vec<Ptr<cef_window_t>>> = Mutex::new(Vec::new());
+
+// The value of both the windows and the browser views.
+struct View {
+    // The window delegate or the browser view delegate.
+    delegate: Ptr<cef_view_delegate_t>,
+    client: Ptr<cef_client_t>,
+    url: CefString,
+    settings: cef_browser_settings_t,
+    state: Mutex<State>,
+}
+
+// The settings hold no string, and the mock only runs on the thread of the test.
+unsafe impl Send for View {}
+
+unsafe impl Sync for View {}
+
+impl Drop for View {
+    fn drop(&mut self) {
+        let state = locked(&self.state);
+        unsafe {
+            release(self.delegate.0);
+            release(self.client.0);
+            release(state.browser.0);
+            for child in &state.children {
+                release(child.0);
+            }
+        }
+    }
+}
+
+struct State {
+    id: i32,
+    visible: bool,
+    enabled: bool,
+    closed: bool,
+    fullscreen: bool,
+    title: String,
+    // Not a reference: the parent owns its children.
+    parent: Ptr<cef_view_t>,
+    children: Vec<Ptr<cef_view_t>>,
+    // Created when the browser view is added to a window.
+    browser: Ptr<cef_browser_t>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State {
+            id: 0,
+            visible: false,
+            enabled: true,
+            closed: false,
+            fullscreen: false,
+            title: String::new(),
+            parent: Ptr(ptr::null_mut()),
+            children: vec![],
+            browser: Ptr(ptr::null_mut()),
+        }
+    }
+}
+
+// The size of the C struct, set by RefCounted::wrap, tells a window from a browser view.
+unsafe fn is_window(view: *mut cef_view_t) -> bool {
+    (*view).base.size == mem::size_of::<cef_window_t>()
+}
+
+unsafe fn value<'a>(view: *mut cef_view_t) -> &'a View {
+    if is_window(view) {
+        RefCounted::<_, View>::value(view as *mut cef_window_t)
+    }
+    else {
+        RefCounted::<_, View>::value(view as *mut cef_browser_view_t)
+    }
+}
+
+fn state<'a>(view: *mut cef_view_t) -> &'a Mutex<State> {
+    unsafe { &value(view).state }
+}
+
+fn fill_view(raw: &mut cef_view_t) {
+    raw.as_browser_view = Some(as_browser_view);
+    raw.as_button = Some(as_button);
+    raw.as_panel = Some(as_panel);
+    raw.as_scroll_view = Some(as_scroll_view);
+    raw.as_textfield = Some(as_textfield);
+    raw.is_valid = Some(is_valid);
+    raw.is_attached = Some(is_attached);
+    raw.is_same = Some(is_same);
+    raw.get_delegate = Some(get_delegate);
+    raw.get_window = Some(get_window);
+    raw.get_id = Some(get_id);
+    raw.set_id = Some(set_id);
+    raw.get_parent_view = Some(get_parent_view);
+    raw.set_visible = Some(set_visible);
+    raw.is_visible = Some(is_visible);
+    raw.set_enabled = Some(set_enabled);
+    raw.is_enabled = Some(is_enabled);
+    raw.request_focus = Some(request_focus);
+}
+
+#[no_mangle]
+pub extern "C" fn cef_window_create_top_level(delegate: *mut cef_window_delegate_t) -> *mut cef_window_t {
+    record("cef_window_create_top_level");
+    let mut raw: cef_window_t = unsafe { mem::zeroed() };
+    fill_view(&mut raw.base.base);
+    raw.base.as_window = Some(as_window);
+    raw.base.layout = Some(layout);
+    raw.base.add_child_view = Some(add_child_view);
+    raw.base.add_child_view_at = Some(add_child_view_at);
+    raw.base.remove_child_view = Some(remove_child_view);
+    raw.base.remove_all_child_views = Some(remove_all_child_views);
+    raw.base.get_child_view_count = Some(get_child_view_count);
+    raw.base.get_child_view_at = Some(get_child_view_at);
+    raw.show = Some(show);
+    raw.hide = Some(hide);
+    raw.close = Some(close);
+    raw.is_closed = Some(is_closed);
+    raw.activate = Some(activate);
+    raw.deactivate = Some(deactivate);
+    raw.is_active = Some(is_active);
+    raw.bring_to_top = Some(bring_to_top);
+    raw.set_always_on_top = Some(set_always_on_top);
+    raw.maximize = Some(maximize);
+    raw.minimize = Some(minimize);
+    raw.restore = Some(restore);
+    raw.set_fullscreen = Some(set_fullscreen);
+    raw.is_fullscreen = Some(is_fullscreen);
+    raw.set_title = Some(set_title);
+    raw.get_title = Some(get_title);
+    let window = RefCounted::wrap(raw, View {
+        delegate: Ptr(delegate as *mut cef_view_delegate_t),
+        client: Ptr(ptr::null_mut()),
+        url: CefString::default(),
+        settings: unsafe { mem::zeroed() },
+        state: Mutex::new(State::default()),
+    });
+    unsafe {
+        locked(&WINDOWS).push(Ptr(add_ref(window)));
+        if !delegate.is_null() {
+            if let Some(on_window_created) = (*delegate).on_window_created {
+                on_window_created(delegate, add_ref(window));
+            }
+        }
+    }
+    window
+}
+
+#[no_mangle]
+pub extern "C" fn cef_browser_view_create(client: *mut cef_client_t, url: *const cef_string_t,
+    settings: *const cef_browser_settings_t, request_context: *mut cef_request_context_t,
+    delegate: *mut cef_browser_view_delegate_t) -> *mut cef_browser_view_t
+{
+    record("cef_browser_view_create");
+    let mut raw: cef_browser_view_t = unsafe { mem::zeroed() };
+    fill_view(&mut raw.base);
+    raw.get_browser = Some(get_browser);
+    raw.set_prefer_accelerators = Some(set_prefer_accelerators);
+    // The strings of the settings are not kept: the browsers of the mock do not use them.
+    let mut browser_settings: cef_browser_settings_t = unsafe { mem::zeroed() };
+    browser_settings.size = mem::size_of::<cef_browser_settings_t>();
+    if !settings.is_null() {
+        browser_settings.windowless_frame_rate = unsafe { (*settings).windowless_frame_rate };
+    }
+    unsafe { release(request_context) };
+    RefCounted::wrap(raw, View {
+        delegate: Ptr(delegate as *mut cef_view_delegate_t),
+        client: Ptr(client),
+        url: CefString::from(unsafe { CefStr::from_ptr(url) }),
+        settings: browser_settings,
+        state: Mutex::new(State::default()),
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn cef_browser_view_get_for_browser(browser: *mut cef_browser_t) -> *mut cef_browser_view_t {
+    let windows: Vec<_> = locked(&WINDOWS).clone();
+    let mut result = ptr::null_mut();
+    for window in windows {
+        for child in &locked(state(window.0 as *mut cef_view_t)).children {
+            let child_browser = locked(state(child.0)).browser.0;
+            if !child_browser.is_null() && child_browser == browser {
+                result = unsafe { add_ref(child.0 as *mut cef_browser_view_t) };
+            }
+        }
+    }
+    unsafe { release(browser) };
+    result
+}
+
+/// The number of top-level windows which are not destroyed yet.
+pub fn window_count() -> usize {
+    locked(&WINDOWS).len()
+}
+
+/// Destroy the windows left open at shutdown.
+pub(super) fn close_all() {
+    let windows: Vec<_> = locked(&WINDOWS).clone();
+    for window in windows {
+        unsafe { destroy(window.0) };
+    }
+}
+
+// Close the browsers of the window, then notify its delegate.
+unsafe fn destroy(window: *mut cef_window_t) {
+    let view = window as *mut cef_view_t;
+    let children = {
+        let mut state = locked(state(view));
+        if state.closed {
+            return;
+        }
+        state.closed = true;
+        state.visible = false;
+        mem::take(&mut state.children)
+    };
+    for child in children {
+        detach(child.0);
+        release(child.0);
+    }
+    let delegate = value(view).delegate.0 as *mut cef_window_delegate_t;
+    if !delegate.is_null() {
+        if let Some(on_window_destroyed) = (*delegate).on_window_destroyed {
+            on_window_destroyed(delegate, add_ref(window));
+        }
+    }
+    let destroyed = {
+        let mut windows = locked(&WINDOWS);
+        let index = windows.iter().position(|open_window| open_window.0 == window);
+        index.map(|index| windows.remove(index))
+    };
+    if let Some(destroyed) = destroyed {
+        release(destroyed.0);
+    }
+}
+
+// Create the browser of a browser view added to a window.
+unsafe fn attach(child: *mut cef_view_t, parent: *mut cef_view_t) {
+    locked(state(child)).parent = Ptr(parent);
+    if is_window(child) {
+        return;
+    }
+    let view = value(child);
+    if !locked(&view.state).browser.0.is_null() {
+        return;
+    }
+    let browser = browser::create(add_ref(view.client.0), view.url.as_raw(), &view.settings, false, true, false);
+    locked(&view.state).browser = Ptr(add_ref(browser));
+    let delegate = view.delegate.0 as *mut cef_browser_view_delegate_t;
+    if !delegate.is_null() {
+        if let Some(on_browser_created) = (*delegate).on_browser_created {
+            on_browser_created(delegate, add_ref(child as *mut cef_browser_view_t), add_ref(browser));
+        }
+    }
+    release(browser);
+}
+
+// Close the browser of a browser view removed from its window.
+unsafe fn detach(child: *mut cef_view_t) {
+    let browser = {
+        let mut state = locked(state(child));
+        state.parent = Ptr(ptr::null_mut());
+        mem::replace(&mut state.browser, Ptr(ptr::null_mut()))
+    };
+    if browser.0.is_null() {
+        return;
+    }
+    let browser_id = (*browser.0).get_identifier.expect("get_identifier")(browser.0);
+    browser::close(browser_id, false);
+    let delegate = value(child).delegate.0 as *mut cef_browser_view_delegate_t;
+    if !delegate.is_null() {
+        if let Some(on_browser_destroyed) = (*delegate).on_browser_destroyed {
+            on_browser_destroyed(delegate, add_ref(child as *mut cef_browser_view_t), add_ref(browser.0));
+        }
+    }
+    release(browser.0);
+}
+
+extern "C" fn as_browser_view(self_: *mut cef_view_t) -> *mut cef_browser_view_t {
+    unsafe {
+        if is_window(self_) {
+            ptr::null_mut()
+        }
+        else {
+            add_ref(self_ as *mut cef_browser_view_t)
+        }
+    }
+}
+
+extern "C" fn as_button(_self: *mut cef_view_t) -> *mut cef_button_t {
+    ptr::null_mut()
+}
+
+extern "C" fn as_panel(self_: *mut cef_view_t) -> *mut cef_panel_t {
+    unsafe {
+        if is_window(self_) {
+            add_ref(self_ as *mut cef_panel_t)
+        }
+        else {
+            ptr::null_mut()
+        }
+    }
+}
+
+extern "C" fn as_scroll_view(_self: *mut cef_view_t) -> *mut cef_scroll_view_t {
+    ptr::null_mut()
+}
+
+extern "C" fn as_textfield(_self: *mut cef_view_t) -> *mut cef_textfield_t {
+    ptr::null_mut()
+}
+
+extern "C" fn is_valid(self_: *mut cef_view_t) -> i32 {
+    !locked(state(self_)).closed as i32
+}
+
+extern "C" fn is_attached(self_: *mut cef_view_t) -> i32 {
+    unsafe { (is_window(self_) || !locked(state(self_)).parent.0.is_null()) as i32 }
+}
+
+extern "C" fn is_same(self_: *mut cef_view_t, that: *mut cef_view_t) -> i32 {
+    unsafe { release(that) };
+    (self_ == that) as i32
+}
+
+extern "C" fn get_delegate(self_: *mut cef_view_t) -> *mut cef_view_delegate_t {
+    unsafe { add_ref(value(self_).delegate.0) }
+}
+
+extern "C" fn get_window(self_: *mut cef_view_t) -> *mut cef_window_t {
+    unsafe {
+        if is_window(self_) {
+            add_ref(self_ as *mut cef_window_t)
+        }
+        else {
+            add_ref(locked(state(self_)).parent.0 as *mut cef_window_t)
+        }
+    }
+}
+
+extern "C" fn get_id(self_: *mut cef_view_t) -> i32 {
+    locked(state(self_)).id
+}
+
+extern "C" fn set_id(self_: *mut cef_view_t, id: i32) {
+    locked(state(self_)).id = id;
+}
+
+extern "C" fn get_parent_view(self_: *mut cef_view_t) -> *mut cef_view_t {
+    unsafe { add_ref(locked(state(self_)).parent.0) }
+}
+
+extern "C" fn set_visible(self_: *mut cef_view_t, visible: i32) {
+    locked(state(self_)).visible = visible != 0;
+}
+
+extern "C" fn is_visible(self_: *mut cef_view_t) -> i32 {
+    locked(state(self_)).visible as i32
+}
+
+extern "C" fn set_enabled(self_: *mut cef_view_t, enabled: i32) {
+    locked(state(self_)).enabled = enabled != 0;
+}
+
+extern "C" fn is_enabled(self_: *mut cef_view_t) -> i32 {
+    locked(state(self_)).enabled as i32
+}
+
+extern "C" fn request_focus(_self: *mut cef_view_t) {
+    record("View::request_focus");
+}
+
+extern "C" fn get_browser(self_: *mut cef_browser_view_t) -> *mut cef_browser_t {
+    unsafe { add_ref(locked(state(self_ as *mut cef_view_t)).browser.0) }
+}
+
+extern "C" fn set_prefer_accelerators(_self: *mut cef_browser_view_t, prefer_accelerators: i32) {
+    record(format!("BrowserView::set_prefer_accelerators {}", prefer_accelerators != 0));
+}
+
+extern "C" fn as_window(self_: *mut cef_panel_t) -> *mut cef_window_t {
+    unsafe { add_ref(self_ as *mut cef_window_t) }
+}
+
+extern "C" fn layout(_self: *mut cef_panel_t) {
+    record("Panel::layout");
+}
+
+extern "C" fn add_child_view(self_: *mut cef_panel_t, view: *mut cef_view_t) {
+    let count = get_child_view_count(self_);
+    add_child_view_at(self_, view, count as i32);
+}
+
+// Takes the reference of the view.
+extern "C" fn add_child_view_at(self_: *mut cef_panel_t, view: *mut cef_view_t, index: i32) {
+    record("Panel::add_child_view");
+    if view.is_null() {
+        return;
+    }
+    let panel = self_ as *mut cef_view_t;
+    {
+        let mut state = locked(state(panel));
+        let index = (index.max(0) as usize).min(state.children.len());
+        state.children.insert(index, Ptr(view));
+    }
+    unsafe { attach(view, panel) };
+}
+
+extern "C" fn remove_child_view(self_: *mut cef_panel_t, view: *mut cef_view_t) {
+    record("Panel::remove_child_view");
+    let removed = {
+        let mut state = locked(state(self_ as *mut cef_view_t));
+        let index = state.children.iter().position(|child| child.0 == view);
+        index.map(|index| state.children.remove(index))
+    };
+    unsafe {
+        if let Some(removed) = removed {
+            detach(removed.0);
+            release(removed.0);
+        }
+        release(view);
+    }
+}
+
+extern "C" fn remove_all_child_views(self_: *mut cef_panel_t) {
+    record("Panel::remove_all_child_views");
+    let children = mem::take(&mut locked(state(self_ as *mut cef_view_t)).children);
+    for child in children {
+        unsafe {
+            detach(child.0);
+            release(child.0);
+        }
+    }
+}
+
+extern "C" fn get_child_view_count(self_: *mut cef_panel_t) -> usize {
+    locked(state(self_ as *mut cef_view_t)).children.len()
+}
+
+extern "C" fn get_child_view_at(self_: *mut cef_panel_t, index: i32) -> *mut cef_view_t {
+    let state = locked(state(self_ as *mut cef_view_t));
+    match state.children.get(index as usize) {
+        Some(child) if index >= 0 => unsafe { add_ref(child.0) },
+        _ => ptr::null_mut(),
+    }
+}
+
+extern "C" fn show(self_: *mut cef_window_t) {
+    record("Window::show");
+    locked(state(self_ as *mut cef_view_t)).visible = true;
+}
+
+extern "C" fn hide(self_: *mut cef_window_t) {
+    record("Window::hide");
+    locked(state(self_ as *mut cef_view_t)).visible = false;
+}
+
+extern "C" fn close(self_: *mut cef_window_t) {
+    record("Window::close");
+    let window = Ptr(unsafe { add_ref(self_) });
+    post(move || unsafe {
+        destroy(window.0);
+        release(window.0);
+    });
+}
+
+extern "C" fn is_closed(self_: *mut cef_window_t) -> i32 {
+    locked(state(self_ as *mut cef_view_t)).closed as i32
+}
+
+macro_rules! window_commands {
+    ($($name:ident),*) => {
+        $(
+            extern "C" fn $name(_self: *mut cef_window_t) {
+                record(concat!("Window::", stringify!($name)));
+            }
+        )*
+    };
+}
+
+window_commands!(activate, deactivate, bring_to_top, maximize, minimize, restore);
+
+extern "C" fn is_active(self_: *mut cef_window_t) -> i32 {
+    is_visible(self_ as *mut cef_view_t)
+}
+
+extern "C" fn set_always_on_top(_self: *mut cef_window_t, on_top: i32) {
+    record(format!("Window::set_always_on_top {}", on_top != 0));
+}
+
+extern "C" fn set_fullscreen(self_: *mut cef_window_t, fullscreen: i32) {
+    locked(state(self_ as *mut cef_view_t)).fullscreen = fullscreen != 0;
+}
+
+extern "C" fn is_fullscreen(self_: *mut cef_window_t) -> i32 {
+    locked(state(self_ as *mut cef_view_t)).fullscreen as i32
+}
+
+extern "C" fn set_title(self_: *mut cef_window_t, title: *const cef_string_t) {
+    locked(state(self_ as *mut cef_view_t)).title = unsafe { CefStr::from_ptr(title) }.to_string();
+}
+
+extern "C" fn get_title(self_: *mut cef_window_t) -> cef_string_userfree_t {
+    userfree(&locked(state(self_ as *mut cef_view_t)).title)
+}
@@ -0,0 +1,352 @@
+use std::error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use browser::{Browser, Frame};
+use error::NetError;
+use pending::{self, Pending, PendingState, locked};
+
+// How long a navigation can take when no other timeout is given.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The HTTP status code of a loaded page, which is 0 when the page was not loaded over HTTP.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HttpStatus(pub i32);
+
+impl HttpStatus {
+    /// From 200 to 299.
+    pub fn is_success(&self) -> bool {
+        self.0 >= 200 && self.0 < 300
+    }
+}
+
+/// Why the main frame did not finish loading the URL of a `Navigation`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LoadError {
+    Failed {
+        error: NetError,
+        text: String,
+        url: String,
+    },
+    /// The navigation was stopped, or replaced by another one, like a new `Browser::load_url`.
+    Aborted,
+    TimedOut,
+    /// The browser was closed before the page was loaded.
+    Closed,
+    /// A task could not be posted to the UI thread, because CEF is not running.
+    PostTask,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LoadError::Failed { ref error, ref url, .. } => write!(formatter, "cannot load {}: {}", url, error),
+            LoadError::Aborted => write!(formatter, "the navigation was aborted"),
+            LoadError::TimedOut => write!(formatter, "the navigation timed out"),
+            LoadError::Closed => write!(formatter, "the browser was closed before the page was loaded"),
+            LoadError::PostTask => write!(formatter, "cannot post a task to the UI thread"),
+        }
+    }
+}
+
+impl error::Error for LoadError {}
+
+type LoadResult = Result<HttpStatus, LoadError>;
+
+struct State {
+    browser_id: i32,
+    url: String,
+    // Whether the main frame started loading since the navigation was created.
+    started: bool,
+    pending: Pending<LoadResult>,
+}
+
+impl PendingState for State {
+    type Output = LoadResult;
+    type Finished = Option<Waker>;
+
+    fn pending(&mut self) -> &mut Pending<LoadResult> {
+        &mut self.pending
+    }
+
+    fn finish(&mut self, result: LoadResult) -> Option<Waker> {
+        self.pending.finish(result)
+    }
+
+    fn complete(state: &Arc<Mutex<State>>, waker: Option<Waker>) {
+        remove(state);
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+// The navigations waiting for a load event of their browser.
+static PENDING: Mutex<Vec<Arc<Mutex<State>>>> = Mutex::new(Vec::new());
+
+// Finish the pending navigations of the browser for which `event` returns a result.
+fn dispatch<F: FnMut(&mut State) -> Option<LoadResult>>(browser_id: i32, mut event: F) {
+    let mut wakers = vec![];
+    locked(&PENDING).retain(|state| {
+        let mut state = locked(state);
+        if state.browser_id != browser_id {
+            return true;
+        }
+        match event(&mut state) {
+            Some(result) => {
+                wakers.extend(state.finish(result));
+                false
+            },
+            None => true,
+        }
+    });
+    for waker in wakers {
+        waker.wake();
+    }
+}
+
+fn remove(state: &Arc<Mutex<State>>) {
+    locked(&PENDING).retain(|pending| !Arc::ptr_eq(pending, state));
+}
+
+// CEF adds a slash to the URLs without a path.
+fn same_url(url1: &str, url2: &str) -> bool {
+    url1.trim_end_matches('/') == url2.trim_end_matches('/')
+}
+
+pub(crate) fn on_load_start(browser: &Browser, frame: &Frame) {
+    if frame.is_main() {
+        dispatch(browser.identifier(), |state| {
+            state.started = true;
+            None
+        });
+    }
+}
+
+pub(crate) fn on_load_end(browser: &Browser, frame: &Frame, status: HttpStatus) {
+    if frame.is_main() {
+        // Before the start, this is the end of the previous page.
+        dispatch(browser.identifier(), |state| if state.started { Some(Ok(status)) } else { None });
+    }
+}
+
+pub(crate) fn on_load_error(browser: &Browser, frame: &Frame, error: NetError, text: &str, url: &str) {
+    if !frame.is_main() {
+        return;
+    }
+    dispatch(browser.identifier(), |state| {
+        if error == NetError::ABORTED {
+            // Loading a URL aborts the previous navigation when it is still loading.
+            if state.started || same_url(&state.url, url) {
+                Some(Err(LoadError::Aborted))
+            }
+            else {
+                None
+            }
+        }
+        else {
+            Some(Err(LoadError::Failed {
+                error,
+                text: text.to_string(),
+                url: url.to_string(),
+            }))
+        }
+    });
+}
+
+pub(crate) fn on_before_close(browser: &Browser) {
+    dispatch(browser.identifier(), |_| Some(Err(LoadError::Closed)));
+}
+
+/// A future resolved when the main frame finishes loading the URL given to `Browser::load_url`, or fails to.
+///
+/// The subframes are ignored. Only the browsers whose client was created by `wrap_client` resolve it, with
+/// `LoadError::Closed` when they are closed first.
+#[must_use = "the load result is only known by awaiting the navigation"]
+pub struct Navigation {
+    state: Arc<Mutex<State>>,
+}
+
+impl Navigation {
+    pub(crate) fn start(browser: &Browser, url: &str) -> Self {
+        let browser_id = browser.identifier();
+        dispatch(browser_id, |_| Some(Err(LoadError::Aborted)));
+        let navigation = Navigation {
+            state: Arc::new(Mutex::new(State {
+                browser_id,
+                url: url.to_string(),
+                started: false,
+                pending: Pending::new(),
+            })),
+        };
+        match browser.main_frame() {
+            Some(frame) => {
+                locked(&PENDING).push(navigation.state.clone());
+                frame.load_url(url);
+            },
+            None => {
+                pending::finish(&navigation.state, Err(LoadError::Failed {
+                    error: NetError::FAILED,
+                    text: "the browser has no main frame".to_string(),
+                    url: url.to_string(),
+                }));
+            },
+        }
+        navigation.timeout(DEFAULT_TIMEOUT)
+    }
+
+    /// Resolve with `LoadError::TimedOut` when the page is not loaded after `timeout`, measured on the UI
+    /// thread, instead of the default of 30 seconds.
+    ///
+    /// If the timeout cannot be posted to the UI thread, because CEF is not running, the navigation fails right
+    /// away with `LoadError::PostTask`.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        pending::set_timeout(&self.state, timeout, Err(LoadError::TimedOut), Err(LoadError::PostTask));
+        self
+    }
+}
+
+impl Future for Navigation {
+    type Output = LoadResult;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<LoadResult> {
+        locked(&self.state).pending.poll(context)
+    }
+}
+
+impl Drop for Navigation {
+    fn drop(&mut self) {
+        remove(&self.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::Poll;
+
+    use mock::{self, TestClient};
+    use sys::{ERR_ABORTED, ERR_NAME_NOT_RESOLVED, TT_AUTO_SUBFRAME, TT_EXPLICIT};
+    use super::*;
+
+    #[test]
+    fn load() {
+        let _guard = mock::lock();
+        let cef = mock::start();
+        let browser = mock::create_browser(TestClient::new());
+        let (id, main_frame) = (browser.identifier(), mock::main_frame_id(browser.identifier()).unwrap());
+        let mut navigation = browser.load_url("https://example.com/page");
+        assert!(mock::calls().contains(&"Frame::load_url https://example.com/page".to_string()));
+        // The end of the previous page.
+        mock::load_end(id, main_frame, 200);
+        assert_eq!(mock::poll(&mut navigation), Poll::Pending);
+        mock::load_start(id, main_frame, TT_EXPLICIT);
+        mock::load_end(id, main_frame, 404);
+        assert_eq!(mock::poll(&mut navigation), Poll::Ready(Ok(HttpStatus(404))));
+        drop((navigation, browser));
+        drop(cef);
+        assert_eq!(mock::live_objects(), 0);
+    }
+
+    #[test]
+    fn subframes() {
+        let _guard = mock::lock();
+        let cef = mock::start();
+        let browser = mock::create_browser(TestClient::new());
+        let (id, main_frame) = (browser.identifier(), mock::main_frame_id(browser.identifier()).unwrap());
+        let subframe = mock::add_subframe(id, "child").unwrap();
+        let mut navigation = browser.load_url("https://example.com");
+        mock::load_start(id, main_frame, TT_EXPLICIT);
+        mock::load_start(id, subframe, TT_AUTO_SUBFRAME);
+        mock::load_error(id, subframe, ERR_NAME_NOT_RESOLVED, "net::ERR_NAME_NOT_RESOLVED", "https://ads.com/");
+        mock::load_end(id, subframe, 200);
+        assert_eq!(mock::poll(&mut navigation), Poll::Pending);
+        mock::load_end(id, main_frame, 200);
+        assert_eq!(mock::poll(&mut navigation), Poll::Ready(Ok(HttpStatus(200))));
+        drop((navigation, browser));
+        drop(cef);
+        assert_eq!(mock::live_objects(), 0);
+    }
+
+    #[test]
+    fn aborted() {
+        let _guard = mock::lock();
+        let cef = mock::start();
+        let browser = mock::create_browser(TestClient::new());
+        let (id, main_frame) = (browser.identifier(), mock::main_frame_id(browser.identifier()).unwrap());
+        let mut first = browser.load_url("https://example.com/first");
+        let mut second = browser.load_url("https://example.com/second");
+        assert_eq!(mock::poll(&mut first), Poll::Ready(Err(LoadError::Aborted)));
+        // The abort of the previous page, before the second navigation starts.
+        mock::load_error(id, main_frame, ERR_ABORTED, "net::ERR_ABORTED", "https://example.com/first");
+        assert_eq!(mock::poll(&mut second), Poll::Pending);
+        mock::load_start(id, main_frame, TT_EXPLICIT);
+        mock::load_error(id, main_frame, ERR_ABORTED, "net::ERR_ABORTED", "https://example.com/second");
+        assert_eq!(mock::poll(&mut second), Poll::Ready(Err(LoadError::Aborted)));
+
+        let mut failed = browser.load_url("https://unknown.com");
+        mock::load_error(id, main_frame, ERR_NAME_NOT_RESOLVED, "net::ERR_NAME_NOT_RESOLVED", "https://unknown.com/");
+        assert_eq!(mock::poll(&mut failed), Poll::Ready(Err(LoadError::Failed {
+            error: NetError::NAME_NOT_RESOLVED,
+            text: "net::ERR_NAME_NOT_RESOLVED".to_string(),
+            url: "https://unknown.com/".to_string(),
+        })));
+        drop((first, second, failed, browser));
+        drop(cef);
+        assert_eq!(mock::live_objects(), 0);
+    }
+
+    #[test]
+    fn closed() {
+        mock::run(|| {
+            let browser = mock::create_browser(TestClient::new());
+            let mut navigation = browser.load_url("https://example.com");
+            assert_eq!(mock::poll(&mut navigation), Poll::Pending);
+            browser.host().close_browser(false);
+            mock::run_pending_tasks();
+            assert_eq!(mock::poll(&mut navigation), Poll::Ready(Err(LoadError::Closed)));
+            assert!(locked(&PENDING).is_empty());
+        });
+    }
+
+    #[test]
+    fn default_timeout() {
+        mock::run(|| {
+            let browser = mock::create_browser(TestClient::new());
+            let mut navigation = browser.load_url("https://example.com");
+            mock::advance_time(29_999);
+            assert_eq!(mock::poll(&mut navigation), Poll::Pending);
+            mock::advance_time(1);
+            assert_eq!(mock::poll(&mut navigation), Poll::Ready(Err(LoadError::TimedOut)));
+
+            // Only the last timeout fires.
+            let mut navigation = browser.load_url("https://example.com").timeout(Duration::from_secs(60));
+            mock::advance_time(30_000);
+            assert_eq!(mock::poll(&mut navigation), Poll::Pending);
+            mock::advance_time(30_000);
+            assert_eq!(mock::poll(&mut navigation), Poll::Ready(Err(LoadError::TimedOut)));
+        });
+    }
+
+    #[test]
+    fn timeout() {
+        let _guard = mock::lock();
+        let cef = mock::start();
+        let browser = mock::create_browser(TestClient::new());
+        let mut navigation = browser.load_url("https://example.com").timeout(Duration::from_secs(1));
+        mock::advance_time(999);
+        assert_eq!(mock::poll(&mut navigation), Poll::Pending);
+        mock::advance_time(1);
+        assert_eq!(mock::poll(&mut navigation), Poll::Ready(Err(LoadError::TimedOut)));
+
+        // The timeout cannot be posted once CEF is shut down.
+        drop(cef);
+        let mut navigation = browser.load_url("https://example.com").timeout(Duration::from_secs(1));
+        assert_eq!(mock::poll(&mut navigation), Poll::Ready(Err(LoadError::PostTask)));
+        drop((navigation, browser));
+        assert_eq!(mock::live_objects(), 0);
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use sys::cef_thread_id_t;
+use task::post_delayed_task;
+
+pub(crate) fn locked<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+/// The result of a future resolved by the callbacks of CEF, and the task waiting for it.
+pub(crate) struct Pending<T> {
+    result: Option<T>,
+    finished: bool,
+    waker: Option<Waker>,
+    // Incremented by each timeout, so that only the last one fires.
+    timeout_generation: u32,
+}
+
+impl<T> Pending<T> {
+    pub(crate) fn new() -> Self {
+        Pending {
+            result: None,
+            finished: false,
+            waker: None,
+            timeout_generation: 0,
+        }
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Keep the first result only, and return the waker to call once the lock is released.
+    pub(crate) fn finish(&mut self, result: T) -> Option<Waker> {
+        if self.finished {
+            return None;
+        }
+        self.finished = true;
+        self.result = Some(result);
+        self.waker.take()
+    }
+
+    pub(crate) fn poll(&mut self, context: &mut Context) -> Poll<T> {
+        match self.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                self.waker = Some(context.waker().clone());
+                Poll::Pending
+            },
+        }
+    }
+}
+
+/// The state of a future, shared with the callbacks resolving it.
+pub(crate) trait PendingState: Send + Sized + 'static {
+    type Output: Send;
+    /// What `complete` does once the lock is released, like waking the task or closing a browser, whose
+    /// callbacks could lock the state again.
+    type Finished;
+
+    fn pending(&mut self) -> &mut Pending<Self::Output>;
+
+    fn finish(&mut self, result: Self::Output) -> Self::Finished;
+
+    fn complete(state: &Arc<Mutex<Self>>, finished: Self::Finished);
+}
+
+pub(crate) fn finish<S: PendingState>(state: &Arc<Mutex<S>>, result: S::Output) {
+    let finished = locked(state).finish(result);
+    S::complete(state, finished);
+}
+
+/// Finish `state` with `timed_out` after `timeout`, measured on the UI thread, unless another timeout is set in
+/// the meantime. It is finished with `not_posted` right away if the timeout cannot be posted, because CEF is not
+/// running.
+pub(crate) fn set_timeout<S: PendingState>(state: &Arc<Mutex<S>>, timeout: Duration, timed_out: S::Output,
+    not_posted: S::Output)
+{
+    let generation = {
+        let mut state = locked(state);
+        let pending = state.pending();
+        pending.timeout_generation += 1;
+        pending.timeout_generation
+    };
+    let task_state = state.clone();
+    let posted = post_delayed_task(cef_thread_id_t::TID_UI, timeout, move || {
+        let finished = {
+            let mut state = locked(&task_state);
+            if state.pending().timeout_generation != generation {
+                return;
+            }
+            state.finish(timed_out)
+        };
+        S::complete(&task_state, finished);
+    });
+    if !posted {
+        finish(state, not_posted);
+    }
+}
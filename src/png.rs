@@ -0,0 +1,430 @@
+/*
+ * A small PNG encoder, for the images of `Browser::capture`.
+ *
+ * Each row is filtered with the filter giving the smallest sum of absolute differences, as advised by the PNG
+ * specification, and the result is compressed in a single deflate block with the fixed Huffman codes and a hash
+ * chain to find the repeated sequences.
+ */
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+const WINDOW_SIZE: usize = 32768;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+// The number of previous positions compared for each sequence.
+const MAX_CHAIN: usize = 32;
+const HASH_BITS: u32 = 15;
+
+const LENGTH_BASES: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+const LENGTH_EXTRA_BITS: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASES: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145,
+    8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA_BITS: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+
+/// Encode an RGBA image of `width * height * 4` bytes, without premultiplied alpha, as a PNG file. PNG does not
+/// allow empty images.
+pub fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+    assert!(width > 0 && height > 0, "empty image");
+    assert_eq!(rgba.len(), width as usize * height as usize * 4, "size of the RGBA image");
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // 8 bits per channel, RGBA, deflate, adaptive filtering, no interlacing.
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut png = SIGNATURE.to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib_compress(&filter_rows(width as usize * 4, rgba)));
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_chunk(png: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(chunk_type);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut table = [0u32; 256];
+    for (index, entry) in table.iter_mut().enumerate() {
+        let mut value = index as u32;
+        for _ in 0..8 {
+            value = if value & 1 != 0 { 0xedb8_8320 ^ (value >> 1) } else { value >> 1 };
+        }
+        *entry = value;
+    }
+    !bytes.iter().fold(!0, |crc, &byte| table[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8))
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    // The sums cannot overflow before 5552 bytes.
+    for block in bytes.chunks(5552) {
+        for &byte in block {
+            a += byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+fn paeth(left: u8, up: u8, up_left: u8) -> u8 {
+    let estimate = left as i16 + up as i16 - up_left as i16;
+    let (to_left, to_up, to_up_left) =
+        ((estimate - left as i16).abs(), (estimate - up as i16).abs(), (estimate - up_left as i16).abs());
+    if to_left <= to_up && to_left <= to_up_left {
+        left
+    }
+    else if to_up <= to_up_left {
+        up
+    }
+    else {
+        up_left
+    }
+}
+
+// Prefix each row with the filter type which gives the smallest differences.
+fn filter_rows(stride: usize, pixels: &[u8]) -> Vec<u8> {
+    const BYTES_PER_PIXEL: usize = 4;
+    let mut filtered = Vec::with_capacity(pixels.len() + pixels.len() / stride);
+    let mut candidate = vec![0; stride];
+    let mut best = vec![0; stride];
+    let zero_row = vec![0; stride];
+    for (index, row) in pixels.chunks(stride).enumerate() {
+        let previous = if index == 0 { &zero_row[..] } else { &pixels[(index - 1) * stride..index * stride] };
+        let mut best_type = 0;
+        let mut best_cost = u64::MAX;
+        for filter_type in 0..5u8 {
+            for i in 0..stride {
+                let left = if i >= BYTES_PER_PIXEL { row[i - BYTES_PER_PIXEL] } else { 0 };
+                let up_left = if i >= BYTES_PER_PIXEL { previous[i - BYTES_PER_PIXEL] } else { 0 };
+                let prediction = match filter_type {
+                    0 => 0,
+                    1 => left,
+                    2 => previous[i],
+                    3 => ((left as u16 + previous[i] as u16) / 2) as u8,
+                    _ => paeth(left, previous[i], up_left),
+                };
+                candidate[i] = row[i].wrapping_sub(prediction);
+            }
+            let cost = candidate.iter().map(|&byte| (byte as i8).unsigned_abs() as u64).sum();
+            if cost < best_cost {
+                best_cost = cost;
+                best_type = filter_type;
+                best.copy_from_slice(&candidate);
+            }
+        }
+        filtered.push(best_type);
+        filtered.extend_from_slice(&best);
+    }
+    filtered
+}
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    count: u32,
+}
+
+impl BitWriter {
+    // The bits are packed starting from the least significant bit of each byte.
+    fn write(&mut self, value: u32, count: u32) {
+        self.buffer |= value << self.count;
+        self.count += count;
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    // The Huffman codes are packed starting from their most significant bit.
+    fn write_code(&mut self, code: u32, length: u32) {
+        self.write(code.reverse_bits() >> (32 - length), length);
+    }
+
+    fn write_literal(&mut self, symbol: u16) {
+        let symbol = symbol as u32;
+        match symbol {
+            0..=143 => self.write_code(0x30 + symbol, 8),
+            144..=255 => self.write_code(0x190 + symbol - 144, 9),
+            256..=279 => self.write_code(symbol - 256, 7),
+            _ => self.write_code(0xc0 + symbol - 280, 8),
+        }
+    }
+
+    fn write_match(&mut self, length: usize, distance: usize) {
+        let code = LENGTH_BASES.iter().rposition(|&base| base as usize <= length).expect("length code");
+        self.write_literal(257 + code as u16);
+        self.write((length - LENGTH_BASES[code] as usize) as u32, LENGTH_EXTRA_BITS[code] as u32);
+        let code = DISTANCE_BASES.iter().rposition(|&base| base as usize <= distance).expect("distance code");
+        self.write_code(code as u32, 5);
+        self.write((distance - DISTANCE_BASES[code] as usize) as u32, DISTANCE_EXTRA_BITS[code] as u32);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+fn hash(bytes: &[u8]) -> usize {
+    let value = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+    (value.wrapping_mul(2_654_435_761) >> (32 - HASH_BITS)) as usize
+}
+
+fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter {
+        // A 32K window, deflate, and the check bits of the header.
+        bytes: vec![0x78, 0x01],
+        buffer: 0,
+        count: 0,
+    };
+    // The final block, with the fixed Huffman codes.
+    writer.write(1, 1);
+    writer.write(1, 2);
+
+    // The last position of each hash, and the previous position with the same hash of each position.
+    let mut head = vec![usize::MAX; 1 << HASH_BITS];
+    let mut previous = vec![usize::MAX; WINDOW_SIZE];
+
+    let mut position = 0;
+    while position < data.len() {
+        let mut best_length = 0;
+        let mut best_distance = 0;
+        if position + MIN_MATCH <= data.len() {
+            let max_length = MAX_MATCH.min(data.len() - position);
+            let mut candidate = head[hash(&data[position..])];
+            let mut chain = 0;
+            while candidate != usize::MAX && position - candidate <= WINDOW_SIZE && chain < MAX_CHAIN {
+                let length = data[candidate..].iter()
+                    .zip(&data[position..position + max_length])
+                    .take_while(|&(a, b)| a == b)
+                    .count();
+                if length > best_length {
+                    best_length = length;
+                    best_distance = position - candidate;
+                    if length == max_length {
+                        break;
+                    }
+                }
+                let next = previous[candidate % WINDOW_SIZE];
+                // The older positions are overwritten when the window wraps around.
+                if next == usize::MAX || next >= candidate {
+                    break;
+                }
+                candidate = next;
+                chain += 1;
+            }
+        }
+        let advance =
+            if best_length >= MIN_MATCH {
+                writer.write_match(best_length, best_distance);
+                best_length
+            }
+            else {
+                writer.write_literal(data[position] as u16);
+                1
+            };
+        for inserted in position..position + advance {
+            if inserted + MIN_MATCH <= data.len() {
+                let hash = hash(&data[inserted..]);
+                previous[inserted % WINDOW_SIZE] = head[hash];
+                head[hash] = inserted;
+            }
+        }
+        position += advance;
+    }
+    writer.write_literal(256);
+
+    let mut bytes = writer.finish();
+    bytes.extend_from_slice(&adler32(data).to_be_bytes());
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct BitReader<'a> {
+        bytes: &'a [u8],
+        position: usize,
+    }
+
+    impl<'a> BitReader<'a> {
+        fn bits(&mut self, count: u32) -> u32 {
+            let mut value = 0;
+            for index in 0..count {
+                let bit = (self.bytes[self.position / 8] >> (self.position % 8)) & 1;
+                value |= (bit as u32) << index;
+                self.position += 1;
+            }
+            value
+        }
+
+        fn code(&mut self, length: u32) -> u32 {
+            (0..length).fold(0, |code, _| code << 1 | self.bits(1))
+        }
+
+        fn literal(&mut self) -> u16 {
+            let code = self.code(7);
+            if code <= 23 {
+                return 256 + code as u16;
+            }
+            let code = code << 1 | self.bits(1);
+            match code {
+                0x30..=0xbf => (code - 0x30) as u16,
+                0xc0..=0xc7 => (280 + code - 0xc0) as u16,
+                _ => (144 + (code << 1 | self.bits(1)) - 0x190) as u16,
+            }
+        }
+    }
+
+    // Inflate the single block with fixed Huffman codes written by the encoder.
+    fn zlib_decompress(bytes: &[u8]) -> Vec<u8> {
+        assert_eq!(&bytes[..2], &[0x78, 0x01]);
+        assert_eq!((bytes[0] as u16 * 256 + bytes[1] as u16) % 31, 0, "check bits");
+        let mut reader = BitReader {
+            bytes: &bytes[2..bytes.len() - 4],
+            position: 0,
+        };
+        assert_eq!(reader.bits(3), 0b011, "final block with fixed codes");
+        let mut data = vec![];
+        loop {
+            let symbol = reader.literal();
+            match symbol {
+                0..=255 => data.push(symbol as u8),
+                256 => break,
+                _ => {
+                    let code = (symbol - 257) as usize;
+                    let length = LENGTH_BASES[code] as usize + reader.bits(LENGTH_EXTRA_BITS[code] as u32) as usize;
+                    let code = reader.code(5) as usize;
+                    let distance =
+                        DISTANCE_BASES[code] as usize + reader.bits(DISTANCE_EXTRA_BITS[code] as u32) as usize;
+                    assert!(distance <= data.len() && distance <= WINDOW_SIZE);
+                    for _ in 0..length {
+                        data.push(data[data.len() - distance]);
+                    }
+                },
+            }
+        }
+        assert_eq!(&bytes[bytes.len() - 4..], &adler32(&data).to_be_bytes());
+        data
+    }
+
+    fn unfilter_rows(stride: usize, filtered: &[u8]) -> Vec<u8> {
+        let mut pixels: Vec<u8> = Vec::with_capacity(filtered.len());
+        for (index, row) in filtered.chunks(stride + 1).enumerate() {
+            assert_eq!(row.len(), stride + 1);
+            let start = pixels.len();
+            for i in 0..stride {
+                let left = if i >= 4 { pixels[start + i - 4] } else { 0 };
+                let up = if index > 0 { pixels[start - stride + i] } else { 0 };
+                let up_left = if index > 0 && i >= 4 { pixels[start - stride + i - 4] } else { 0 };
+                let prediction = match row[0] {
+                    0 => 0,
+                    1 => left,
+                    2 => up,
+                    3 => ((left as u16 + up as u16) / 2) as u8,
+                    4 => paeth(left, up, up_left),
+                    filter_type => panic!("filter type {}", filter_type),
+                };
+                pixels.push(row[i + 1].wrapping_add(prediction));
+            }
+        }
+        pixels
+    }
+
+    // Decode a PNG file written by the encoder, checking the CRC of its chunks.
+    fn decode_png(png: &[u8]) -> (u32, u32, Vec<u8>) {
+        assert_eq!(&png[..8], &SIGNATURE);
+        let mut chunks = vec![];
+        let mut position = 8;
+        while position < png.len() {
+            let length = u32::from_be_bytes([png[position], png[position + 1], png[position + 2], png[position + 3]]);
+            let end = position + 8 + length as usize;
+            let crc = u32::from_be_bytes([png[end], png[end + 1], png[end + 2], png[end + 3]]);
+            assert_eq!(crc32(&png[position + 4..end]), crc, "CRC of the chunk");
+            chunks.push((&png[position + 4..position + 8], &png[position + 8..end]));
+            position = end + 4;
+        }
+        let types: Vec<_> = chunks.iter().map(|&(chunk_type, _)| chunk_type).collect();
+        assert_eq!(types, vec![b"IHDR", b"IDAT", b"IEND"]);
+        let header = chunks[0].1;
+        let width = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+        let height = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+        assert_eq!(&header[8..], &[8, 6, 0, 0, 0]);
+        let pixels = unfilter_rows(width as usize * 4, &zlib_decompress(chunks[1].1));
+        (width, height, pixels)
+    }
+
+    fn round_trip(width: u32, height: u32, rgba: &[u8]) {
+        assert_eq!(decode_png(&encode_png(width, height, rgba)), (width, height, rgba.to_vec()));
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        // Long enough for the sums to be reduced.
+        assert_eq!(adler32(&[255; 10000]), 0xb623_eb2b);
+    }
+
+    #[test]
+    fn round_trips() {
+        round_trip(1, 1, &[1, 2, 3, 4]);
+        round_trip(1, 1, &[0, 0, 0, 0]);
+
+        // A gradient, a repeated pattern and noise, for every filter type and for long matches.
+        let (width, height) = (64, 48);
+        let mut rgba = Vec::with_capacity(width * height * 4);
+        let mut noise = 1u32;
+        for y in 0..height {
+            for x in 0..width {
+                noise = noise.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                let pixel = match y / 16 {
+                    0 => [x as u8 * 4, y as u8 * 5, 128, 255],
+                    1 => [(x % 3) as u8 * 100, 0, 255, 128],
+                    _ => [(noise >> 24) as u8, (noise >> 16) as u8, (noise >> 8) as u8, 255],
+                };
+                rgba.extend_from_slice(&pixel);
+            }
+        }
+        round_trip(width as u32, height as u32, &rgba);
+    }
+
+    #[test]
+    fn long_rows() {
+        // Rows of 80000 bytes, longer than a stored block and than the window.
+        let width = 20000;
+        let mut rgba = vec![0; width * 2 * 4];
+        for (index, byte) in rgba.iter_mut().enumerate() {
+            *byte = (index % 251) as u8 ^ (index / 4000) as u8;
+        }
+        round_trip(width as u32, 2, &rgba);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty image")]
+    fn empty_image() {
+        encode_png(0, 0, &[]);
+    }
+}
@@ -0,0 +1,147 @@
+use rc::release_base;
+use string::CefString;
+use sys::{
+    cef_base_ref_counted_t,
+    cef_list_value_create,
+    cef_list_value_t,
+    cef_process_id_t,
+    cef_process_message_create,
+    cef_process_message_t,
+    cef_value_type_t,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessId {
+    Browser,
+    Renderer,
+}
+
+impl ProcessId {
+    pub fn from_raw(process_id: cef_process_id_t) -> Self {
+        match process_id {
+            cef_process_id_t::PID_BROWSER => ProcessId::Browser,
+            cef_process_id_t::PID_RENDERER => ProcessId::Renderer,
+        }
+    }
+
+    pub fn to_raw(self) -> cef_process_id_t {
+        match self {
+            ProcessId::Browser => cef_process_id_t::PID_BROWSER,
+            ProcessId::Renderer => cef_process_id_t::PID_RENDERER,
+        }
+    }
+}
+
+/// An argument of a process message.
+///
+/// The binary and dictionary values are not supported and are read as `Null`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i32),
+    Double(f64),
+    String(String),
+    List(Vec<Value>),
+}
+
+/// A message sent between the browser process and a render process, copied out of the CEF object.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProcessMessage {
+    pub name: String,
+    pub arguments: Vec<Value>,
+}
+
+impl ProcessMessage {
+    pub fn new(name: &str) -> Self {
+        ProcessMessage {
+            name: name.to_string(),
+            arguments: vec![],
+        }
+    }
+
+    pub fn with_argument(mut self, argument: Value) -> Self {
+        self.arguments.push(argument);
+        self
+    }
+
+    /// Copy a message received from CEF, without releasing it.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid message.
+    pub unsafe fn from_raw(raw: *mut cef_process_message_t) -> Self {
+        let name = CefString::from_userfree(call!(&*raw, get_name())).to_string();
+        let list = call!(&*raw, get_argument_list());
+        let arguments = read_list(list);
+        release(list);
+        ProcessMessage {
+            name,
+            arguments,
+        }
+    }
+
+    /// Create a CEF message, whose reference is owned by the caller.
+    pub fn to_raw(&self) -> *mut cef_process_message_t {
+        let name = CefString::new(&self.name);
+        unsafe {
+            let raw = cef_process_message_create(name.as_raw());
+            let list = call!(&*raw, get_argument_list());
+            write_list(list, &self.arguments);
+            release(list);
+            raw
+        }
+    }
+}
+
+unsafe fn release(list: *mut cef_list_value_t) {
+    if !list.is_null() {
+        release_base(list as *mut cef_base_ref_counted_t);
+    }
+}
+
+unsafe fn read_list(list: *mut cef_list_value_t) -> Vec<Value> {
+    if list.is_null() {
+        return vec![];
+    }
+    let list = &*list;
+    (0..call!(list, get_size()))
+        .map(|index| match call!(list, get_type(index)) {
+            cef_value_type_t::VTYPE_BOOL => Value::Bool(call!(list, get_bool(index)) != 0),
+            cef_value_type_t::VTYPE_INT => Value::Int(call!(list, get_int(index))),
+            cef_value_type_t::VTYPE_DOUBLE => Value::Double(call!(list, get_double(index))),
+            cef_value_type_t::VTYPE_STRING =>
+                Value::String(CefString::from_userfree(call!(list, get_string(index))).to_string()),
+            cef_value_type_t::VTYPE_LIST => {
+                let sublist = call!(list, get_list(index));
+                let values = read_list(sublist);
+                release(sublist);
+                Value::List(values)
+            },
+            _ => Value::Null,
+        })
+        .collect()
+}
+
+unsafe fn write_list(list: *mut cef_list_value_t, values: &[Value]) {
+    if list.is_null() {
+        return;
+    }
+    let list = &*list;
+    call!(list, set_size(values.len()));
+    for (index, value) in values.iter().enumerate() {
+        match *value {
+            Value::Null => call!(list, set_null(index)),
+            Value::Bool(value) => call!(list, set_bool(index, value as i32)),
+            Value::Int(value) => call!(list, set_int(index, value)),
+            Value::Double(value) => call!(list, set_double(index, value)),
+            Value::String(ref value) => call!(list, set_string(index, CefString::new(value).as_raw())),
+            Value::List(ref values) => {
+                let sublist = cef_list_value_create();
+                write_list(sublist, values);
+                // set_list takes the reference.
+                call!(list, set_list(index, sublist))
+            },
+        };
+    }
+}
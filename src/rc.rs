@@ -0,0 +1,321 @@
+use std::mem;
+use std::ops::Deref;
+use std::ptr::NonNull;
+use std::sync::atomic::{self, AtomicUsize, Ordering};
+
+use sys::*;
+
+/// Implemented by the C structs that start with a `cef_base_ref_counted_t`, directly or through their parent
+/// struct.
+///
+/// # Safety
+///
+/// The pointers to these structs are cast to pointers to their base.
+pub unsafe trait CefRefCounted {}
+
+macro_rules! ref_counted {
+    ($($name:ident),* $(,)*) => {
+        $(unsafe impl CefRefCounted for $name {})*
+    };
+}
+
+ref_counted!(
+    cef_app_t,
+    cef_before_download_callback_t,
+    cef_box_layout_t,
+    cef_browser_host_t,
+    cef_browser_process_handler_t,
+    cef_browser_t,
+    cef_browser_view_delegate_t,
+    cef_browser_view_t,
+    cef_client_t,
+    cef_command_line_t,
+    cef_context_menu_handler_t,
+    cef_context_menu_params_t,
+    cef_dialog_handler_t,
+    cef_display_handler_t,
+    cef_download_handler_t,
+    cef_download_item_callback_t,
+    cef_download_item_t,
+    cef_drag_data_t,
+    cef_drag_handler_t,
+    cef_file_dialog_callback_t,
+    cef_fill_layout_t,
+    cef_find_handler_t,
+    cef_focus_handler_t,
+    cef_frame_t,
+    cef_jsdialog_callback_t,
+    cef_jsdialog_handler_t,
+    cef_keyboard_handler_t,
+    cef_layout_t,
+    cef_life_span_handler_t,
+    cef_list_value_t,
+    cef_load_handler_t,
+    cef_menu_model_t,
+    cef_panel_delegate_t,
+    cef_panel_t,
+    cef_print_handler_t,
+    cef_process_message_t,
+    cef_render_handler_t,
+    cef_render_process_handler_t,
+    cef_request_context_t,
+    cef_resource_bundle_handler_t,
+    cef_task_t,
+    cef_v8context_t,
+    cef_v8value_t,
+    cef_view_delegate_t,
+    cef_view_t,
+    cef_window_delegate_t,
+    cef_window_t,
+);
+
+/// A C struct of callbacks allocated together with the Rust value used by these callbacks.
+///
+/// The base of the C struct is filled by `wrap`, so the callbacks of CEF objects implemented in Rust are never
+/// left null, and the value is dropped when CEF releases the last reference.
+#[repr(C)]
+pub struct RefCounted<C, T> {
+    object: C,
+    count: AtomicUsize,
+    value: T,
+}
+
+impl<C: CefRefCounted, T: Send + Sync> RefCounted<C, T> {
+    /// Allocate `object` with a reference count of 1, which is owned by the caller.
+    ///
+    /// Passing the returned pointer to a CEF function or returning it from a callback gives this reference to
+    /// CEF. `value` must be `Send + Sync`, since CEF can use the object and drop it on any of its threads.
+    pub fn wrap(object: C, value: T) -> *mut C {
+        let this = Box::into_raw(Box::new(RefCounted {
+            object,
+            count: AtomicUsize::new(1),
+            value,
+        }));
+        #[cfg(test)]
+        ::mock::object_created();
+        unsafe {
+            let base = this as *mut cef_base_ref_counted_t;
+            (*base).size = mem::size_of::<C>();
+            (*base).add_ref = Some(add_ref::<C, T>);
+            (*base).release = Some(release::<C, T>);
+            (*base).has_one_ref = Some(has_one_ref::<C, T>);
+            (*base).has_at_least_one_ref = Some(has_at_least_one_ref::<C, T>);
+        }
+        this as *mut C
+    }
+
+    /// Get the Rust value of an object received as the `self` argument of a callback.
+    ///
+    /// # Safety
+    ///
+    /// `object` must have been created by `RefCounted::<C, T>::wrap` and must still be alive.
+    pub unsafe fn value<'a>(object: *mut C) -> &'a T {
+        &(*(object as *mut Self)).value
+    }
+
+    /// Take a new reference on `object`.
+    ///
+    /// # Safety
+    ///
+    /// `object` must have been created by `RefCounted::<C, T>::wrap` and must still be alive.
+    pub unsafe fn add_ref(object: *mut C) -> *mut C {
+        add_ref::<C, T>(object as *mut cef_base_ref_counted_t);
+        object
+    }
+
+    /// Release a reference on `object`, dropping it if it was the last one.
+    ///
+    /// # Safety
+    ///
+    /// `object` must have been created by `RefCounted::<C, T>::wrap` and the caller must own a reference.
+    pub unsafe fn release(object: *mut C) {
+        release::<C, T>(object as *mut cef_base_ref_counted_t);
+    }
+}
+
+extern "C" fn add_ref<C, T>(base: *mut cef_base_ref_counted_t) {
+    let this = base as *mut RefCounted<C, T>;
+    unsafe {
+        (*this).count.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+extern "C" fn release<C, T>(base: *mut cef_base_ref_counted_t) -> i32 {
+    let this = base as *mut RefCounted<C, T>;
+    unsafe {
+        if (*this).count.fetch_sub(1, Ordering::Release) != 1 {
+            return 0;
+        }
+        atomic::fence(Ordering::Acquire);
+        drop(Box::from_raw(this));
+    }
+    #[cfg(test)]
+    ::mock::object_destroyed();
+    1
+}
+
+extern "C" fn has_one_ref<C, T>(base: *mut cef_base_ref_counted_t) -> i32 {
+    let this = base as *mut RefCounted<C, T>;
+    unsafe {
+        ((*this).count.load(Ordering::Acquire) == 1) as i32
+    }
+}
+
+extern "C" fn has_at_least_one_ref<C, T>(base: *mut cef_base_ref_counted_t) -> i32 {
+    let this = base as *mut RefCounted<C, T>;
+    unsafe {
+        ((*this).count.load(Ordering::Acquire) >= 1) as i32
+    }
+}
+
+/// Implemented by the safe wrappers of the objects implemented by CEF.
+///
+/// # Safety
+///
+/// A wrapper must have the same layout as its C struct, so that a pointer to one is a pointer to the other.
+pub unsafe trait CefObject: Sized {
+    type Raw: CefRefCounted;
+
+    fn as_raw(&self) -> *mut Self::Raw {
+        self as *const Self as *mut Self::Raw
+    }
+
+    /// Get a new reference, to pass this object as an argument to CEF, which releases it.
+    fn to_raw(&self) -> *mut Self::Raw {
+        let raw = self.as_raw();
+        unsafe {
+            add_base_ref(raw as *mut cef_base_ref_counted_t);
+        }
+        raw
+    }
+
+    fn to_rc(&self) -> CefRc<Self> {
+        CefRc {
+            ptr: unsafe { NonNull::new_unchecked(self.to_raw() as *mut Self) },
+        }
+    }
+}
+
+pub(crate) unsafe fn add_base_ref(base: *mut cef_base_ref_counted_t) {
+    if let Some(add_ref) = (*base).add_ref {
+        add_ref(base);
+    }
+}
+
+pub(crate) unsafe fn release_base(base: *mut cef_base_ref_counted_t) {
+    if let Some(release) = (*base).release {
+        release(base);
+    }
+}
+
+/// An owned reference to an object implemented by CEF, released on drop.
+///
+/// CEF gives a reference with the objects it returns and with the arguments of the handlers, except `self`;
+/// both are taken with `from_raw`.
+pub struct CefRc<T: CefObject> {
+    ptr: NonNull<T>,
+}
+
+unsafe impl<T: CefObject> Send for CefRc<T> {}
+unsafe impl<T: CefObject> Sync for CefRc<T> {}
+
+impl<T: CefObject> CefRc<T> {
+    /// Take ownership of a reference. Returns `None` for a null pointer.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be null or a valid object whose reference is owned by the caller.
+    pub unsafe fn from_raw(raw: *mut T::Raw) -> Option<Self> {
+        NonNull::new(raw as *mut T)
+            .map(|ptr| CefRc {
+                ptr,
+            })
+    }
+
+    /// Take a new reference on an object borrowed from CEF.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be null or a valid object.
+    pub unsafe fn from_raw_add_ref(raw: *mut T::Raw) -> Option<Self> {
+        if !raw.is_null() {
+            add_base_ref(raw as *mut cef_base_ref_counted_t);
+        }
+        Self::from_raw(raw)
+    }
+
+    /// Give the reference to the caller.
+    pub fn into_raw(self) -> *mut T::Raw {
+        let raw = self.ptr.as_ptr() as *mut T::Raw;
+        mem::forget(self);
+        raw
+    }
+}
+
+impl<T: CefObject> Clone for CefRc<T> {
+    fn clone(&self) -> Self {
+        (**self).to_rc()
+    }
+}
+
+impl<T: CefObject> Deref for CefRc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T: CefObject> Drop for CefRc<T> {
+    fn drop(&mut self) {
+        unsafe {
+            release_base(self.ptr.as_ptr() as *mut cef_base_ref_counted_t);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+    use std::sync::Arc;
+
+    use browser::Browser;
+    use mock::{self, TestClient};
+    use super::*;
+
+    #[test]
+    fn ref_counted() {
+        let _guard = mock::lock();
+        let value = Arc::new(());
+        let task = RefCounted::wrap(unsafe { mem::zeroed::<cef_task_t>() }, value.clone());
+        let base = task as *mut cef_base_ref_counted_t;
+        unsafe {
+            assert_eq!((*base).size, mem::size_of::<cef_task_t>());
+            assert!(Arc::ptr_eq(RefCounted::<cef_task_t, Arc<()>>::value(task), &value));
+            RefCounted::<cef_task_t, Arc<()>>::add_ref(task);
+            assert_eq!((*base).has_one_ref.unwrap()(base), 0);
+            assert_eq!((*base).release.unwrap()(base), 0);
+            assert_eq!((*base).has_one_ref.unwrap()(base), 1);
+            assert_eq!(Arc::strong_count(&value), 2);
+            assert_eq!(mock::live_objects(), 1);
+            RefCounted::<cef_task_t, Arc<()>>::release(task);
+        }
+        assert_eq!(Arc::strong_count(&value), 1);
+        assert_eq!(mock::live_objects(), 0);
+    }
+
+    #[test]
+    fn cef_rc() {
+        let _guard = mock::lock();
+        let cef = mock::start();
+        let browser = mock::create_browser(TestClient::new());
+        let clone = browser.clone();
+        assert!(clone.is_same(&browser));
+        let clone = unsafe { CefRc::<Browser>::from_raw(clone.into_raw()) }.unwrap();
+        assert!(clone.host().browser().is_same(&browser));
+        assert!(unsafe { CefRc::<Browser>::from_raw(ptr::null_mut()) }.is_none());
+        drop((clone, browser));
+        drop(cef);
+        assert_eq!(mock::live_objects(), 0);
+    }
+}
@@ -0,0 +1,455 @@
+use std::mem;
+use std::os::raw::c_void;
+use std::slice;
+use std::sync::Arc;
+
+use browser::Browser;
+use geometry::{Point, Rect};
+use rc::{CefRc, RefCounted};
+use sys::*;
+
+macro_rules! cursor_types {
+    ($($name:ident = $raw:ident,)*) => {
+        /// The cursor to show over a windowless browser.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum CursorType {
+            $($name,)*
+        }
+
+        impl CursorType {
+            pub fn from_raw(cursor_type: cef_cursor_type_t) -> Self {
+                match cursor_type {
+                    $(cef_cursor_type_t::$raw => CursorType::$name,)*
+                }
+            }
+
+            pub fn to_raw(self) -> cef_cursor_type_t {
+                match self {
+                    $(CursorType::$name => cef_cursor_type_t::$raw,)*
+                }
+            }
+        }
+    };
+}
+
+cursor_types! {
+    Pointer = CT_POINTER,
+    Cross = CT_CROSS,
+    Hand = CT_HAND,
+    IBeam = CT_IBEAM,
+    Wait = CT_WAIT,
+    Help = CT_HELP,
+    EastResize = CT_EASTRESIZE,
+    NorthResize = CT_NORTHRESIZE,
+    NorthEastResize = CT_NORTHEASTRESIZE,
+    NorthWestResize = CT_NORTHWESTRESIZE,
+    SouthResize = CT_SOUTHRESIZE,
+    SouthEastResize = CT_SOUTHEASTRESIZE,
+    SouthWestResize = CT_SOUTHWESTRESIZE,
+    WestResize = CT_WESTRESIZE,
+    NorthSouthResize = CT_NORTHSOUTHRESIZE,
+    EastWestResize = CT_EASTWESTRESIZE,
+    NorthEastSouthWestResize = CT_NORTHEASTSOUTHWESTRESIZE,
+    NorthWestSouthEastResize = CT_NORTHWESTSOUTHEASTRESIZE,
+    ColumnResize = CT_COLUMNRESIZE,
+    RowResize = CT_ROWRESIZE,
+    MiddlePanning = CT_MIDDLEPANNING,
+    EastPanning = CT_EASTPANNING,
+    NorthPanning = CT_NORTHPANNING,
+    NorthEastPanning = CT_NORTHEASTPANNING,
+    NorthWestPanning = CT_NORTHWESTPANNING,
+    SouthPanning = CT_SOUTHPANNING,
+    SouthEastPanning = CT_SOUTHEASTPANNING,
+    SouthWestPanning = CT_SOUTHWESTPANNING,
+    WestPanning = CT_WESTPANNING,
+    Move = CT_MOVE,
+    VerticalText = CT_VERTICALTEXT,
+    Cell = CT_CELL,
+    ContextMenu = CT_CONTEXTMENU,
+    Alias = CT_ALIAS,
+    Progress = CT_PROGRESS,
+    NoDrop = CT_NODROP,
+    Copy = CT_COPY,
+    None = CT_NONE,
+    NotAllowed = CT_NOTALLOWED,
+    ZoomIn = CT_ZOOMIN,
+    ZoomOut = CT_ZOOMOUT,
+    Grab = CT_GRAB,
+    Grabbing = CT_GRABBING,
+    Custom = CT_CUSTOM,
+}
+
+/// The part of a windowless browser which is painted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PaintElementType {
+    /// The page.
+    View,
+    /// A popup widget drawn over the page, like the list of a `<select>`.
+    Popup,
+}
+
+impl PaintElementType {
+    pub fn from_raw(element_type: cef_paint_element_type_t) -> Self {
+        match element_type {
+            cef_paint_element_type_t::PET_VIEW => PaintElementType::View,
+            cef_paint_element_type_t::PET_POPUP => PaintElementType::Popup,
+        }
+    }
+
+    pub fn to_raw(self) -> cef_paint_element_type_t {
+        match self {
+            PaintElementType::View => cef_paint_element_type_t::PET_VIEW,
+            PaintElementType::Popup => cef_paint_element_type_t::PET_POPUP,
+        }
+    }
+}
+
+/// The screen on which a windowless browser is shown.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScreenInfo {
+    /// The number of pixels painted per view unit, like 2.0 on a high DPI screen.
+    pub device_scale_factor: f32,
+    /// The number of bits per pixel.
+    pub depth: i32,
+    pub depth_per_component: i32,
+    pub is_monochrome: bool,
+    /// The bounds of the screen, in screen coordinates. When empty, the view rectangle is used.
+    pub rect: Rect,
+    /// The bounds of the screen without the task bars and the docks.
+    pub available_rect: Rect,
+}
+
+impl Default for ScreenInfo {
+    /// A 24 bits screen with a scale factor of 1.
+    fn default() -> Self {
+        ScreenInfo {
+            device_scale_factor: 1.0,
+            depth: 24,
+            depth_per_component: 8,
+            is_monochrome: false,
+            rect: Rect::default(),
+            available_rect: Rect::default(),
+        }
+    }
+}
+
+impl ScreenInfo {
+    pub fn from_raw(raw: &cef_screen_info_t) -> Self {
+        ScreenInfo {
+            device_scale_factor: raw.device_scale_factor,
+            depth: raw.depth,
+            depth_per_component: raw.depth_per_component,
+            is_monochrome: raw.is_monochrome != 0,
+            rect: Rect::from_raw(&raw.rect),
+            available_rect: Rect::from_raw(&raw.available_rect),
+        }
+    }
+
+    pub fn to_raw(&self) -> cef_screen_info_t {
+        cef_screen_info_t {
+            device_scale_factor: self.device_scale_factor,
+            depth: self.depth,
+            depth_per_component: self.depth_per_component,
+            is_monochrome: self.is_monochrome as i32,
+            rect: self.rect.to_raw(),
+            available_rect: self.available_rect.to_raw(),
+        }
+    }
+}
+
+/// The image of a `CursorType::Custom` cursor, which CEF only keeps during the callback.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CustomCursor<'a> {
+    pub hotspot: Point,
+    pub image_scale_factor: f32,
+    /// A BGRA image of `width * height * 4` bytes, whose origin is its top-left corner.
+    pub buffer: &'a [u8],
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Called to paint a windowless browser, created with `WindowInfo::windowless`, on the UI thread.
+///
+/// The windowless rendering must also be enabled in the `Settings`.
+pub trait RenderHandler: Send + Sync {
+    /// The bounds of the view, in screen coordinates, which CEF asks for before painting. Its size must not be
+    /// empty. `BrowserHost::was_resized` must be called when it changes.
+    fn get_view_rect(&self, browser: &Browser) -> Rect;
+
+    /// The bounds of the window containing the view, in screen coordinates, or `None` to use the view rectangle.
+    fn get_root_screen_rect(&self, _browser: &Browser) -> Option<Rect> {
+        None
+    }
+
+    /// Convert a point of the view to screen coordinates, or return `None` if it cannot be converted.
+    fn get_screen_point(&self, _browser: &Browser, _view_point: Point) -> Option<Point> {
+        None
+    }
+
+    /// Describe the screen, or return `None` to use the default values. `BrowserHost::notify_screen_info_changed`
+    /// must be called when it changes.
+    fn get_screen_info(&self, _browser: &Browser) -> Option<ScreenInfo> {
+        None
+    }
+
+    /// Called when a popup widget is shown or hidden.
+    fn on_popup_show(&self, _browser: &Browser, _show: bool) {
+    }
+
+    /// Called with the bounds of a popup widget, in view coordinates, before it is painted.
+    fn on_popup_size(&self, _browser: &Browser, _rect: Rect) {
+    }
+
+    /// Called with the whole image of the view or of a popup widget, in which only the `dirty_rects` changed.
+    ///
+    /// `buffer` is a BGRA image of `width * height * 4` bytes, whose origin is its top-left corner. Its size is
+    /// in pixels, which is the size of the view multiplied by the device scale factor.
+    fn on_paint(&self, _browser: &Browser, _element_type: PaintElementType, _dirty_rects: &[Rect],
+        _buffer: &[u8], _width: i32, _height: i32)
+    {
+    }
+
+    /// Called when the cursor over the view changes. `custom` is the image of a `CursorType::Custom` cursor.
+    fn on_cursor_change(&self, _browser: &Browser, _cursor: cef_cursor_handle_t, _cursor_type: CursorType,
+        _custom: Option<&CustomCursor>)
+    {
+    }
+
+    /// Called when the page scrolls, with the new offset in view units.
+    fn on_scroll_offset_changed(&self, _browser: &Browser, _x: f64, _y: f64) {
+    }
+}
+
+type RenderHandlerRc = RefCounted<cef_render_handler_t, Arc<dyn RenderHandler>>;
+
+pub(crate) fn wrap_render_handler(handler: Arc<dyn RenderHandler>) -> *mut cef_render_handler_t {
+    extern "C" fn get_root_screen_rect(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        rect: *mut cef_rect_t) -> i32
+    {
+        let handler = unsafe { RenderHandlerRc::value(self_) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        match browser.and_then(|browser| handler.get_root_screen_rect(&browser)) {
+            Some(root_rect) if !rect.is_null() => {
+                unsafe { *rect = root_rect.to_raw() };
+                1
+            },
+            _ => 0,
+        }
+    }
+
+    extern "C" fn get_view_rect(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        rect: *mut cef_rect_t)
+    {
+        let handler = unsafe { RenderHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            let view_rect = handler.get_view_rect(&browser);
+            if !rect.is_null() {
+                unsafe { *rect = view_rect.to_raw() };
+            }
+        }
+    }
+
+    extern "C" fn get_screen_point(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t, view_x: i32,
+        view_y: i32, screen_x: *mut i32, screen_y: *mut i32) -> i32
+    {
+        let handler = unsafe { RenderHandlerRc::value(self_) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        let point = browser.and_then(|browser| handler.get_screen_point(&browser, Point::new(view_x, view_y)));
+        match point {
+            Some(point) if !screen_x.is_null() && !screen_y.is_null() => {
+                unsafe {
+                    *screen_x = point.x;
+                    *screen_y = point.y;
+                }
+                1
+            },
+            _ => 0,
+        }
+    }
+
+    extern "C" fn get_screen_info(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        screen_info: *mut cef_screen_info_t) -> i32
+    {
+        let handler = unsafe { RenderHandlerRc::value(self_) };
+        let browser = unsafe { CefRc::<Browser>::from_raw(browser) };
+        match browser.and_then(|browser| handler.get_screen_info(&browser)) {
+            Some(info) if !screen_info.is_null() => {
+                unsafe { *screen_info = info.to_raw() };
+                1
+            },
+            _ => 0,
+        }
+    }
+
+    extern "C" fn on_popup_show(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t, show: i32) {
+        let handler = unsafe { RenderHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            handler.on_popup_show(&browser, show != 0);
+        }
+    }
+
+    extern "C" fn on_popup_size(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        rect: *const cef_rect_t)
+    {
+        let handler = unsafe { RenderHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            if !rect.is_null() {
+                handler.on_popup_size(&browser, Rect::from_raw(unsafe { &*rect }));
+            }
+        }
+    }
+
+    extern "C" fn on_paint(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        type_: cef_paint_element_type_t, dirty_rects_count: usize, dirty_rects: *const cef_rect_t,
+        buffer: *const c_void, width: i32, height: i32)
+    {
+        let handler = unsafe { RenderHandlerRc::value(self_) };
+        let browser = match unsafe { CefRc::<Browser>::from_raw(browser) } {
+            Some(browser) => browser,
+            None => return,
+        };
+        if buffer.is_null() || width <= 0 || height <= 0 {
+            return;
+        }
+        let dirty_rects: Vec<_> =
+            if dirty_rects.is_null() {
+                vec![]
+            }
+            else {
+                unsafe { slice::from_raw_parts(dirty_rects, dirty_rects_count) }.iter().map(Rect::from_raw).collect()
+            };
+        let buffer = unsafe { slice::from_raw_parts(buffer as *const u8, width as usize * height as usize * 4) };
+        handler.on_paint(&browser, PaintElementType::from_raw(type_), &dirty_rects, buffer, width, height);
+    }
+
+    extern "C" fn on_cursor_change(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        cursor: cef_cursor_handle_t, type_: cef_cursor_type_t, custom_cursor_info: *const cef_cursor_info_t)
+    {
+        let handler = unsafe { RenderHandlerRc::value(self_) };
+        let browser = match unsafe { CefRc::<Browser>::from_raw(browser) } {
+            Some(browser) => browser,
+            None => return,
+        };
+        let cursor_type = CursorType::from_raw(type_);
+        let custom = unsafe { custom_cursor_info.as_ref() }
+            .filter(|info| cursor_type == CursorType::Custom && !info.buffer.is_null())
+            .map(|info| {
+                let (width, height) = (info.size.width.max(0), info.size.height.max(0));
+                CustomCursor {
+                    hotspot: Point::from_raw(&info.hotspot),
+                    image_scale_factor: info.image_scale_factor,
+                    buffer: unsafe {
+                        slice::from_raw_parts(info.buffer as *const u8, width as usize * height as usize * 4)
+                    },
+                    width,
+                    height,
+                }
+            });
+        handler.on_cursor_change(&browser, cursor, cursor_type, custom.as_ref());
+    }
+
+    extern "C" fn on_scroll_offset_changed(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t, x: f64,
+        y: f64)
+    {
+        let handler = unsafe { RenderHandlerRc::value(self_) };
+        if let Some(browser) = unsafe { CefRc::<Browser>::from_raw(browser) } {
+            handler.on_scroll_offset_changed(&browser, x, y);
+        }
+    }
+
+    let mut raw: cef_render_handler_t = unsafe { mem::zeroed() };
+    raw.get_root_screen_rect = Some(get_root_screen_rect);
+    raw.get_view_rect = Some(get_view_rect);
+    raw.get_screen_point = Some(get_screen_point);
+    raw.get_screen_info = Some(get_screen_info);
+    raw.on_popup_show = Some(on_popup_show);
+    raw.on_popup_size = Some(on_popup_size);
+    raw.on_paint = Some(on_paint);
+    raw.on_cursor_change = Some(on_cursor_change);
+    raw.on_scroll_offset_changed = Some(on_scroll_offset_changed);
+    RefCounted::wrap(raw, handler)
+}
+
+#[cfg(test)]
+mod tests {
+    use mock::{self, Recorder, TestClient};
+    use super::*;
+
+    // A view of 200x100 on a high DPI screen.
+    impl RenderHandler for Recorder {
+        fn get_view_rect(&self, _browser: &Browser) -> Rect {
+            Rect::new(0, 0, 200, 100)
+        }
+
+        fn get_screen_info(&self, _browser: &Browser) -> Option<ScreenInfo> {
+            Some(ScreenInfo {
+                device_scale_factor: 2.0,
+                ..ScreenInfo::default()
+            })
+        }
+
+        fn on_popup_show(&self, _browser: &Browser, show: bool) {
+            self.push(format!("popup show {}", show));
+        }
+
+        fn on_popup_size(&self, _browser: &Browser, rect: Rect) {
+            self.push(format!("popup size {:?}", rect));
+        }
+
+        fn on_paint(&self, _browser: &Browser, element_type: PaintElementType, dirty_rects: &[Rect], buffer: &[u8],
+            width: i32, height: i32)
+        {
+            self.push(format!("paint {:?} {:?} {}x{} {:?}", element_type, dirty_rects, width,
+                height, &buffer[..4]));
+        }
+
+        fn on_cursor_change(&self, _browser: &Browser, _cursor: cef_cursor_handle_t, cursor_type: CursorType,
+            custom: Option<&CustomCursor>)
+        {
+            self.push(format!("cursor {:?} {}", cursor_type, custom.is_some()));
+        }
+    }
+
+    #[test]
+    fn render_handler() {
+        mock::run(|| {
+            let recorder = Arc::new(Recorder::default());
+            let browser = mock::create_windowless_browser(TestClient::new().render(recorder.clone()));
+            let id = browser.identifier();
+            assert_eq!(mock::view_rect(id), Some(Rect::new(0, 0, 200, 100)));
+            assert_eq!(mock::screen_info(id).map(|info| info.device_scale_factor), Some(2.0));
+            let mut buffer = vec![0; 400 * 200 * 4];
+            buffer[..4].copy_from_slice(&[1, 2, 3, 255]);
+            mock::paint(id, PaintElementType::View, &[Rect::new(0, 0, 400, 200)], &buffer, 400, 200);
+            mock::popup_size(id, Rect::new(10, 20, 50, 40));
+            mock::popup_show(id, true);
+            mock::paint(id, PaintElementType::Popup, &[], &[9; 100 * 80 * 4], 100, 80);
+            mock::popup_show(id, false);
+            mock::cursor_change(id, CursorType::Hand);
+            assert_eq!(recorder.events(), [
+                "paint View [Rect { x: 0, y: 0, width: 400, height: 200 }] 400x200 [1, 2, 3, 255]",
+                "popup size Rect { x: 10, y: 20, width: 50, height: 40 }",
+                "popup show true",
+                "paint Popup [] 100x80 [9, 9, 9, 9]",
+                "popup show false",
+                "cursor Hand false",
+            ]);
+        });
+    }
+
+    #[test]
+    fn raw_values() {
+        let info = ScreenInfo {
+            device_scale_factor: 1.5,
+            depth: 30,
+            depth_per_component: 10,
+            is_monochrome: true,
+            rect: Rect::new(0, 0, 1920, 1080),
+            available_rect: Rect::new(0, 30, 1920, 1050),
+        };
+        assert_eq!(ScreenInfo::from_raw(&info.to_raw()), info);
+        for &cursor_type in &[CursorType::Pointer, CursorType::Grabbing, CursorType::Custom] {
+            assert_eq!(CursorType::from_raw(cursor_type.to_raw()), cursor_type);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use string::CefString;
+use sys::cef_scheme_registrar_t;
+
+/// How a custom scheme is treated by the browser. See `cef_scheme_capi.h` for the details of each option.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SchemeOptions {
+    /// Parsed like http: with a host and a path.
+    pub standard: bool,
+    /// Treated like file: URLs, only reachable from the same scheme.
+    pub local: bool,
+    /// Only displayable by pages of the same scheme.
+    pub display_isolated: bool,
+    /// Treated like https: URLs for mixed content.
+    pub secure: bool,
+    /// Allowed to do CORS requests.
+    pub cors_enabled: bool,
+    /// Bypass the Content-Security-Policy checks.
+    pub csp_bypassing: bool,
+    /// Allowed to do Fetch API requests.
+    pub fetch_enabled: bool,
+}
+
+/// Registers the custom schemes, in every process.
+pub struct SchemeRegistrar(cef_scheme_registrar_t);
+
+impl SchemeRegistrar {
+    pub(crate) unsafe fn from_raw<'a>(raw: *mut cef_scheme_registrar_t) -> &'a SchemeRegistrar {
+        &*(raw as *const SchemeRegistrar)
+    }
+
+    /// Returns false if the scheme cannot be registered, for instance if it was already registered.
+    pub fn add_custom_scheme(&self, name: &str, options: SchemeOptions) -> bool {
+        let name = CefString::new(name);
+        call!(&self.0, add_custom_scheme(name.as_raw(), options.standard as i32, options.local as i32,
+            options.display_isolated as i32, options.secure as i32, options.cors_enabled as i32,
+            options.csp_bypassing as i32, options.fetch_enabled as i32)) != 0
+    }
+}
@@ -0,0 +1,419 @@
+use std::error;
+use std::fmt;
+use std::mem;
+use std::path::{Path, PathBuf};
+
+use string::CefString;
+use sys::{cef_color_t, cef_log_severity_t, cef_settings_t, cef_string_t};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogSeverity {
+    #[default]
+    Default,
+    Verbose,
+    Info,
+    Warning,
+    Error,
+    Disable,
+}
+
+impl LogSeverity {
+    pub fn from_raw(severity: cef_log_severity_t) -> Self {
+        match severity {
+            cef_log_severity_t::LOGSEVERITY_DEFAULT => LogSeverity::Default,
+            cef_log_severity_t::LOGSEVERITY_VERBOSE => LogSeverity::Verbose,
+            cef_log_severity_t::LOGSEVERITY_INFO => LogSeverity::Info,
+            cef_log_severity_t::LOGSEVERITY_WARNING => LogSeverity::Warning,
+            cef_log_severity_t::LOGSEVERITY_ERROR => LogSeverity::Error,
+            cef_log_severity_t::LOGSEVERITY_DISABLE => LogSeverity::Disable,
+        }
+    }
+
+    pub fn to_raw(self) -> cef_log_severity_t {
+        match self {
+            LogSeverity::Default => cef_log_severity_t::LOGSEVERITY_DEFAULT,
+            LogSeverity::Verbose => cef_log_severity_t::LOGSEVERITY_VERBOSE,
+            LogSeverity::Info => cef_log_severity_t::LOGSEVERITY_INFO,
+            LogSeverity::Warning => cef_log_severity_t::LOGSEVERITY_WARNING,
+            LogSeverity::Error => cef_log_severity_t::LOGSEVERITY_ERROR,
+            LogSeverity::Disable => cef_log_severity_t::LOGSEVERITY_DISABLE,
+        }
+    }
+}
+
+/// An ARGB color.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Color {
+    pub alpha: u8,
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Color {
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self::argb(255, red, green, blue)
+    }
+
+    pub fn argb(alpha: u8, red: u8, green: u8, blue: u8) -> Self {
+        Color {
+            alpha,
+            red,
+            green,
+            blue,
+        }
+    }
+
+    pub fn from_raw(color: cef_color_t) -> Self {
+        Color {
+            alpha: (color >> 24) as u8,
+            red: (color >> 16) as u8,
+            green: (color >> 8) as u8,
+            blue: color as u8,
+        }
+    }
+
+    pub fn to_raw(self) -> cef_color_t {
+        (self.alpha as u32) << 24 | (self.red as u32) << 16 | (self.green as u32) << 8 | self.blue as u32
+    }
+}
+
+#[derive(Debug)]
+pub enum SettingsError {
+    /// CEF only accepts one way to run its message loop.
+    ConflictingMessageLoops,
+    /// CEF only allows ports from 1024 to 65535.
+    InvalidDebuggingPort(u16),
+    /// The path cannot be converted to a CEF string without losing characters.
+    NonUnicodePath(PathBuf),
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SettingsError::ConflictingMessageLoops =>
+                write!(formatter, "multi_threaded_message_loop cannot be used with external_message_pump"),
+            SettingsError::InvalidDebuggingPort(port) =>
+                write!(formatter, "remote debugging port {} is not between 1024 and 65535", port),
+            SettingsError::NonUnicodePath(ref path) =>
+                write!(formatter, "path {} is not valid unicode", path.display()),
+        }
+    }
+}
+
+impl error::Error for SettingsError {}
+
+/// The settings of `cef_initialize`, built by `SettingsBuilder`.
+///
+/// The raw struct points to the strings owned by this object, which must outlive the call to `cef_initialize`.
+pub struct Settings {
+    raw: cef_settings_t,
+    _strings: Vec<CefString>,
+}
+
+unsafe impl Send for Settings {}
+
+impl Settings {
+    pub fn builder() -> SettingsBuilder {
+        SettingsBuilder::default()
+    }
+
+    pub fn as_raw(&self) -> *const cef_settings_t {
+        &self.raw
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        SettingsBuilder::default().build()
+            .expect("default settings")
+    }
+}
+
+/// Every setting left unset keeps the default of CEF.
+#[derive(Clone, Debug, Default)]
+pub struct SettingsBuilder {
+    no_sandbox: bool,
+    browser_subprocess_path: Option<PathBuf>,
+    framework_dir_path: Option<PathBuf>,
+    multi_threaded_message_loop: bool,
+    external_message_pump: bool,
+    windowless_rendering_enabled: bool,
+    command_line_args_disabled: bool,
+    cache_path: Option<PathBuf>,
+    user_data_path: Option<PathBuf>,
+    persist_session_cookies: bool,
+    persist_user_preferences: bool,
+    user_agent: Option<String>,
+    product_version: Option<String>,
+    locale: Option<String>,
+    log_file: Option<PathBuf>,
+    log_severity: LogSeverity,
+    javascript_flags: Option<String>,
+    resources_dir_path: Option<PathBuf>,
+    locales_dir_path: Option<PathBuf>,
+    pack_loading_disabled: bool,
+    remote_debugging_port: Option<u16>,
+    uncaught_exception_stack_size: i32,
+    ignore_certificate_errors: bool,
+    enable_net_security_expiration: bool,
+    background_color: Option<Color>,
+    accept_language_list: Option<String>,
+}
+
+impl SettingsBuilder {
+    pub fn no_sandbox(mut self, no_sandbox: bool) -> Self {
+        self.no_sandbox = no_sandbox;
+        self
+    }
+
+    /// The executable launched for the subprocesses. Defaults to the current executable.
+    pub fn browser_subprocess_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.browser_subprocess_path = Some(path.into());
+        self
+    }
+
+    pub fn framework_dir_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.framework_dir_path = Some(path.into());
+        self
+    }
+
+    /// Run the browser message loop in a separate thread instead of calling `cef_run_message_loop`.
+    pub fn multi_threaded_message_loop(mut self, enabled: bool) -> Self {
+        self.multi_threaded_message_loop = enabled;
+        self
+    }
+
+    /// Integrate the browser message loop in an existing one with `cef_do_message_loop_work`.
+    pub fn external_message_pump(mut self, enabled: bool) -> Self {
+        self.external_message_pump = enabled;
+        self
+    }
+
+    pub fn windowless_rendering_enabled(mut self, enabled: bool) -> Self {
+        self.windowless_rendering_enabled = enabled;
+        self
+    }
+
+    pub fn command_line_args_disabled(mut self, disabled: bool) -> Self {
+        self.command_line_args_disabled = disabled;
+        self
+    }
+
+    /// Without a cache path, the browsers are in incognito mode.
+    pub fn cache_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.cache_path = Some(path.into());
+        self
+    }
+
+    pub fn user_data_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.user_data_path = Some(path.into());
+        self
+    }
+
+    pub fn persist_session_cookies(mut self, persist: bool) -> Self {
+        self.persist_session_cookies = persist;
+        self
+    }
+
+    pub fn persist_user_preferences(mut self, persist: bool) -> Self {
+        self.persist_user_preferences = persist;
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Used in the user agent when it is not set.
+    pub fn product_version(mut self, product_version: &str) -> Self {
+        self.product_version = Some(product_version.to_string());
+        self
+    }
+
+    /// Like `"en-US"`.
+    pub fn locale(mut self, locale: &str) -> Self {
+        self.locale = Some(locale.to_string());
+        self
+    }
+
+    pub fn log_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.log_file = Some(path.into());
+        self
+    }
+
+    pub fn log_severity(mut self, severity: LogSeverity) -> Self {
+        self.log_severity = severity;
+        self
+    }
+
+    pub fn javascript_flags(mut self, flags: &str) -> Self {
+        self.javascript_flags = Some(flags.to_string());
+        self
+    }
+
+    pub fn resources_dir_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.resources_dir_path = Some(path.into());
+        self
+    }
+
+    pub fn locales_dir_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.locales_dir_path = Some(path.into());
+        self
+    }
+
+    pub fn pack_loading_disabled(mut self, disabled: bool) -> Self {
+        self.pack_loading_disabled = disabled;
+        self
+    }
+
+    /// `None` disables remote debugging.
+    pub fn remote_debugging_port(mut self, port: Option<u16>) -> Self {
+        self.remote_debugging_port = port;
+        self
+    }
+
+    /// The number of stack frames of the uncaught exceptions given to the render process handler. 0 disables
+    /// them.
+    pub fn uncaught_exception_stack_size(mut self, size: i32) -> Self {
+        self.uncaught_exception_stack_size = size;
+        self
+    }
+
+    pub fn ignore_certificate_errors(mut self, ignore: bool) -> Self {
+        self.ignore_certificate_errors = ignore;
+        self
+    }
+
+    pub fn enable_net_security_expiration(mut self, enabled: bool) -> Self {
+        self.enable_net_security_expiration = enabled;
+        self
+    }
+
+    /// The color shown before a page is loaded. It must be opaque, except with windowless rendering.
+    pub fn background_color(mut self, color: Color) -> Self {
+        self.background_color = Some(color);
+        self
+    }
+
+    /// Like `"en-US,en"`.
+    pub fn accept_language_list(mut self, languages: &str) -> Self {
+        self.accept_language_list = Some(languages.to_string());
+        self
+    }
+
+    pub fn build(&self) -> Result<Settings, SettingsError> {
+        if self.multi_threaded_message_loop && self.external_message_pump {
+            return Err(SettingsError::ConflictingMessageLoops);
+        }
+        if let Some(port) = self.remote_debugging_port {
+            if port < 1024 {
+                return Err(SettingsError::InvalidDebuggingPort(port));
+            }
+        }
+
+        let mut strings = vec![];
+        let mut raw: cef_settings_t = unsafe { mem::zeroed() };
+        raw.size = mem::size_of::<cef_settings_t>();
+        raw.no_sandbox = self.no_sandbox as i32;
+        raw.browser_subprocess_path = path(&mut strings, &self.browser_subprocess_path)?;
+        raw.framework_dir_path = path(&mut strings, &self.framework_dir_path)?;
+        raw.multi_threaded_message_loop = self.multi_threaded_message_loop as i32;
+        raw.external_message_pump = self.external_message_pump as i32;
+        raw.windowless_rendering_enabled = self.windowless_rendering_enabled as i32;
+        raw.command_line_args_disabled = self.command_line_args_disabled as i32;
+        raw.cache_path = path(&mut strings, &self.cache_path)?;
+        raw.user_data_path = path(&mut strings, &self.user_data_path)?;
+        raw.persist_session_cookies = self.persist_session_cookies as i32;
+        raw.persist_user_preferences = self.persist_user_preferences as i32;
+        raw.user_agent = string(&mut strings, &self.user_agent);
+        raw.product_version = string(&mut strings, &self.product_version);
+        raw.locale = string(&mut strings, &self.locale);
+        raw.log_file = path(&mut strings, &self.log_file)?;
+        raw.log_severity = self.log_severity.to_raw();
+        raw.javascript_flags = string(&mut strings, &self.javascript_flags);
+        raw.resources_dir_path = path(&mut strings, &self.resources_dir_path)?;
+        raw.locales_dir_path = path(&mut strings, &self.locales_dir_path)?;
+        raw.pack_loading_disabled = self.pack_loading_disabled as i32;
+        raw.remote_debugging_port = self.remote_debugging_port.unwrap_or(0) as i32;
+        raw.uncaught_exception_stack_size = self.uncaught_exception_stack_size;
+        raw.ignore_certificate_errors = self.ignore_certificate_errors as i32;
+        raw.enable_net_security_expiration = self.enable_net_security_expiration as i32;
+        raw.background_color = self.background_color.map(Color::to_raw).unwrap_or(0);
+        raw.accept_language_list = string(&mut strings, &self.accept_language_list);
+        Ok(Settings {
+            raw,
+            _strings: strings,
+        })
+    }
+}
+
+/// Keep the string in `strings` and return a raw copy that borrows it.
+pub(crate) fn string(strings: &mut Vec<CefString>, value: &Option<String>) -> cef_string_t {
+    match *value {
+        Some(ref value) => {
+            let string = CefString::new(value);
+            let raw = string.to_borrowed_raw();
+            strings.push(string);
+            raw
+        },
+        None => unsafe { mem::zeroed() },
+    }
+}
+
+fn path(strings: &mut Vec<CefString>, value: &Option<PathBuf>) -> Result<cef_string_t, SettingsError> {
+    let value = match *value {
+        Some(ref value) => Some(path_to_string(value)?),
+        None => None,
+    };
+    Ok(string(strings, &value))
+}
+
+fn path_to_string(path: &Path) -> Result<String, SettingsError> {
+    path.to_str()
+        .map(str::to_string)
+        .ok_or_else(|| SettingsError::NonUnicodePath(path.to_path_buf()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    use string::CefStr;
+    use super::*;
+
+    #[test]
+    fn raw_settings() {
+        let settings = Settings::builder()
+            .no_sandbox(true)
+            .cache_path("/tmp/cache")
+            .user_agent("agent")
+            .log_severity(LogSeverity::Warning)
+            .remote_debugging_port(Some(9222))
+            .background_color(Color::rgb(1, 2, 3))
+            .build()
+            .unwrap();
+        let raw = unsafe { &*settings.as_raw() };
+        assert_eq!(raw.size, mem::size_of::<cef_settings_t>());
+        assert_eq!(raw.no_sandbox, 1);
+        assert!(unsafe { CefStr::from_ptr(&raw.cache_path) } == "/tmp/cache");
+        assert!(unsafe { CefStr::from_ptr(&raw.user_agent) } == "agent");
+        assert!(unsafe { CefStr::from_ptr(&raw.locale) }.is_empty());
+        assert!(matches!(raw.log_severity, cef_log_severity_t::LOGSEVERITY_WARNING));
+        assert_eq!(raw.remote_debugging_port, 9222);
+        assert_eq!(raw.background_color, 0xff01_0203);
+        assert_eq!(Color::from_raw(raw.background_color), Color::rgb(1, 2, 3));
+    }
+
+    #[test]
+    fn invalid_settings() {
+        let conflicting = Settings::builder().multi_threaded_message_loop(true).external_message_pump(true).build();
+        assert!(matches!(conflicting, Err(SettingsError::ConflictingMessageLoops)));
+        let port = Settings::builder().remote_debugging_port(Some(80)).build();
+        assert!(matches!(port, Err(SettingsError::InvalidDebuggingPort(80))));
+        let path = Settings::builder().log_file(OsStr::from_bytes(b"/tmp/\xff.log")).build();
+        assert!(matches!(path, Err(SettingsError::NonUnicodePath(_))));
+    }
+}
@@ -0,0 +1,365 @@
+use std::alloc::{Layout, alloc, dealloc, handle_alloc_error};
+use std::char;
+use std::fmt;
+use std::mem;
+use std::ptr;
+use std::slice;
+use std::string::FromUtf16Error;
+
+use sys::{
+    cef_string_list_size,
+    cef_string_list_t,
+    cef_string_list_value,
+    cef_string_t,
+    cef_string_userfree_t,
+    cef_string_userfree_utf16_free,
+    cef_string_utf16_set,
+    char16,
+};
+
+// The dtor only receives the character pointer, so the length is stored in a header before it to be able to
+// rebuild the layout of the allocation.
+const HEADER_SIZE: usize = mem::size_of::<usize>();
+
+fn layout(length: usize) -> Layout {
+    Layout::from_size_align(HEADER_SIZE + length * mem::size_of::<char16>(), mem::align_of::<usize>())
+        .expect("cef string layout")
+}
+
+fn alloc_utf16(chars: &[char16]) -> *mut char16 {
+    let layout = layout(chars.len());
+    unsafe {
+        let block = alloc(layout);
+        if block.is_null() {
+            handle_alloc_error(layout);
+        }
+        *(block as *mut usize) = chars.len();
+        let str = block.add(HEADER_SIZE) as *mut char16;
+        ptr::copy_nonoverlapping(chars.as_ptr(), str, chars.len());
+        str
+    }
+}
+
+extern "C" fn dtor(str: *mut char16) {
+    unsafe {
+        let block = (str as *mut u8).sub(HEADER_SIZE);
+        let length = *(block as *const usize);
+        dealloc(block, layout(length));
+    }
+}
+
+unsafe fn utf16<'a>(string: *const cef_string_t) -> &'a [char16] {
+    if string.is_null() || (*string).str.is_null() {
+        &[]
+    }
+    else {
+        slice::from_raw_parts((*string).str, (*string).length)
+    }
+}
+
+fn write_lossy(chars: &[char16], formatter: &mut fmt::Formatter) -> fmt::Result {
+    for character in char::decode_utf16(chars.iter().cloned()) {
+        let character = character.unwrap_or(char::REPLACEMENT_CHARACTER);
+        fmt::Write::write_char(formatter, character)?;
+    }
+    Ok(())
+}
+
+/// An owned UTF-16 string, laid out as the `cef_string_t` libcef expects.
+pub struct CefString {
+    raw: cef_string_t,
+}
+
+unsafe impl Send for CefString {}
+unsafe impl Sync for CefString {}
+
+impl CefString {
+    pub fn new(string: &str) -> Self {
+        let chars: Vec<char16> = string.encode_utf16().collect();
+        Self::from_utf16(&chars)
+    }
+
+    pub fn from_utf16(chars: &[char16]) -> Self {
+        CefString {
+            raw: cef_string_t {
+                str: alloc_utf16(chars),
+                length: chars.len(),
+                dtor: Some(dtor),
+            },
+        }
+    }
+
+    /// Take ownership of a string filled by CEF: it will be freed with its own dtor.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid string that nothing else frees.
+    pub unsafe fn from_raw(raw: cef_string_t) -> Self {
+        CefString {
+            raw,
+        }
+    }
+
+    /// Copy and free a string returned by CEF. A null pointer is an empty string.
+    ///
+    /// # Safety
+    ///
+    /// `string` must be null or a userfree string allocated by CEF.
+    pub unsafe fn from_userfree(string: cef_string_userfree_t) -> Self {
+        if string.is_null() {
+            return Self::default();
+        }
+        let result = Self::from_utf16(utf16(string));
+        cef_string_userfree_utf16_free(string);
+        result
+    }
+
+    /// The pointer stays valid as long as this string is alive and CEF copies it when needed.
+    pub fn as_raw(&self) -> *const cef_string_t {
+        &self.raw
+    }
+
+    /// A copy without the dtor, which stays owned by this string, for the structs that CEF copies.
+    pub(crate) fn to_borrowed_raw(&self) -> cef_string_t {
+        cef_string_t {
+            str: self.raw.str,
+            length: self.raw.length,
+            dtor: None,
+        }
+    }
+
+    /// Give the ownership of the characters to CEF, which will call the dtor when done with them.
+    pub fn into_raw(self) -> cef_string_t {
+        let raw = cef_string_t {
+            str: self.raw.str,
+            length: self.raw.length,
+            dtor: self.raw.dtor,
+        };
+        mem::forget(self);
+        raw
+    }
+
+    /// Copy into a string owned by CEF, like the output arguments of the handlers.
+    ///
+    /// # Safety
+    ///
+    /// `output` must be a valid string, whose previous value is freed.
+    pub unsafe fn copy_to(&self, output: *mut cef_string_t) {
+        cef_string_utf16_set(self.raw.str, self.raw.length, output, 1);
+    }
+
+    pub fn as_utf16(&self) -> &[char16] {
+        unsafe { utf16(&self.raw) }
+    }
+
+    pub fn as_cef_str(&self) -> CefStr<'_> {
+        CefStr {
+            chars: self.as_utf16(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.raw.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.raw.length == 0
+    }
+
+    /// Convert to a Rust string, failing on unpaired surrogates instead of replacing them.
+    pub fn to_string_checked(&self) -> Result<String, FromUtf16Error> {
+        String::from_utf16(self.as_utf16())
+    }
+}
+
+impl Clone for CefString {
+    fn clone(&self) -> Self {
+        Self::from_utf16(self.as_utf16())
+    }
+}
+
+impl Default for CefString {
+    fn default() -> Self {
+        Self::from_utf16(&[])
+    }
+}
+
+impl Drop for CefString {
+    fn drop(&mut self) {
+        if let Some(dtor) = self.raw.dtor {
+            if !self.raw.str.is_null() {
+                dtor(self.raw.str);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for CefString {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_string(), formatter)
+    }
+}
+
+impl fmt::Display for CefString {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write_lossy(self.as_utf16(), formatter)
+    }
+}
+
+impl<'a> From<&'a str> for CefString {
+    fn from(string: &'a str) -> Self {
+        Self::new(string)
+    }
+}
+
+impl From<String> for CefString {
+    fn from(string: String) -> Self {
+        Self::new(&string)
+    }
+}
+
+impl<'a> From<&'a String> for CefString {
+    fn from(string: &'a String) -> Self {
+        Self::new(string)
+    }
+}
+
+impl<'a> From<CefStr<'a>> for CefString {
+    fn from(string: CefStr<'a>) -> Self {
+        Self::from_utf16(string.as_utf16())
+    }
+}
+
+impl PartialEq for CefString {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_utf16() == other.as_utf16()
+    }
+}
+
+impl Eq for CefString {}
+
+impl<'a> PartialEq<&'a str> for CefString {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_utf16().iter().cloned().eq(other.encode_utf16())
+    }
+}
+
+/// A string borrowed from CEF, like the `*const cef_string_t` arguments of the handlers.
+#[derive(Clone, Copy)]
+pub struct CefStr<'a> {
+    chars: &'a [char16],
+}
+
+impl<'a> CefStr<'a> {
+    /// A null pointer is an empty string.
+    ///
+    /// # Safety
+    ///
+    /// `string` must be null or a valid string that outlives `'a`.
+    pub unsafe fn from_ptr(string: *const cef_string_t) -> Self {
+        CefStr {
+            chars: utf16(string),
+        }
+    }
+
+    pub fn as_utf16(&self) -> &'a [char16] {
+        self.chars
+    }
+
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    pub fn to_cef_string(&self) -> CefString {
+        CefString::from(*self)
+    }
+
+    /// Convert to a Rust string, failing on unpaired surrogates instead of replacing them.
+    pub fn to_string_checked(&self) -> Result<String, FromUtf16Error> {
+        String::from_utf16(self.chars)
+    }
+}
+
+impl<'a> fmt::Debug for CefStr<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_string(), formatter)
+    }
+}
+
+impl<'a> fmt::Display for CefStr<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write_lossy(self.chars, formatter)
+    }
+}
+
+impl<'a, 'b> PartialEq<&'b str> for CefStr<'a> {
+    fn eq(&self, other: &&'b str) -> bool {
+        self.chars.iter().cloned().eq(other.encode_utf16())
+    }
+}
+
+impl<'a> From<CefStr<'a>> for String {
+    fn from(string: CefStr<'a>) -> Self {
+        string.to_string()
+    }
+}
+
+/// Copy the strings of a list owned by CEF.
+///
+/// # Safety
+///
+/// `list` must be null or a valid string list.
+pub(crate) unsafe fn string_list(list: cef_string_list_t) -> Vec<String> {
+    if list.is_null() {
+        return vec![];
+    }
+    (0..cef_string_list_size(list))
+        .map(|index| {
+            let mut value = CefString::default().into_raw();
+            cef_string_list_value(list, index, &mut value);
+            CefString::from_raw(value).to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use sys::cef_string_userfree_utf16_alloc;
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let string = CefString::new("héllo 🦀");
+        assert_eq!(string.len(), 8);
+        assert_eq!(string.to_string(), "héllo 🦀");
+        assert_eq!(string.clone(), string);
+        assert!(string.as_cef_str() == "héllo 🦀");
+
+        let string = unsafe { CefString::from_raw(string.into_raw()) };
+        assert_eq!(string, "héllo 🦀");
+        assert!(CefString::default().is_empty());
+    }
+
+    #[test]
+    fn unpaired_surrogate() {
+        let string = CefString::from_utf16(&[0x61, 0xd800]);
+        assert_eq!(string.to_string(), "a\u{fffd}");
+        assert!(string.to_string_checked().is_err());
+        assert!(string.as_cef_str().to_string_checked().is_err());
+    }
+
+    #[test]
+    fn strings_owned_by_cef() {
+        unsafe {
+            let output = cef_string_userfree_utf16_alloc();
+            CefString::new("copied").copy_to(output);
+            assert_eq!(CefString::from_userfree(output), "copied");
+            assert!(CefString::from_userfree(ptr::null_mut()).is_empty());
+            assert!(CefStr::from_ptr(ptr::null()).is_empty());
+        }
+    }
+}
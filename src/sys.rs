@@ -0,0 +1,1900 @@
+/*
+ * Raw bindings to the CEF C API (libcef 74).
+ *
+ * Every ref-counted struct starts with a cef_base_ref_counted_t (possibly nested through its parent
+ * struct), so a pointer to any of them can be cast to a pointer to its base.
+ * Function pointers are optional because CEF checks for missing members before calling them.
+ */
+
+#![allow(non_camel_case_types)]
+#![allow(clippy::type_complexity)]
+
+use std::os::raw::{c_char, c_void};
+
+// internal/cef_string_types.h
+
+pub type char16 = u16;
+
+#[repr(C)]
+pub struct cef_string_wide_t {
+    pub str: *mut i32,
+    pub length: usize,
+    pub dtor: Option<extern "C" fn(str: *mut i32)>,
+}
+
+#[repr(C)]
+pub struct cef_string_utf8_t {
+    pub str: *mut c_char,
+    pub length: usize,
+    pub dtor: Option<extern "C" fn(str: *mut c_char)>,
+}
+
+#[repr(C)]
+pub struct cef_string_utf16_t {
+    pub str: *mut char16,
+    pub length: usize,
+    pub dtor: Option<extern "C" fn(str: *mut char16)>,
+}
+
+pub type cef_string_userfree_wide_t = *mut cef_string_wide_t;
+pub type cef_string_userfree_utf8_t = *mut cef_string_utf8_t;
+pub type cef_string_userfree_utf16_t = *mut cef_string_utf16_t;
+
+// internal/cef_string.h: libcef is built with CEF_STRING_TYPE_UTF16 by default.
+
+pub type cef_string_t = cef_string_utf16_t;
+pub type cef_string_userfree_t = cef_string_userfree_utf16_t;
+
+// internal/cef_string_list.h and internal/cef_string_map.h
+
+pub type cef_string_list_t = *mut c_void;
+pub type cef_string_map_t = *mut c_void;
+pub type cef_string_multimap_t = *mut c_void;
+
+// internal/cef_linux.h and internal/cef_types_linux.h
+
+pub type cef_cursor_handle_t = u64;
+pub type cef_event_handle_t = *mut c_void;
+pub type cef_window_handle_t = u64;
+
+#[repr(C)]
+pub struct cef_main_args_t {
+    pub argc: i32,
+    pub argv: *mut *mut c_char,
+}
+
+#[repr(C)]
+pub struct cef_window_info_t {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    pub parent_window: cef_window_handle_t,
+    pub windowless_rendering_enabled: i32,
+    pub shared_texture_enabled: i32,
+    pub external_begin_frame_enabled: i32,
+    pub window: cef_window_handle_t,
+}
+
+// internal/cef_time.h
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct cef_time_t {
+    pub year: i32,
+    pub month: i32,
+    pub day_of_week: i32,
+    pub day_of_month: i32,
+    pub hour: i32,
+    pub minute: i32,
+    pub second: i32,
+    pub millisecond: i32,
+}
+
+// internal/cef_types.h
+
+pub type cef_color_t = u32;
+
+#[repr(C)]
+pub enum cef_log_severity_t {
+  LOGSEVERITY_DEFAULT,
+  LOGSEVERITY_VERBOSE,
+  //LOGSEVERITY_DEBUG = cef_log_severity_t::LOGSEVERITY_VERBOSE as isize,
+  LOGSEVERITY_INFO,
+  LOGSEVERITY_WARNING,
+  LOGSEVERITY_ERROR,
+  LOGSEVERITY_DISABLE = 99
+}
+
+#[repr(C)]
+pub enum cef_state_t {
+  STATE_DEFAULT = 0,
+  STATE_ENABLED,
+  STATE_DISABLED,
+}
+
+#[repr(C)]
+pub struct cef_settings_t {
+    pub size: usize,
+    pub no_sandbox: i32,
+    pub browser_subprocess_path: cef_string_t,
+    pub framework_dir_path: cef_string_t,
+    pub multi_threaded_message_loop: i32,
+    pub external_message_pump: i32,
+    pub windowless_rendering_enabled: i32,
+    pub command_line_args_disabled: i32,
+    pub cache_path: cef_string_t,
+    pub user_data_path: cef_string_t,
+    pub persist_session_cookies: i32,
+    pub persist_user_preferences: i32,
+    pub user_agent: cef_string_t,
+    pub product_version: cef_string_t,
+    pub locale: cef_string_t,
+    pub log_file: cef_string_t,
+    pub log_severity: cef_log_severity_t,
+    pub javascript_flags: cef_string_t,
+    pub resources_dir_path: cef_string_t,
+    pub locales_dir_path: cef_string_t,
+    pub pack_loading_disabled: i32,
+    pub remote_debugging_port: i32,
+    pub uncaught_exception_stack_size: i32,
+    pub ignore_certificate_errors: i32,
+    pub enable_net_security_expiration: i32,
+    pub background_color: cef_color_t,
+    pub accept_language_list: cef_string_t,
+}
+
+#[repr(C)]
+pub struct cef_browser_settings_t {
+    pub size: usize,
+    pub windowless_frame_rate: i32,
+    pub standard_font_family: cef_string_t,
+    pub fixed_font_family: cef_string_t,
+    pub serif_font_family: cef_string_t,
+    pub sans_serif_font_family: cef_string_t,
+    pub cursive_font_family: cef_string_t,
+    pub fantasy_font_family: cef_string_t,
+    pub default_font_size: i32,
+    pub default_fixed_font_size: i32,
+    pub minimum_font_size: i32,
+    pub minimum_logical_font_size: i32,
+    pub default_encoding: cef_string_t,
+    pub remote_fonts: cef_state_t,
+    pub javascript: cef_state_t,
+    pub javascript_close_windows: cef_state_t,
+    pub javascript_access_clipboard: cef_state_t,
+    pub javascript_dom_paste: cef_state_t,
+    pub plugins: cef_state_t,
+    pub universal_access_from_file_urls: cef_state_t,
+    pub file_access_from_file_urls: cef_state_t,
+    pub web_security: cef_state_t,
+    pub image_loading: cef_state_t,
+    pub image_shrink_standalone_to_fit: cef_state_t,
+    pub text_area_resize: cef_state_t,
+    pub tab_to_links: cef_state_t,
+    pub local_storage: cef_state_t,
+    pub databases: cef_state_t,
+    pub application_cache: cef_state_t,
+    pub webgl: cef_state_t,
+    pub background_color: cef_color_t,
+    pub accept_language_list: cef_string_t,
+}
+
+// A source combined with qualifier flags, so it cannot be a Rust enum.
+
+pub type cef_transition_type_t = u32;
+
+pub const TT_LINK: cef_transition_type_t = 0;
+pub const TT_EXPLICIT: cef_transition_type_t = 1;
+pub const TT_AUTO_SUBFRAME: cef_transition_type_t = 3;
+pub const TT_MANUAL_SUBFRAME: cef_transition_type_t = 4;
+pub const TT_FORM_SUBMIT: cef_transition_type_t = 7;
+pub const TT_RELOAD: cef_transition_type_t = 8;
+pub const TT_SOURCE_MASK: cef_transition_type_t = 0xFF;
+pub const TT_BLOCKED_FLAG: cef_transition_type_t = 0x00800000;
+pub const TT_FORWARD_BACK_FLAG: cef_transition_type_t = 0x01000000;
+pub const TT_CHAIN_START_FLAG: cef_transition_type_t = 0x10000000;
+pub const TT_CHAIN_END_FLAG: cef_transition_type_t = 0x20000000;
+pub const TT_CLIENT_REDIRECT_FLAG: cef_transition_type_t = 0x40000000;
+pub const TT_SERVER_REDIRECT_FLAG: cef_transition_type_t = 0x80000000;
+pub const TT_IS_REDIRECT_MASK: cef_transition_type_t = 0xC0000000;
+pub const TT_QUALIFIER_MASK: cef_transition_type_t = 0xFFFFFF00;
+
+// Integer constants: CEF sends many more codes than the ones declared here.
+
+pub type cef_errorcode_t = i32;
+
+pub const ERR_NONE: cef_errorcode_t = 0;
+pub const ERR_FAILED: cef_errorcode_t = -2;
+pub const ERR_ABORTED: cef_errorcode_t = -3;
+pub const ERR_INVALID_ARGUMENT: cef_errorcode_t = -4;
+pub const ERR_INVALID_HANDLE: cef_errorcode_t = -5;
+pub const ERR_FILE_NOT_FOUND: cef_errorcode_t = -6;
+pub const ERR_TIMED_OUT: cef_errorcode_t = -7;
+pub const ERR_FILE_TOO_BIG: cef_errorcode_t = -8;
+pub const ERR_UNEXPECTED: cef_errorcode_t = -9;
+pub const ERR_ACCESS_DENIED: cef_errorcode_t = -10;
+pub const ERR_NOT_IMPLEMENTED: cef_errorcode_t = -11;
+pub const ERR_CONNECTION_CLOSED: cef_errorcode_t = -100;
+pub const ERR_CONNECTION_RESET: cef_errorcode_t = -101;
+pub const ERR_CONNECTION_REFUSED: cef_errorcode_t = -102;
+pub const ERR_CONNECTION_ABORTED: cef_errorcode_t = -103;
+pub const ERR_CONNECTION_FAILED: cef_errorcode_t = -104;
+pub const ERR_NAME_NOT_RESOLVED: cef_errorcode_t = -105;
+pub const ERR_INTERNET_DISCONNECTED: cef_errorcode_t = -106;
+pub const ERR_SSL_PROTOCOL_ERROR: cef_errorcode_t = -107;
+pub const ERR_ADDRESS_INVALID: cef_errorcode_t = -108;
+pub const ERR_ADDRESS_UNREACHABLE: cef_errorcode_t = -109;
+pub const ERR_SSL_CLIENT_AUTH_CERT_NEEDED: cef_errorcode_t = -110;
+pub const ERR_TUNNEL_CONNECTION_FAILED: cef_errorcode_t = -111;
+pub const ERR_NO_SSL_VERSIONS_ENABLED: cef_errorcode_t = -112;
+pub const ERR_SSL_VERSION_OR_CIPHER_MISMATCH: cef_errorcode_t = -113;
+pub const ERR_SSL_RENEGOTIATION_REQUESTED: cef_errorcode_t = -114;
+pub const ERR_CERT_COMMON_NAME_INVALID: cef_errorcode_t = -200;
+pub const ERR_CERT_BEGIN: cef_errorcode_t = ERR_CERT_COMMON_NAME_INVALID;
+pub const ERR_CERT_DATE_INVALID: cef_errorcode_t = -201;
+pub const ERR_CERT_AUTHORITY_INVALID: cef_errorcode_t = -202;
+pub const ERR_CERT_CONTAINS_ERRORS: cef_errorcode_t = -203;
+pub const ERR_CERT_NO_REVOCATION_MECHANISM: cef_errorcode_t = -204;
+pub const ERR_CERT_UNABLE_TO_CHECK_REVOCATION: cef_errorcode_t = -205;
+pub const ERR_CERT_REVOKED: cef_errorcode_t = -206;
+pub const ERR_CERT_INVALID: cef_errorcode_t = -207;
+pub const ERR_CERT_WEAK_SIGNATURE_ALGORITHM: cef_errorcode_t = -208;
+// -209 is available: was ERR_CERT_NOT_IN_DNS.
+pub const ERR_CERT_NON_UNIQUE_NAME: cef_errorcode_t = -210;
+pub const ERR_CERT_WEAK_KEY: cef_errorcode_t = -211;
+pub const ERR_CERT_NAME_CONSTRAINT_VIOLATION: cef_errorcode_t = -212;
+pub const ERR_CERT_VALIDITY_TOO_LONG: cef_errorcode_t = -213;
+pub const ERR_CERT_END: cef_errorcode_t = ERR_CERT_VALIDITY_TOO_LONG;
+pub const ERR_INVALID_URL: cef_errorcode_t = -300;
+pub const ERR_DISALLOWED_URL_SCHEME: cef_errorcode_t = -301;
+pub const ERR_UNKNOWN_URL_SCHEME: cef_errorcode_t = -302;
+pub const ERR_TOO_MANY_REDIRECTS: cef_errorcode_t = -310;
+pub const ERR_UNSAFE_REDIRECT: cef_errorcode_t = -311;
+pub const ERR_UNSAFE_PORT: cef_errorcode_t = -312;
+pub const ERR_INVALID_RESPONSE: cef_errorcode_t = -320;
+pub const ERR_INVALID_CHUNKED_ENCODING: cef_errorcode_t = -321;
+pub const ERR_METHOD_NOT_SUPPORTED: cef_errorcode_t = -322;
+pub const ERR_UNEXPECTED_PROXY_AUTH: cef_errorcode_t = -323;
+pub const ERR_EMPTY_RESPONSE: cef_errorcode_t = -324;
+pub const ERR_RESPONSE_HEADERS_TOO_BIG: cef_errorcode_t = -325;
+pub const ERR_CACHE_MISS: cef_errorcode_t = -400;
+pub const ERR_INSECURE_RESPONSE: cef_errorcode_t = -501;
+
+#[repr(C)]
+pub enum cef_process_id_t {
+  PID_BROWSER,
+  PID_RENDERER,
+}
+
+#[repr(C)]
+pub enum cef_thread_id_t {
+  TID_UI,
+  TID_FILE_BACKGROUND,
+  TID_FILE_USER_VISIBLE,
+  TID_FILE_USER_BLOCKING,
+  TID_PROCESS_LAUNCHER,
+  TID_IO,
+  TID_RENDERER,
+}
+
+#[repr(C)]
+pub enum cef_value_type_t {
+  VTYPE_INVALID = 0,
+  VTYPE_NULL,
+  VTYPE_BOOL,
+  VTYPE_INT,
+  VTYPE_DOUBLE,
+  VTYPE_STRING,
+  VTYPE_BINARY,
+  VTYPE_DICTIONARY,
+  VTYPE_LIST,
+}
+
+// Integer constants: a newer CEF can send dispositions not declared here.
+
+pub type cef_window_open_disposition_t = i32;
+
+pub const WOD_UNKNOWN: cef_window_open_disposition_t = 0;
+pub const WOD_CURRENT_TAB: cef_window_open_disposition_t = 1;
+pub const WOD_SINGLETON_TAB: cef_window_open_disposition_t = 2;
+pub const WOD_NEW_FOREGROUND_TAB: cef_window_open_disposition_t = 3;
+pub const WOD_NEW_BACKGROUND_TAB: cef_window_open_disposition_t = 4;
+pub const WOD_NEW_POPUP: cef_window_open_disposition_t = 5;
+pub const WOD_NEW_WINDOW: cef_window_open_disposition_t = 6;
+pub const WOD_SAVE_TO_DISK: cef_window_open_disposition_t = 7;
+pub const WOD_OFF_THE_RECORD: cef_window_open_disposition_t = 8;
+pub const WOD_IGNORE_ACTION: cef_window_open_disposition_t = 9;
+
+#[repr(C)]
+pub enum cef_paint_element_type_t {
+  PET_VIEW = 0,
+  PET_POPUP,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub enum cef_key_event_type_t {
+  KEYEVENT_RAWKEYDOWN = 0,
+  KEYEVENT_KEYDOWN,
+  KEYEVENT_KEYUP,
+  KEYEVENT_CHAR
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct cef_key_event_t {
+    pub type_: cef_key_event_type_t,
+    pub modifiers: u32,
+    pub windows_key_code: i32,
+    pub native_key_code: i32,
+    pub is_system_key: i32,
+    pub character: char16,
+    pub unmodified_character: char16,
+    pub focus_on_editable_field: i32,
+}
+
+#[repr(C)]
+pub enum cef_mouse_button_type_t {
+  MBT_LEFT = 0,
+  MBT_MIDDLE,
+  MBT_RIGHT,
+}
+
+#[repr(C)]
+pub struct cef_mouse_event_t {
+    pub x: i32,
+    pub y: i32,
+    pub modifiers: u32,
+}
+
+#[repr(C)]
+pub enum cef_touch_event_type_t {
+  CEF_TET_RELEASED = 0,
+  CEF_TET_PRESSED,
+  CEF_TET_MOVED,
+  CEF_TET_CANCELLED
+}
+
+#[repr(C)]
+pub enum cef_pointer_type_t {
+  CEF_POINTER_TYPE_TOUCH = 0,
+  CEF_POINTER_TYPE_MOUSE,
+  CEF_POINTER_TYPE_PEN,
+  CEF_POINTER_TYPE_ERASER,
+  CEF_POINTER_TYPE_UNKNOWN
+}
+
+#[repr(C)]
+pub struct cef_touch_event_t {
+    pub id: i32,
+    pub x: f32,
+    pub y: f32,
+    pub radius_x: f32,
+    pub radius_y: f32,
+    pub rotation_angle: f32,
+    pub pressure: f32,
+    pub type_: cef_touch_event_type_t,
+    pub modifiers: u32,
+    pub pointer_type: cef_pointer_type_t,
+}
+
+#[repr(C)]
+pub enum cef_focus_source_t {
+  FOCUS_SOURCE_NAVIGATION = 0,
+  FOCUS_SOURCE_SYSTEM,
+}
+
+#[repr(C)]
+pub enum cef_jsdialog_type_t {
+  JSDIALOGTYPE_ALERT = 0,
+  JSDIALOGTYPE_CONFIRM,
+  JSDIALOGTYPE_PROMPT,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct cef_point_t {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct cef_rect_t {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct cef_size_t {
+    pub width: i32,
+    pub height: i32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct cef_range_t {
+    pub from: i32,
+    pub to: i32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct cef_insets_t {
+    pub top: i32,
+    pub left: i32,
+    pub bottom: i32,
+    pub right: i32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct cef_draggable_region_t {
+    pub bounds: cef_rect_t,
+    pub draggable: i32,
+}
+
+#[repr(C)]
+pub struct cef_screen_info_t {
+    pub device_scale_factor: f32,
+    pub depth: i32,
+    pub depth_per_component: i32,
+    pub is_monochrome: i32,
+    pub rect: cef_rect_t,
+    pub available_rect: cef_rect_t,
+}
+
+#[repr(C)]
+pub struct cef_popup_features_t {
+    pub x: i32,
+    pub x_set: i32,
+    pub y: i32,
+    pub y_set: i32,
+    pub width: i32,
+    pub width_set: i32,
+    pub height: i32,
+    pub height_set: i32,
+    pub menu_bar_visible: i32,
+    pub status_bar_visible: i32,
+    pub tool_bar_visible: i32,
+    pub scrollbars_visible: i32,
+}
+
+#[repr(C)]
+pub struct cef_composition_underline_t {
+    pub range: cef_range_t,
+    pub color: cef_color_t,
+    pub background_color: cef_color_t,
+    pub thick: i32,
+}
+
+// Bit flags: these are combined by CEF, so they cannot be Rust enums.
+
+pub type cef_event_flags_t = u32;
+
+pub const EVENTFLAG_NONE: cef_event_flags_t = 0;
+pub const EVENTFLAG_CAPS_LOCK_ON: cef_event_flags_t = 1 << 0;
+pub const EVENTFLAG_SHIFT_DOWN: cef_event_flags_t = 1 << 1;
+pub const EVENTFLAG_CONTROL_DOWN: cef_event_flags_t = 1 << 2;
+pub const EVENTFLAG_ALT_DOWN: cef_event_flags_t = 1 << 3;
+pub const EVENTFLAG_LEFT_MOUSE_BUTTON: cef_event_flags_t = 1 << 4;
+pub const EVENTFLAG_MIDDLE_MOUSE_BUTTON: cef_event_flags_t = 1 << 5;
+pub const EVENTFLAG_RIGHT_MOUSE_BUTTON: cef_event_flags_t = 1 << 6;
+pub const EVENTFLAG_COMMAND_DOWN: cef_event_flags_t = 1 << 7;
+pub const EVENTFLAG_NUM_LOCK_ON: cef_event_flags_t = 1 << 8;
+pub const EVENTFLAG_IS_KEY_PAD: cef_event_flags_t = 1 << 9;
+pub const EVENTFLAG_IS_LEFT: cef_event_flags_t = 1 << 10;
+pub const EVENTFLAG_IS_RIGHT: cef_event_flags_t = 1 << 11;
+
+pub type cef_drag_operations_mask_t = u32;
+
+pub const DRAG_OPERATION_NONE: cef_drag_operations_mask_t = 0;
+pub const DRAG_OPERATION_COPY: cef_drag_operations_mask_t = 1;
+pub const DRAG_OPERATION_LINK: cef_drag_operations_mask_t = 2;
+pub const DRAG_OPERATION_GENERIC: cef_drag_operations_mask_t = 4;
+pub const DRAG_OPERATION_PRIVATE: cef_drag_operations_mask_t = 8;
+pub const DRAG_OPERATION_MOVE: cef_drag_operations_mask_t = 16;
+pub const DRAG_OPERATION_DELETE: cef_drag_operations_mask_t = 32;
+pub const DRAG_OPERATION_EVERY: cef_drag_operations_mask_t = u32::MAX;
+
+pub type cef_file_dialog_mode_t = u32;
+
+pub const FILE_DIALOG_OPEN: cef_file_dialog_mode_t = 0;
+pub const FILE_DIALOG_OPEN_MULTIPLE: cef_file_dialog_mode_t = 1;
+pub const FILE_DIALOG_OPEN_FOLDER: cef_file_dialog_mode_t = 2;
+pub const FILE_DIALOG_SAVE: cef_file_dialog_mode_t = 3;
+pub const FILE_DIALOG_TYPE_MASK: cef_file_dialog_mode_t = 0xFF;
+pub const FILE_DIALOG_OVERWRITEPROMPT_FLAG: cef_file_dialog_mode_t = 0x01000000;
+pub const FILE_DIALOG_HIDEREADONLY_FLAG: cef_file_dialog_mode_t = 0x02000000;
+
+pub type cef_context_menu_type_flags_t = u32;
+
+pub const CM_TYPEFLAG_NONE: cef_context_menu_type_flags_t = 0;
+pub const CM_TYPEFLAG_PAGE: cef_context_menu_type_flags_t = 1 << 0;
+pub const CM_TYPEFLAG_FRAME: cef_context_menu_type_flags_t = 1 << 1;
+pub const CM_TYPEFLAG_LINK: cef_context_menu_type_flags_t = 1 << 2;
+pub const CM_TYPEFLAG_MEDIA: cef_context_menu_type_flags_t = 1 << 3;
+pub const CM_TYPEFLAG_SELECTION: cef_context_menu_type_flags_t = 1 << 4;
+pub const CM_TYPEFLAG_EDITABLE: cef_context_menu_type_flags_t = 1 << 5;
+
+pub type cef_context_menu_media_state_flags_t = u32;
+
+pub const CM_MEDIAFLAG_NONE: cef_context_menu_media_state_flags_t = 0;
+pub const CM_MEDIAFLAG_ERROR: cef_context_menu_media_state_flags_t = 1 << 0;
+pub const CM_MEDIAFLAG_PAUSED: cef_context_menu_media_state_flags_t = 1 << 1;
+pub const CM_MEDIAFLAG_MUTED: cef_context_menu_media_state_flags_t = 1 << 2;
+pub const CM_MEDIAFLAG_LOOP: cef_context_menu_media_state_flags_t = 1 << 3;
+pub const CM_MEDIAFLAG_CAN_SAVE: cef_context_menu_media_state_flags_t = 1 << 4;
+pub const CM_MEDIAFLAG_HAS_AUDIO: cef_context_menu_media_state_flags_t = 1 << 5;
+pub const CM_MEDIAFLAG_HAS_VIDEO: cef_context_menu_media_state_flags_t = 1 << 6;
+pub const CM_MEDIAFLAG_CONTROL_ROOT_ELEMENT: cef_context_menu_media_state_flags_t = 1 << 7;
+pub const CM_MEDIAFLAG_CAN_PRINT: cef_context_menu_media_state_flags_t = 1 << 8;
+pub const CM_MEDIAFLAG_CAN_ROTATE: cef_context_menu_media_state_flags_t = 1 << 9;
+
+pub type cef_context_menu_edit_state_flags_t = u32;
+
+pub const CM_EDITFLAG_NONE: cef_context_menu_edit_state_flags_t = 0;
+pub const CM_EDITFLAG_CAN_UNDO: cef_context_menu_edit_state_flags_t = 1 << 0;
+pub const CM_EDITFLAG_CAN_REDO: cef_context_menu_edit_state_flags_t = 1 << 1;
+pub const CM_EDITFLAG_CAN_CUT: cef_context_menu_edit_state_flags_t = 1 << 2;
+pub const CM_EDITFLAG_CAN_COPY: cef_context_menu_edit_state_flags_t = 1 << 3;
+pub const CM_EDITFLAG_CAN_PASTE: cef_context_menu_edit_state_flags_t = 1 << 4;
+pub const CM_EDITFLAG_CAN_DELETE: cef_context_menu_edit_state_flags_t = 1 << 5;
+pub const CM_EDITFLAG_CAN_SELECT_ALL: cef_context_menu_edit_state_flags_t = 1 << 6;
+pub const CM_EDITFLAG_CAN_TRANSLATE: cef_context_menu_edit_state_flags_t = 1 << 7;
+
+#[repr(C)]
+pub enum cef_context_menu_media_type_t {
+  CM_MEDIATYPE_NONE,
+  CM_MEDIATYPE_IMAGE,
+  CM_MEDIATYPE_VIDEO,
+  CM_MEDIATYPE_AUDIO,
+  CM_MEDIATYPE_FILE,
+  CM_MEDIATYPE_PLUGIN,
+}
+
+#[repr(C)]
+pub enum cef_menu_item_type_t {
+  MENUITEMTYPE_NONE,
+  MENUITEMTYPE_COMMAND,
+  MENUITEMTYPE_CHECK,
+  MENUITEMTYPE_RADIO,
+  MENUITEMTYPE_SEPARATOR,
+  MENUITEMTYPE_SUBMENU,
+}
+
+#[repr(C)]
+pub enum cef_menu_color_type_t {
+  CEF_MENU_COLOR_TEXT,
+  CEF_MENU_COLOR_TEXT_HOVERED,
+  CEF_MENU_COLOR_TEXT_ACCELERATOR,
+  CEF_MENU_COLOR_TEXT_ACCELERATOR_HOVERED,
+  CEF_MENU_COLOR_BACKGROUND,
+  CEF_MENU_COLOR_BACKGROUND_HOVERED,
+  CEF_MENU_COLOR_COUNT,
+}
+
+pub const MENU_ID_USER_FIRST: i32 = 26500;
+pub const MENU_ID_USER_LAST: i32 = 28500;
+
+#[repr(C)]
+pub enum cef_menu_anchor_position_t {
+  CEF_MENU_ANCHOR_TOPLEFT,
+  CEF_MENU_ANCHOR_TOPRIGHT,
+  CEF_MENU_ANCHOR_BOTTOMCENTER,
+}
+
+#[repr(C)]
+pub enum cef_main_axis_alignment_t {
+  CEF_MAIN_AXIS_ALIGNMENT_START,
+  CEF_MAIN_AXIS_ALIGNMENT_CENTER,
+  CEF_MAIN_AXIS_ALIGNMENT_END,
+}
+
+#[repr(C)]
+pub enum cef_cross_axis_alignment_t {
+  CEF_CROSS_AXIS_ALIGNMENT_STRETCH,
+  CEF_CROSS_AXIS_ALIGNMENT_START,
+  CEF_CROSS_AXIS_ALIGNMENT_CENTER,
+  CEF_CROSS_AXIS_ALIGNMENT_END,
+}
+
+#[repr(C)]
+pub struct cef_box_layout_settings_t {
+    pub horizontal: i32,
+    pub inside_border_horizontal_spacing: i32,
+    pub inside_border_vertical_spacing: i32,
+    pub inside_border_insets: cef_insets_t,
+    pub between_child_spacing: i32,
+    pub main_axis_alignment: cef_main_axis_alignment_t,
+    pub cross_axis_alignment: cef_cross_axis_alignment_t,
+    pub minimum_cross_axis_size: i32,
+}
+
+#[repr(C)]
+pub enum cef_cursor_type_t {
+  CT_POINTER = 0,
+  CT_CROSS,
+  CT_HAND,
+  CT_IBEAM,
+  CT_WAIT,
+  CT_HELP,
+  CT_EASTRESIZE,
+  CT_NORTHRESIZE,
+  CT_NORTHEASTRESIZE,
+  CT_NORTHWESTRESIZE,
+  CT_SOUTHRESIZE,
+  CT_SOUTHEASTRESIZE,
+  CT_SOUTHWESTRESIZE,
+  CT_WESTRESIZE,
+  CT_NORTHSOUTHRESIZE,
+  CT_EASTWESTRESIZE,
+  CT_NORTHEASTSOUTHWESTRESIZE,
+  CT_NORTHWESTSOUTHEASTRESIZE,
+  CT_COLUMNRESIZE,
+  CT_ROWRESIZE,
+  CT_MIDDLEPANNING,
+  CT_EASTPANNING,
+  CT_NORTHPANNING,
+  CT_NORTHEASTPANNING,
+  CT_NORTHWESTPANNING,
+  CT_SOUTHPANNING,
+  CT_SOUTHEASTPANNING,
+  CT_SOUTHWESTPANNING,
+  CT_WESTPANNING,
+  CT_MOVE,
+  CT_VERTICALTEXT,
+  CT_CELL,
+  CT_CONTEXTMENU,
+  CT_ALIAS,
+  CT_PROGRESS,
+  CT_NODROP,
+  CT_COPY,
+  CT_NONE,
+  CT_NOTALLOWED,
+  CT_ZOOMIN,
+  CT_ZOOMOUT,
+  CT_GRAB,
+  CT_GRABBING,
+  CT_CUSTOM,
+}
+
+#[repr(C)]
+pub struct cef_cursor_info_t {
+    pub hotspot: cef_point_t,
+    pub image_scale_factor: f32,
+    pub buffer: *mut c_void,
+    pub size: cef_size_t,
+}
+
+#[repr(C)]
+pub enum cef_termination_status_t {
+  TS_ABNORMAL_TERMINATION,
+  TS_PROCESS_WAS_KILLED,
+  TS_PROCESS_CRASHED,
+  TS_PROCESS_OOM,
+}
+
+// Types that are only ever handled through pointers by these bindings.
+
+pub enum cef_accessibility_handler_t {}
+pub enum cef_binary_value_t {}
+pub enum cef_button_t {}
+pub enum cef_cookie_manager_t {}
+pub enum cef_completion_callback_t {}
+pub enum cef_dictionary_value_t {}
+pub enum cef_display_t {}
+pub enum cef_domnode_t {}
+pub enum cef_domvisitor_t {}
+pub enum cef_download_image_callback_t {}
+pub enum cef_extension_t {}
+pub enum cef_extension_handler_t {}
+pub enum cef_image_t {}
+pub enum cef_menu_model_delegate_t {}
+pub enum cef_navigation_entry_t {}
+pub enum cef_navigation_entry_visitor_t {}
+pub enum cef_pdf_print_callback_t {}
+pub enum cef_pdf_print_settings_t {}
+pub enum cef_print_dialog_callback_t {}
+pub enum cef_print_job_callback_t {}
+pub enum cef_print_settings_t {}
+pub enum cef_request_t {}
+pub enum cef_request_context_handler_t {}
+pub enum cef_request_handler_t {}
+pub enum cef_resolve_callback_t {}
+pub enum cef_run_context_menu_callback_t {}
+pub enum cef_run_file_dialog_callback_t {}
+pub enum cef_scheme_handler_factory_t {}
+pub enum cef_scroll_view_t {}
+pub enum cef_stream_writer_t {}
+pub enum cef_string_visitor_t {}
+pub enum cef_task_runner_t {}
+pub enum cef_textfield_t {}
+pub enum cef_v8exception_t {}
+pub enum cef_v8stack_trace_t {}
+pub enum cef_value_t {}
+
+// cef_base_capi.h
+
+#[repr(C)]
+pub struct cef_base_ref_counted_t {
+    pub size: usize,
+    pub add_ref: Option<extern "C" fn(self_: *mut cef_base_ref_counted_t)>,
+    pub release: Option<extern "C" fn(self_: *mut cef_base_ref_counted_t) -> i32>,
+    pub has_one_ref: Option<extern "C" fn(self_: *mut cef_base_ref_counted_t) -> i32>,
+    pub has_at_least_one_ref: Option<extern "C" fn(self_: *mut cef_base_ref_counted_t) -> i32>,
+}
+
+#[repr(C)]
+pub struct cef_base_scoped_t {
+    pub size: usize,
+    pub del: Option<extern "C" fn(self_: *mut cef_base_scoped_t)>,
+}
+
+// cef_app_capi.h
+
+#[repr(C)]
+pub struct cef_app_t {
+    pub base: cef_base_ref_counted_t,
+    pub on_before_command_line_processing: Option<extern "C" fn(self_: *mut cef_app_t,
+        process_type: *const cef_string_t, command_line: *mut cef_command_line_t)>,
+    pub on_register_custom_schemes: Option<extern "C" fn(self_: *mut cef_app_t,
+        registrar: *mut cef_scheme_registrar_t)>,
+    pub get_resource_bundle_handler: Option<extern "C" fn(self_: *mut cef_app_t)
+        -> *mut cef_resource_bundle_handler_t>,
+    pub get_browser_process_handler: Option<extern "C" fn(self_: *mut cef_app_t)
+        -> *mut cef_browser_process_handler_t>,
+    pub get_render_process_handler: Option<extern "C" fn(self_: *mut cef_app_t)
+        -> *mut cef_render_process_handler_t>,
+}
+
+// cef_browser_process_handler_capi.h
+
+#[repr(C)]
+pub struct cef_browser_process_handler_t {
+    pub base: cef_base_ref_counted_t,
+    pub on_context_initialized: Option<extern "C" fn(self_: *mut cef_browser_process_handler_t)>,
+    pub on_before_child_process_launch: Option<extern "C" fn(self_: *mut cef_browser_process_handler_t,
+        command_line: *mut cef_command_line_t)>,
+    pub on_render_process_thread_created: Option<extern "C" fn(self_: *mut cef_browser_process_handler_t,
+        extra_info: *mut cef_list_value_t)>,
+    pub get_print_handler: Option<extern "C" fn(self_: *mut cef_browser_process_handler_t)
+        -> *mut cef_print_handler_t>,
+    pub on_schedule_message_pump_work: Option<extern "C" fn(self_: *mut cef_browser_process_handler_t,
+        delay_ms: i64)>,
+}
+
+// cef_render_process_handler_capi.h
+
+#[repr(C)]
+pub struct cef_render_process_handler_t {
+    pub base: cef_base_ref_counted_t,
+    pub on_render_thread_created: Option<extern "C" fn(self_: *mut cef_render_process_handler_t,
+        extra_info: *mut cef_list_value_t)>,
+    pub on_web_kit_initialized: Option<extern "C" fn(self_: *mut cef_render_process_handler_t)>,
+    pub on_browser_created: Option<extern "C" fn(self_: *mut cef_render_process_handler_t,
+        browser: *mut cef_browser_t)>,
+    pub on_browser_destroyed: Option<extern "C" fn(self_: *mut cef_render_process_handler_t,
+        browser: *mut cef_browser_t)>,
+    pub get_load_handler: Option<extern "C" fn(self_: *mut cef_render_process_handler_t)
+        -> *mut cef_load_handler_t>,
+    pub on_context_created: Option<extern "C" fn(self_: *mut cef_render_process_handler_t,
+        browser: *mut cef_browser_t, frame: *mut cef_frame_t, context: *mut cef_v8context_t)>,
+    pub on_context_released: Option<extern "C" fn(self_: *mut cef_render_process_handler_t,
+        browser: *mut cef_browser_t, frame: *mut cef_frame_t, context: *mut cef_v8context_t)>,
+    pub on_uncaught_exception: Option<extern "C" fn(self_: *mut cef_render_process_handler_t,
+        browser: *mut cef_browser_t, frame: *mut cef_frame_t, context: *mut cef_v8context_t,
+        exception: *mut cef_v8exception_t, stack_trace: *mut cef_v8stack_trace_t)>,
+    pub on_focused_node_changed: Option<extern "C" fn(self_: *mut cef_render_process_handler_t,
+        browser: *mut cef_browser_t, frame: *mut cef_frame_t, node: *mut cef_domnode_t)>,
+    pub on_process_message_received: Option<extern "C" fn(self_: *mut cef_render_process_handler_t,
+        browser: *mut cef_browser_t, source_process: cef_process_id_t, message: *mut cef_process_message_t)
+        -> i32>,
+}
+
+// cef_resource_bundle_handler_capi.h
+
+#[repr(C)]
+pub struct cef_resource_bundle_handler_t {
+    pub base: cef_base_ref_counted_t,
+    pub get_localized_string: Option<extern "C" fn(self_: *mut cef_resource_bundle_handler_t, string_id: i32,
+        string: *mut cef_string_t) -> i32>,
+    pub get_data_resource: Option<extern "C" fn(self_: *mut cef_resource_bundle_handler_t, resource_id: i32,
+        data: *mut *mut c_void, data_size: *mut usize) -> i32>,
+    pub get_data_resource_for_scale: Option<extern "C" fn(self_: *mut cef_resource_bundle_handler_t,
+        resource_id: i32, scale_factor: cef_scale_factor_t, data: *mut *mut c_void, data_size: *mut usize)
+        -> i32>,
+}
+
+#[repr(C)]
+pub enum cef_scale_factor_t {
+  SCALE_FACTOR_NONE = 0,
+  SCALE_FACTOR_100P,
+  SCALE_FACTOR_125P,
+  SCALE_FACTOR_133P,
+  SCALE_FACTOR_140P,
+  SCALE_FACTOR_150P,
+  SCALE_FACTOR_180P,
+  SCALE_FACTOR_200P,
+  SCALE_FACTOR_250P,
+  SCALE_FACTOR_300P,
+}
+
+// cef_scheme_capi.h
+
+#[repr(C)]
+pub struct cef_scheme_registrar_t {
+    pub base: cef_base_scoped_t,
+    pub add_custom_scheme: Option<extern "C" fn(self_: *mut cef_scheme_registrar_t,
+        scheme_name: *const cef_string_t, is_standard: i32, is_local: i32, is_display_isolated: i32,
+        is_secure: i32, is_cors_enabled: i32, is_csp_bypassing: i32, is_fetch_enabled: i32) -> i32>,
+}
+
+// cef_command_line_capi.h
+
+#[repr(C)]
+pub struct cef_command_line_t {
+    pub base: cef_base_ref_counted_t,
+    pub is_valid: Option<extern "C" fn(self_: *mut cef_command_line_t) -> i32>,
+    pub is_read_only: Option<extern "C" fn(self_: *mut cef_command_line_t) -> i32>,
+    pub copy: Option<extern "C" fn(self_: *mut cef_command_line_t) -> *mut cef_command_line_t>,
+    pub init_from_argv: Option<extern "C" fn(self_: *mut cef_command_line_t, argc: i32,
+        argv: *const *const c_char)>,
+    pub init_from_string: Option<extern "C" fn(self_: *mut cef_command_line_t, command_line: *const cef_string_t)>,
+    pub reset: Option<extern "C" fn(self_: *mut cef_command_line_t)>,
+    pub get_argv: Option<extern "C" fn(self_: *mut cef_command_line_t, argv: cef_string_list_t)>,
+    pub get_command_line_string: Option<extern "C" fn(self_: *mut cef_command_line_t) -> cef_string_userfree_t>,
+    pub get_program: Option<extern "C" fn(self_: *mut cef_command_line_t) -> cef_string_userfree_t>,
+    pub set_program: Option<extern "C" fn(self_: *mut cef_command_line_t, program: *const cef_string_t)>,
+    pub has_switches: Option<extern "C" fn(self_: *mut cef_command_line_t) -> i32>,
+    pub has_switch: Option<extern "C" fn(self_: *mut cef_command_line_t, name: *const cef_string_t) -> i32>,
+    pub get_switch_value: Option<extern "C" fn(self_: *mut cef_command_line_t, name: *const cef_string_t)
+        -> cef_string_userfree_t>,
+    pub get_switches: Option<extern "C" fn(self_: *mut cef_command_line_t, switches: cef_string_map_t)>,
+    pub append_switch: Option<extern "C" fn(self_: *mut cef_command_line_t, name: *const cef_string_t)>,
+    pub append_switch_with_value: Option<extern "C" fn(self_: *mut cef_command_line_t, name: *const cef_string_t,
+        value: *const cef_string_t)>,
+    pub has_arguments: Option<extern "C" fn(self_: *mut cef_command_line_t) -> i32>,
+    pub get_arguments: Option<extern "C" fn(self_: *mut cef_command_line_t, arguments: cef_string_list_t)>,
+    pub append_argument: Option<extern "C" fn(self_: *mut cef_command_line_t, argument: *const cef_string_t)>,
+    pub prepend_wrapper: Option<extern "C" fn(self_: *mut cef_command_line_t, wrapper: *const cef_string_t)>,
+}
+
+// cef_values_capi.h
+
+#[repr(C)]
+pub struct cef_list_value_t {
+    pub base: cef_base_ref_counted_t,
+    pub is_valid: Option<extern "C" fn(self_: *mut cef_list_value_t) -> i32>,
+    pub is_owned: Option<extern "C" fn(self_: *mut cef_list_value_t) -> i32>,
+    pub is_read_only: Option<extern "C" fn(self_: *mut cef_list_value_t) -> i32>,
+    pub is_same: Option<extern "C" fn(self_: *mut cef_list_value_t, that: *mut cef_list_value_t) -> i32>,
+    pub is_equal: Option<extern "C" fn(self_: *mut cef_list_value_t, that: *mut cef_list_value_t) -> i32>,
+    pub copy: Option<extern "C" fn(self_: *mut cef_list_value_t) -> *mut cef_list_value_t>,
+    pub set_size: Option<extern "C" fn(self_: *mut cef_list_value_t, size: usize) -> i32>,
+    pub get_size: Option<extern "C" fn(self_: *mut cef_list_value_t) -> usize>,
+    pub clear: Option<extern "C" fn(self_: *mut cef_list_value_t) -> i32>,
+    pub remove: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize) -> i32>,
+    pub get_type: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize) -> cef_value_type_t>,
+    pub get_value: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize) -> *mut cef_value_t>,
+    pub get_bool: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize) -> i32>,
+    pub get_int: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize) -> i32>,
+    pub get_double: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize) -> f64>,
+    pub get_string: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize) -> cef_string_userfree_t>,
+    pub get_binary: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize) -> *mut cef_binary_value_t>,
+    pub get_dictionary: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize)
+        -> *mut cef_dictionary_value_t>,
+    pub get_list: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize) -> *mut cef_list_value_t>,
+    pub set_value: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize, value: *mut cef_value_t)
+        -> i32>,
+    pub set_null: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize) -> i32>,
+    pub set_bool: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize, value: i32) -> i32>,
+    pub set_int: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize, value: i32) -> i32>,
+    pub set_double: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize, value: f64) -> i32>,
+    pub set_string: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize, value: *const cef_string_t)
+        -> i32>,
+    pub set_binary: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize,
+        value: *mut cef_binary_value_t) -> i32>,
+    pub set_dictionary: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize,
+        value: *mut cef_dictionary_value_t) -> i32>,
+    pub set_list: Option<extern "C" fn(self_: *mut cef_list_value_t, index: usize, value: *mut cef_list_value_t)
+        -> i32>,
+}
+
+// cef_process_message_capi.h
+
+#[repr(C)]
+pub struct cef_process_message_t {
+    pub base: cef_base_ref_counted_t,
+    pub is_valid: Option<extern "C" fn(self_: *mut cef_process_message_t) -> i32>,
+    pub is_read_only: Option<extern "C" fn(self_: *mut cef_process_message_t) -> i32>,
+    pub copy: Option<extern "C" fn(self_: *mut cef_process_message_t) -> *mut cef_process_message_t>,
+    pub get_name: Option<extern "C" fn(self_: *mut cef_process_message_t) -> cef_string_userfree_t>,
+    pub get_argument_list: Option<extern "C" fn(self_: *mut cef_process_message_t) -> *mut cef_list_value_t>,
+}
+
+// cef_v8_capi.h
+
+#[repr(C)]
+pub struct cef_v8context_t {
+    pub base: cef_base_ref_counted_t,
+    pub get_task_runner: Option<extern "C" fn(self_: *mut cef_v8context_t) -> *mut cef_task_runner_t>,
+    pub is_valid: Option<extern "C" fn(self_: *mut cef_v8context_t) -> i32>,
+    pub get_browser: Option<extern "C" fn(self_: *mut cef_v8context_t) -> *mut cef_browser_t>,
+    pub get_frame: Option<extern "C" fn(self_: *mut cef_v8context_t) -> *mut cef_frame_t>,
+    pub get_global: Option<extern "C" fn(self_: *mut cef_v8context_t) -> *mut cef_v8value_t>,
+    pub enter: Option<extern "C" fn(self_: *mut cef_v8context_t) -> i32>,
+    pub exit: Option<extern "C" fn(self_: *mut cef_v8context_t) -> i32>,
+    pub is_same: Option<extern "C" fn(self_: *mut cef_v8context_t, that: *mut cef_v8context_t) -> i32>,
+    pub eval: Option<extern "C" fn(self_: *mut cef_v8context_t, code: *const cef_string_t,
+        script_url: *const cef_string_t, start_line: i32, retval: *mut *mut cef_v8value_t,
+        exception: *mut *mut cef_v8exception_t) -> i32>,
+}
+
+// Only the members up to get_double_value are declared, since the values are only read through the pointers
+// given by CEF.
+#[repr(C)]
+pub struct cef_v8value_t {
+    pub base: cef_base_ref_counted_t,
+    pub is_valid: Option<extern "C" fn(self_: *mut cef_v8value_t) -> i32>,
+    pub is_undefined: Option<extern "C" fn(self_: *mut cef_v8value_t) -> i32>,
+    pub is_null: Option<extern "C" fn(self_: *mut cef_v8value_t) -> i32>,
+    pub is_bool: Option<extern "C" fn(self_: *mut cef_v8value_t) -> i32>,
+    pub is_int: Option<extern "C" fn(self_: *mut cef_v8value_t) -> i32>,
+    pub is_uint: Option<extern "C" fn(self_: *mut cef_v8value_t) -> i32>,
+    pub is_double: Option<extern "C" fn(self_: *mut cef_v8value_t) -> i32>,
+    pub is_date: Option<extern "C" fn(self_: *mut cef_v8value_t) -> i32>,
+    pub is_string: Option<extern "C" fn(self_: *mut cef_v8value_t) -> i32>,
+    pub is_object: Option<extern "C" fn(self_: *mut cef_v8value_t) -> i32>,
+    pub is_array: Option<extern "C" fn(self_: *mut cef_v8value_t) -> i32>,
+    pub is_array_buffer: Option<extern "C" fn(self_: *mut cef_v8value_t) -> i32>,
+    pub is_function: Option<extern "C" fn(self_: *mut cef_v8value_t) -> i32>,
+    pub is_same: Option<extern "C" fn(self_: *mut cef_v8value_t, that: *mut cef_v8value_t) -> i32>,
+    pub get_bool_value: Option<extern "C" fn(self_: *mut cef_v8value_t) -> i32>,
+    pub get_int_value: Option<extern "C" fn(self_: *mut cef_v8value_t) -> i32>,
+    pub get_uint_value: Option<extern "C" fn(self_: *mut cef_v8value_t) -> u32>,
+    pub get_double_value: Option<extern "C" fn(self_: *mut cef_v8value_t) -> f64>,
+}
+
+// cef_task_capi.h
+
+#[repr(C)]
+pub struct cef_task_t {
+    pub base: cef_base_ref_counted_t,
+    pub execute: Option<extern "C" fn(self_: *mut cef_task_t)>,
+}
+
+// cef_print_handler_capi.h
+
+#[repr(C)]
+pub struct cef_print_handler_t {
+    pub base: cef_base_ref_counted_t,
+    pub on_print_start: Option<extern "C" fn(self_: *mut cef_print_handler_t, browser: *mut cef_browser_t)>,
+    pub on_print_settings: Option<extern "C" fn(self_: *mut cef_print_handler_t, browser: *mut cef_browser_t,
+        settings: *mut cef_print_settings_t, get_defaults: i32)>,
+    pub on_print_dialog: Option<extern "C" fn(self_: *mut cef_print_handler_t, browser: *mut cef_browser_t,
+        has_selection: i32, callback: *mut cef_print_dialog_callback_t) -> i32>,
+    pub on_print_job: Option<extern "C" fn(self_: *mut cef_print_handler_t, browser: *mut cef_browser_t,
+        document_name: *const cef_string_t, pdf_file_path: *const cef_string_t,
+        callback: *mut cef_print_job_callback_t) -> i32>,
+    pub on_print_reset: Option<extern "C" fn(self_: *mut cef_print_handler_t, browser: *mut cef_browser_t)>,
+    pub get_pdf_paper_size: Option<extern "C" fn(self_: *mut cef_print_handler_t, device_units_per_inch: i32)
+        -> cef_size_t>,
+}
+
+// cef_request_context_capi.h
+
+#[repr(C)]
+pub struct cef_request_context_t {
+    pub base: cef_base_ref_counted_t,
+    pub is_same: Option<extern "C" fn(self_: *mut cef_request_context_t, other: *mut cef_request_context_t) -> i32>,
+    pub is_sharing_with: Option<extern "C" fn(self_: *mut cef_request_context_t, other: *mut cef_request_context_t)
+        -> i32>,
+    pub is_global: Option<extern "C" fn(self_: *mut cef_request_context_t) -> i32>,
+    pub get_handler: Option<extern "C" fn(self_: *mut cef_request_context_t) -> *mut cef_request_context_handler_t>,
+    pub get_cache_path: Option<extern "C" fn(self_: *mut cef_request_context_t) -> cef_string_userfree_t>,
+    pub get_default_cookie_manager: Option<extern "C" fn(self_: *mut cef_request_context_t,
+        callback: *mut cef_completion_callback_t) -> *mut cef_cookie_manager_t>,
+    pub register_scheme_handler_factory: Option<extern "C" fn(self_: *mut cef_request_context_t,
+        scheme_name: *const cef_string_t, domain_name: *const cef_string_t,
+        factory: *mut cef_scheme_handler_factory_t) -> i32>,
+    pub clear_scheme_handler_factories: Option<extern "C" fn(self_: *mut cef_request_context_t) -> i32>,
+    pub purge_plugin_list_cache: Option<extern "C" fn(self_: *mut cef_request_context_t, reload_pages: i32)>,
+    pub has_preference: Option<extern "C" fn(self_: *mut cef_request_context_t, name: *const cef_string_t) -> i32>,
+    pub get_preference: Option<extern "C" fn(self_: *mut cef_request_context_t, name: *const cef_string_t)
+        -> *mut cef_value_t>,
+    pub get_all_preferences: Option<extern "C" fn(self_: *mut cef_request_context_t, include_defaults: i32)
+        -> *mut cef_dictionary_value_t>,
+    pub can_set_preference: Option<extern "C" fn(self_: *mut cef_request_context_t, name: *const cef_string_t)
+        -> i32>,
+    pub set_preference: Option<extern "C" fn(self_: *mut cef_request_context_t, name: *const cef_string_t,
+        value: *mut cef_value_t, error: *mut cef_string_t) -> i32>,
+    pub clear_certificate_exceptions: Option<extern "C" fn(self_: *mut cef_request_context_t,
+        callback: *mut cef_completion_callback_t)>,
+    pub close_all_connections: Option<extern "C" fn(self_: *mut cef_request_context_t,
+        callback: *mut cef_completion_callback_t)>,
+    pub resolve_host: Option<extern "C" fn(self_: *mut cef_request_context_t, origin: *const cef_string_t,
+        callback: *mut cef_resolve_callback_t)>,
+    pub resolve_host_cached: Option<extern "C" fn(self_: *mut cef_request_context_t, origin: *const cef_string_t,
+        resolved_ips: cef_string_list_t) -> cef_errorcode_t>,
+    pub load_extension: Option<extern "C" fn(self_: *mut cef_request_context_t, root_directory: *const cef_string_t,
+        manifest: *mut cef_dictionary_value_t, handler: *mut cef_extension_handler_t)>,
+    pub did_load_extension: Option<extern "C" fn(self_: *mut cef_request_context_t,
+        extension_id: *const cef_string_t) -> i32>,
+    pub has_extension: Option<extern "C" fn(self_: *mut cef_request_context_t, extension_id: *const cef_string_t)
+        -> i32>,
+    pub get_extensions: Option<extern "C" fn(self_: *mut cef_request_context_t, extension_ids: cef_string_list_t)
+        -> i32>,
+    pub get_extension: Option<extern "C" fn(self_: *mut cef_request_context_t, extension_id: *const cef_string_t)
+        -> *mut cef_extension_t>,
+}
+
+// cef_browser_capi.h
+
+#[repr(C)]
+pub struct cef_browser_t {
+    pub base: cef_base_ref_counted_t,
+    pub get_host: Option<extern "C" fn(self_: *mut cef_browser_t) -> *mut cef_browser_host_t>,
+    pub can_go_back: Option<extern "C" fn(self_: *mut cef_browser_t) -> i32>,
+    pub go_back: Option<extern "C" fn(self_: *mut cef_browser_t)>,
+    pub can_go_forward: Option<extern "C" fn(self_: *mut cef_browser_t) -> i32>,
+    pub go_forward: Option<extern "C" fn(self_: *mut cef_browser_t)>,
+    pub is_loading: Option<extern "C" fn(self_: *mut cef_browser_t) -> i32>,
+    pub reload: Option<extern "C" fn(self_: *mut cef_browser_t)>,
+    pub reload_ignore_cache: Option<extern "C" fn(self_: *mut cef_browser_t)>,
+    pub stop_load: Option<extern "C" fn(self_: *mut cef_browser_t)>,
+    pub get_identifier: Option<extern "C" fn(self_: *mut cef_browser_t) -> i32>,
+    pub is_same: Option<extern "C" fn(self_: *mut cef_browser_t, that: *mut cef_browser_t) -> i32>,
+    pub is_popup: Option<extern "C" fn(self_: *mut cef_browser_t) -> i32>,
+    pub has_document: Option<extern "C" fn(self_: *mut cef_browser_t) -> i32>,
+    pub get_main_frame: Option<extern "C" fn(self_: *mut cef_browser_t) -> *mut cef_frame_t>,
+    pub get_focused_frame: Option<extern "C" fn(self_: *mut cef_browser_t) -> *mut cef_frame_t>,
+    pub get_frame_byident: Option<extern "C" fn(self_: *mut cef_browser_t, identifier: i64) -> *mut cef_frame_t>,
+    pub get_frame: Option<extern "C" fn(self_: *mut cef_browser_t, name: *const cef_string_t) -> *mut cef_frame_t>,
+    pub get_frame_count: Option<extern "C" fn(self_: *mut cef_browser_t) -> usize>,
+    pub get_frame_identifiers: Option<extern "C" fn(self_: *mut cef_browser_t, identifiers_count: *mut usize,
+        identifiers: *mut i64)>,
+    pub get_frame_names: Option<extern "C" fn(self_: *mut cef_browser_t, names: cef_string_list_t)>,
+    pub send_process_message: Option<extern "C" fn(self_: *mut cef_browser_t, target_process: cef_process_id_t,
+        message: *mut cef_process_message_t) -> i32>,
+}
+
+#[repr(C)]
+pub struct cef_browser_host_t {
+    pub base: cef_base_ref_counted_t,
+    pub get_browser: Option<extern "C" fn(self_: *mut cef_browser_host_t) -> *mut cef_browser_t>,
+    pub close_browser: Option<extern "C" fn(self_: *mut cef_browser_host_t, force_close: i32)>,
+    pub try_close_browser: Option<extern "C" fn(self_: *mut cef_browser_host_t) -> i32>,
+    pub set_focus: Option<extern "C" fn(self_: *mut cef_browser_host_t, focus: i32)>,
+    pub get_window_handle: Option<extern "C" fn(self_: *mut cef_browser_host_t) -> cef_window_handle_t>,
+    pub get_opener_window_handle: Option<extern "C" fn(self_: *mut cef_browser_host_t) -> cef_window_handle_t>,
+    pub has_view: Option<extern "C" fn(self_: *mut cef_browser_host_t) -> i32>,
+    pub get_client: Option<extern "C" fn(self_: *mut cef_browser_host_t) -> *mut cef_client_t>,
+    pub get_request_context: Option<extern "C" fn(self_: *mut cef_browser_host_t) -> *mut cef_request_context_t>,
+    pub get_zoom_level: Option<extern "C" fn(self_: *mut cef_browser_host_t) -> f64>,
+    pub set_zoom_level: Option<extern "C" fn(self_: *mut cef_browser_host_t, zoom_level: f64)>,
+    pub run_file_dialog: Option<extern "C" fn(self_: *mut cef_browser_host_t, mode: cef_file_dialog_mode_t,
+        title: *const cef_string_t, default_file_path: *const cef_string_t, accept_filters: cef_string_list_t,
+        selected_accept_filter: i32, callback: *mut cef_run_file_dialog_callback_t)>,
+    pub start_download: Option<extern "C" fn(self_: *mut cef_browser_host_t, url: *const cef_string_t)>,
+    pub download_image: Option<extern "C" fn(self_: *mut cef_browser_host_t, image_url: *const cef_string_t,
+        is_favicon: i32, max_image_size: u32, bypass_cache: i32, callback: *mut cef_download_image_callback_t)>,
+    pub print: Option<extern "C" fn(self_: *mut cef_browser_host_t)>,
+    pub print_to_pdf: Option<extern "C" fn(self_: *mut cef_browser_host_t, path: *const cef_string_t,
+        settings: *const cef_pdf_print_settings_t, callback: *mut cef_pdf_print_callback_t)>,
+    pub find: Option<extern "C" fn(self_: *mut cef_browser_host_t, identifier: i32, search_text: *const cef_string_t,
+        forward: i32, match_case: i32, find_next: i32)>,
+    pub stop_finding: Option<extern "C" fn(self_: *mut cef_browser_host_t, clear_selection: i32)>,
+    pub show_dev_tools: Option<extern "C" fn(self_: *mut cef_browser_host_t, window_info: *const cef_window_info_t,
+        client: *mut cef_client_t, settings: *const cef_browser_settings_t, inspect_element_at: *const cef_point_t)>,
+    pub close_dev_tools: Option<extern "C" fn(self_: *mut cef_browser_host_t)>,
+    pub has_dev_tools: Option<extern "C" fn(self_: *mut cef_browser_host_t) -> i32>,
+    pub get_navigation_entries: Option<extern "C" fn(self_: *mut cef_browser_host_t,
+        visitor: *mut cef_navigation_entry_visitor_t, current_only: i32)>,
+    pub set_mouse_cursor_change_disabled: Option<extern "C" fn(self_: *mut cef_browser_host_t, disabled: i32)>,
+    pub is_mouse_cursor_change_disabled: Option<extern "C" fn(self_: *mut cef_browser_host_t) -> i32>,
+    pub replace_misspelling: Option<extern "C" fn(self_: *mut cef_browser_host_t, word: *const cef_string_t)>,
+    pub add_word_to_dictionary: Option<extern "C" fn(self_: *mut cef_browser_host_t, word: *const cef_string_t)>,
+    pub is_window_rendering_disabled: Option<extern "C" fn(self_: *mut cef_browser_host_t) -> i32>,
+    pub was_resized: Option<extern "C" fn(self_: *mut cef_browser_host_t)>,
+    pub was_hidden: Option<extern "C" fn(self_: *mut cef_browser_host_t, hidden: i32)>,
+    pub notify_screen_info_changed: Option<extern "C" fn(self_: *mut cef_browser_host_t)>,
+    pub invalidate: Option<extern "C" fn(self_: *mut cef_browser_host_t, type_: cef_paint_element_type_t)>,
+    pub send_external_begin_frame: Option<extern "C" fn(self_: *mut cef_browser_host_t)>,
+    pub send_key_event: Option<extern "C" fn(self_: *mut cef_browser_host_t, event: *const cef_key_event_t)>,
+    pub send_mouse_click_event: Option<extern "C" fn(self_: *mut cef_browser_host_t, event: *const cef_mouse_event_t,
+        type_: cef_mouse_button_type_t, mouse_up: i32, click_count: i32)>,
+    pub send_mouse_move_event: Option<extern "C" fn(self_: *mut cef_browser_host_t, event: *const cef_mouse_event_t,
+        mouse_leave: i32)>,
+    pub send_mouse_wheel_event: Option<extern "C" fn(self_: *mut cef_browser_host_t,
+        event: *const cef_mouse_event_t, delta_x: i32, delta_y: i32)>,
+    pub send_touch_event: Option<extern "C" fn(self_: *mut cef_browser_host_t, event: *const cef_touch_event_t)>,
+    pub send_focus_event: Option<extern "C" fn(self_: *mut cef_browser_host_t, set_focus: i32)>,
+    pub send_capture_lost_event: Option<extern "C" fn(self_: *mut cef_browser_host_t)>,
+    pub notify_move_or_resize_started: Option<extern "C" fn(self_: *mut cef_browser_host_t)>,
+    pub get_windowless_frame_rate: Option<extern "C" fn(self_: *mut cef_browser_host_t) -> i32>,
+    pub set_windowless_frame_rate: Option<extern "C" fn(self_: *mut cef_browser_host_t, frame_rate: i32)>,
+    pub ime_set_composition: Option<extern "C" fn(self_: *mut cef_browser_host_t, text: *const cef_string_t,
+        underlines_count: usize, underlines: *const cef_composition_underline_t,
+        replacement_range: *const cef_range_t, selection_range: *const cef_range_t)>,
+    pub ime_commit_text: Option<extern "C" fn(self_: *mut cef_browser_host_t, text: *const cef_string_t,
+        replacement_range: *const cef_range_t, relative_cursor_pos: i32)>,
+    pub ime_finish_composing_text: Option<extern "C" fn(self_: *mut cef_browser_host_t, keep_selection: i32)>,
+    pub ime_cancel_composition: Option<extern "C" fn(self_: *mut cef_browser_host_t)>,
+    pub drag_target_drag_enter: Option<extern "C" fn(self_: *mut cef_browser_host_t, drag_data: *mut cef_drag_data_t,
+        event: *const cef_mouse_event_t, allowed_ops: cef_drag_operations_mask_t)>,
+    pub drag_target_drag_over: Option<extern "C" fn(self_: *mut cef_browser_host_t, event: *const cef_mouse_event_t,
+        allowed_ops: cef_drag_operations_mask_t)>,
+    pub drag_target_drag_leave: Option<extern "C" fn(self_: *mut cef_browser_host_t)>,
+    pub drag_target_drop: Option<extern "C" fn(self_: *mut cef_browser_host_t, event: *const cef_mouse_event_t)>,
+    pub drag_source_ended_at: Option<extern "C" fn(self_: *mut cef_browser_host_t, x: i32, y: i32,
+        op: cef_drag_operations_mask_t)>,
+    pub drag_source_system_drag_ended: Option<extern "C" fn(self_: *mut cef_browser_host_t)>,
+    pub get_visible_navigation_entry: Option<extern "C" fn(self_: *mut cef_browser_host_t)
+        -> *mut cef_navigation_entry_t>,
+    pub set_accessibility_state: Option<extern "C" fn(self_: *mut cef_browser_host_t,
+        accessibility_state: cef_state_t)>,
+    pub set_auto_resize_enabled: Option<extern "C" fn(self_: *mut cef_browser_host_t, enabled: i32,
+        min_size: *const cef_size_t, max_size: *const cef_size_t)>,
+    pub get_extension: Option<extern "C" fn(self_: *mut cef_browser_host_t) -> *mut cef_extension_t>,
+    pub is_background_host: Option<extern "C" fn(self_: *mut cef_browser_host_t) -> i32>,
+}
+
+// cef_frame_capi.h
+
+#[repr(C)]
+pub struct cef_frame_t {
+    pub base: cef_base_ref_counted_t,
+    pub is_valid: Option<extern "C" fn(self_: *mut cef_frame_t) -> i32>,
+    pub undo: Option<extern "C" fn(self_: *mut cef_frame_t)>,
+    pub redo: Option<extern "C" fn(self_: *mut cef_frame_t)>,
+    pub cut: Option<extern "C" fn(self_: *mut cef_frame_t)>,
+    pub copy: Option<extern "C" fn(self_: *mut cef_frame_t)>,
+    pub paste: Option<extern "C" fn(self_: *mut cef_frame_t)>,
+    pub del: Option<extern "C" fn(self_: *mut cef_frame_t)>,
+    pub select_all: Option<extern "C" fn(self_: *mut cef_frame_t)>,
+    pub view_source: Option<extern "C" fn(self_: *mut cef_frame_t)>,
+    pub get_source: Option<extern "C" fn(self_: *mut cef_frame_t, visitor: *mut cef_string_visitor_t)>,
+    pub get_text: Option<extern "C" fn(self_: *mut cef_frame_t, visitor: *mut cef_string_visitor_t)>,
+    pub load_request: Option<extern "C" fn(self_: *mut cef_frame_t, request: *mut cef_request_t)>,
+    pub load_url: Option<extern "C" fn(self_: *mut cef_frame_t, url: *const cef_string_t)>,
+    pub load_string: Option<extern "C" fn(self_: *mut cef_frame_t, string_val: *const cef_string_t,
+        url: *const cef_string_t)>,
+    pub execute_java_script: Option<extern "C" fn(self_: *mut cef_frame_t, code: *const cef_string_t,
+        script_url: *const cef_string_t, start_line: i32)>,
+    pub is_main: Option<extern "C" fn(self_: *mut cef_frame_t) -> i32>,
+    pub is_focused: Option<extern "C" fn(self_: *mut cef_frame_t) -> i32>,
+    pub get_name: Option<extern "C" fn(self_: *mut cef_frame_t) -> cef_string_userfree_t>,
+    pub get_identifier: Option<extern "C" fn(self_: *mut cef_frame_t) -> i64>,
+    pub get_parent: Option<extern "C" fn(self_: *mut cef_frame_t) -> *mut cef_frame_t>,
+    pub get_url: Option<extern "C" fn(self_: *mut cef_frame_t) -> cef_string_userfree_t>,
+    pub get_browser: Option<extern "C" fn(self_: *mut cef_frame_t) -> *mut cef_browser_t>,
+    pub get_v8context: Option<extern "C" fn(self_: *mut cef_frame_t) -> *mut cef_v8context_t>,
+    pub visit_dom: Option<extern "C" fn(self_: *mut cef_frame_t, visitor: *mut cef_domvisitor_t)>,
+}
+
+// cef_client_capi.h
+
+#[repr(C)]
+pub struct cef_client_t {
+    pub base: cef_base_ref_counted_t,
+    pub get_context_menu_handler: Option<extern "C" fn(self_: *mut cef_client_t) -> *mut cef_context_menu_handler_t>,
+    pub get_dialog_handler: Option<extern "C" fn(self_: *mut cef_client_t) -> *mut cef_dialog_handler_t>,
+    pub get_display_handler: Option<extern "C" fn(self_: *mut cef_client_t) -> *mut cef_display_handler_t>,
+    pub get_download_handler: Option<extern "C" fn(self_: *mut cef_client_t) -> *mut cef_download_handler_t>,
+    pub get_drag_handler: Option<extern "C" fn(self_: *mut cef_client_t) -> *mut cef_drag_handler_t>,
+    pub get_find_handler: Option<extern "C" fn(self_: *mut cef_client_t) -> *mut cef_find_handler_t>,
+    pub get_focus_handler: Option<extern "C" fn(self_: *mut cef_client_t) -> *mut cef_focus_handler_t>,
+    pub get_jsdialog_handler: Option<extern "C" fn(self_: *mut cef_client_t) -> *mut cef_jsdialog_handler_t>,
+    pub get_keyboard_handler: Option<extern "C" fn(self_: *mut cef_client_t) -> *mut cef_keyboard_handler_t>,
+    pub get_life_span_handler: Option<extern "C" fn(self_: *mut cef_client_t) -> *mut cef_life_span_handler_t>,
+    pub get_load_handler: Option<extern "C" fn(self_: *mut cef_client_t) -> *mut cef_load_handler_t>,
+    pub get_render_handler: Option<extern "C" fn(self_: *mut cef_client_t) -> *mut cef_render_handler_t>,
+    pub get_request_handler: Option<extern "C" fn(self_: *mut cef_client_t) -> *mut cef_request_handler_t>,
+    pub on_process_message_received: Option<extern "C" fn(self_: *mut cef_client_t, browser: *mut cef_browser_t,
+        source_process: cef_process_id_t, message: *mut cef_process_message_t) -> i32>,
+}
+
+// cef_context_menu_handler_capi.h
+
+#[repr(C)]
+pub struct cef_context_menu_handler_t {
+    pub base: cef_base_ref_counted_t,
+    pub on_before_context_menu: Option<extern "C" fn(self_: *mut cef_context_menu_handler_t,
+        browser: *mut cef_browser_t, frame: *mut cef_frame_t, params: *mut cef_context_menu_params_t,
+        model: *mut cef_menu_model_t)>,
+    pub run_context_menu: Option<extern "C" fn(self_: *mut cef_context_menu_handler_t, browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t, params: *mut cef_context_menu_params_t, model: *mut cef_menu_model_t,
+        callback: *mut cef_run_context_menu_callback_t) -> i32>,
+    pub on_context_menu_command: Option<extern "C" fn(self_: *mut cef_context_menu_handler_t,
+        browser: *mut cef_browser_t, frame: *mut cef_frame_t, params: *mut cef_context_menu_params_t,
+        command_id: i32, event_flags: cef_event_flags_t) -> i32>,
+    pub on_context_menu_dismissed: Option<extern "C" fn(self_: *mut cef_context_menu_handler_t,
+        browser: *mut cef_browser_t, frame: *mut cef_frame_t)>,
+}
+
+#[repr(C)]
+pub struct cef_context_menu_params_t {
+    pub base: cef_base_ref_counted_t,
+    pub get_xcoord: Option<extern "C" fn(self_: *mut cef_context_menu_params_t) -> i32>,
+    pub get_ycoord: Option<extern "C" fn(self_: *mut cef_context_menu_params_t) -> i32>,
+    pub get_type_flags: Option<extern "C" fn(self_: *mut cef_context_menu_params_t)
+        -> cef_context_menu_type_flags_t>,
+    pub get_link_url: Option<extern "C" fn(self_: *mut cef_context_menu_params_t) -> cef_string_userfree_t>,
+    pub get_unfiltered_link_url: Option<extern "C" fn(self_: *mut cef_context_menu_params_t)
+        -> cef_string_userfree_t>,
+    pub get_source_url: Option<extern "C" fn(self_: *mut cef_context_menu_params_t) -> cef_string_userfree_t>,
+    pub has_image_contents: Option<extern "C" fn(self_: *mut cef_context_menu_params_t) -> i32>,
+    pub get_title_text: Option<extern "C" fn(self_: *mut cef_context_menu_params_t) -> cef_string_userfree_t>,
+    pub get_page_url: Option<extern "C" fn(self_: *mut cef_context_menu_params_t) -> cef_string_userfree_t>,
+    pub get_frame_url: Option<extern "C" fn(self_: *mut cef_context_menu_params_t) -> cef_string_userfree_t>,
+    pub get_frame_charset: Option<extern "C" fn(self_: *mut cef_context_menu_params_t) -> cef_string_userfree_t>,
+    pub get_media_type: Option<extern "C" fn(self_: *mut cef_context_menu_params_t)
+        -> cef_context_menu_media_type_t>,
+    pub get_media_state_flags: Option<extern "C" fn(self_: *mut cef_context_menu_params_t)
+        -> cef_context_menu_media_state_flags_t>,
+    pub get_selection_text: Option<extern "C" fn(self_: *mut cef_context_menu_params_t) -> cef_string_userfree_t>,
+    pub get_misspelled_word: Option<extern "C" fn(self_: *mut cef_context_menu_params_t) -> cef_string_userfree_t>,
+    pub get_dictionary_suggestions: Option<extern "C" fn(self_: *mut cef_context_menu_params_t,
+        suggestions: cef_string_list_t) -> i32>,
+    pub is_editable: Option<extern "C" fn(self_: *mut cef_context_menu_params_t) -> i32>,
+    pub is_spell_check_enabled: Option<extern "C" fn(self_: *mut cef_context_menu_params_t) -> i32>,
+    pub get_edit_state_flags: Option<extern "C" fn(self_: *mut cef_context_menu_params_t)
+        -> cef_context_menu_edit_state_flags_t>,
+    pub is_custom_menu: Option<extern "C" fn(self_: *mut cef_context_menu_params_t) -> i32>,
+    pub is_pepper_menu: Option<extern "C" fn(self_: *mut cef_context_menu_params_t) -> i32>,
+}
+
+// cef_menu_model_capi.h
+
+#[repr(C)]
+pub struct cef_menu_model_t {
+    pub base: cef_base_ref_counted_t,
+    pub is_sub_menu: Option<extern "C" fn(self_: *mut cef_menu_model_t) -> i32>,
+    pub clear: Option<extern "C" fn(self_: *mut cef_menu_model_t) -> i32>,
+    pub get_count: Option<extern "C" fn(self_: *mut cef_menu_model_t) -> i32>,
+    pub add_separator: Option<extern "C" fn(self_: *mut cef_menu_model_t) -> i32>,
+    pub add_item: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32, label: *const cef_string_t)
+        -> i32>,
+    pub add_check_item: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32,
+        label: *const cef_string_t) -> i32>,
+    pub add_radio_item: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32,
+        label: *const cef_string_t, group_id: i32) -> i32>,
+    pub add_sub_menu: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32,
+        label: *const cef_string_t) -> *mut cef_menu_model_t>,
+    pub insert_separator_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32) -> i32>,
+    pub insert_item_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32, command_id: i32,
+        label: *const cef_string_t) -> i32>,
+    pub insert_check_item_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32, command_id: i32,
+        label: *const cef_string_t) -> i32>,
+    pub insert_radio_item_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32, command_id: i32,
+        label: *const cef_string_t, group_id: i32) -> i32>,
+    pub insert_sub_menu_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32, command_id: i32,
+        label: *const cef_string_t) -> *mut cef_menu_model_t>,
+    pub remove: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32) -> i32>,
+    pub remove_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32) -> i32>,
+    pub get_index_of: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32) -> i32>,
+    pub get_command_id_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32) -> i32>,
+    pub set_command_id_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32, command_id: i32) -> i32>,
+    pub get_label: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32) -> cef_string_userfree_t>,
+    pub get_label_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32) -> cef_string_userfree_t>,
+    pub set_label: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32, label: *const cef_string_t)
+        -> i32>,
+    pub set_label_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32, label: *const cef_string_t)
+        -> i32>,
+    pub get_type: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32) -> cef_menu_item_type_t>,
+    pub get_type_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32) -> cef_menu_item_type_t>,
+    pub get_group_id: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32) -> i32>,
+    pub get_group_id_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32) -> i32>,
+    pub set_group_id: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32, group_id: i32) -> i32>,
+    pub set_group_id_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32, group_id: i32) -> i32>,
+    pub get_sub_menu: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32) -> *mut cef_menu_model_t>,
+    pub get_sub_menu_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32) -> *mut cef_menu_model_t>,
+    pub is_visible: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32) -> i32>,
+    pub is_visible_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32) -> i32>,
+    pub set_visible: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32, visible: i32) -> i32>,
+    pub set_visible_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32, visible: i32) -> i32>,
+    pub is_enabled: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32) -> i32>,
+    pub is_enabled_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32) -> i32>,
+    pub set_enabled: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32, enabled: i32) -> i32>,
+    pub set_enabled_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32, enabled: i32) -> i32>,
+    pub is_checked: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32) -> i32>,
+    pub is_checked_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32) -> i32>,
+    pub set_checked: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32, checked: i32) -> i32>,
+    pub set_checked_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32, checked: i32) -> i32>,
+    pub has_accelerator: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32) -> i32>,
+    pub has_accelerator_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32) -> i32>,
+    pub set_accelerator: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32, key_code: i32,
+        shift_pressed: i32, ctrl_pressed: i32, alt_pressed: i32) -> i32>,
+    pub set_accelerator_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32, key_code: i32,
+        shift_pressed: i32, ctrl_pressed: i32, alt_pressed: i32) -> i32>,
+    pub remove_accelerator: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32) -> i32>,
+    pub remove_accelerator_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32) -> i32>,
+    pub get_accelerator: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32, key_code: *mut i32,
+        shift_pressed: *mut i32, ctrl_pressed: *mut i32, alt_pressed: *mut i32) -> i32>,
+    pub get_accelerator_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32, key_code: *mut i32,
+        shift_pressed: *mut i32, ctrl_pressed: *mut i32, alt_pressed: *mut i32) -> i32>,
+    pub set_color: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32,
+        color_type: cef_menu_color_type_t, color: cef_color_t) -> i32>,
+    pub set_color_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32,
+        color_type: cef_menu_color_type_t, color: cef_color_t) -> i32>,
+    pub get_color: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32,
+        color_type: cef_menu_color_type_t, color: *mut cef_color_t) -> i32>,
+    pub get_color_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32,
+        color_type: cef_menu_color_type_t, color: *mut cef_color_t) -> i32>,
+    pub set_font_list: Option<extern "C" fn(self_: *mut cef_menu_model_t, command_id: i32,
+        font_list: *const cef_string_t) -> i32>,
+    pub set_font_list_at: Option<extern "C" fn(self_: *mut cef_menu_model_t, index: i32,
+        font_list: *const cef_string_t) -> i32>,
+}
+
+// cef_dialog_handler_capi.h
+
+#[repr(C)]
+pub struct cef_file_dialog_callback_t {
+    pub base: cef_base_ref_counted_t,
+    pub cont: Option<extern "C" fn(self_: *mut cef_file_dialog_callback_t, selected_accept_filter: i32,
+        file_paths: cef_string_list_t)>,
+    pub cancel: Option<extern "C" fn(self_: *mut cef_file_dialog_callback_t)>,
+}
+
+#[repr(C)]
+pub struct cef_dialog_handler_t {
+    pub base: cef_base_ref_counted_t,
+    pub on_file_dialog: Option<extern "C" fn(self_: *mut cef_dialog_handler_t, browser: *mut cef_browser_t,
+        mode: cef_file_dialog_mode_t, title: *const cef_string_t, default_file_path: *const cef_string_t,
+        accept_filters: cef_string_list_t, selected_accept_filter: i32, callback: *mut cef_file_dialog_callback_t)
+        -> i32>,
+}
+
+// cef_display_handler_capi.h
+
+#[repr(C)]
+pub struct cef_display_handler_t {
+    pub base: cef_base_ref_counted_t,
+    pub on_address_change: Option<extern "C" fn(self_: *mut cef_display_handler_t, browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t, url: *const cef_string_t)>,
+    pub on_title_change: Option<extern "C" fn(self_: *mut cef_display_handler_t, browser: *mut cef_browser_t,
+        title: *const cef_string_t)>,
+    pub on_favicon_urlchange: Option<extern "C" fn(self_: *mut cef_display_handler_t, browser: *mut cef_browser_t,
+        icon_urls: cef_string_list_t)>,
+    pub on_fullscreen_mode_change: Option<extern "C" fn(self_: *mut cef_display_handler_t,
+        browser: *mut cef_browser_t, fullscreen: i32)>,
+    pub on_tooltip: Option<extern "C" fn(self_: *mut cef_display_handler_t, browser: *mut cef_browser_t,
+        text: *mut cef_string_t) -> i32>,
+    pub on_status_message: Option<extern "C" fn(self_: *mut cef_display_handler_t, browser: *mut cef_browser_t,
+        value: *const cef_string_t)>,
+    pub on_console_message: Option<extern "C" fn(self_: *mut cef_display_handler_t, browser: *mut cef_browser_t,
+        level: cef_log_severity_t, message: *const cef_string_t, source: *const cef_string_t, line: i32) -> i32>,
+    pub on_auto_resize: Option<extern "C" fn(self_: *mut cef_display_handler_t, browser: *mut cef_browser_t,
+        new_size: *const cef_size_t) -> i32>,
+    pub on_loading_progress_change: Option<extern "C" fn(self_: *mut cef_display_handler_t,
+        browser: *mut cef_browser_t, progress: f64)>,
+}
+
+// cef_download_handler_capi.h and cef_download_item_capi.h
+
+#[repr(C)]
+pub struct cef_before_download_callback_t {
+    pub base: cef_base_ref_counted_t,
+    pub cont: Option<extern "C" fn(self_: *mut cef_before_download_callback_t, download_path: *const cef_string_t,
+        show_dialog: i32)>,
+}
+
+#[repr(C)]
+pub struct cef_download_item_callback_t {
+    pub base: cef_base_ref_counted_t,
+    pub cancel: Option<extern "C" fn(self_: *mut cef_download_item_callback_t)>,
+    pub pause: Option<extern "C" fn(self_: *mut cef_download_item_callback_t)>,
+    pub resume: Option<extern "C" fn(self_: *mut cef_download_item_callback_t)>,
+}
+
+#[repr(C)]
+pub struct cef_download_handler_t {
+    pub base: cef_base_ref_counted_t,
+    pub on_before_download: Option<extern "C" fn(self_: *mut cef_download_handler_t, browser: *mut cef_browser_t,
+        download_item: *mut cef_download_item_t, suggested_name: *const cef_string_t,
+        callback: *mut cef_before_download_callback_t)>,
+    pub on_download_updated: Option<extern "C" fn(self_: *mut cef_download_handler_t, browser: *mut cef_browser_t,
+        download_item: *mut cef_download_item_t, callback: *mut cef_download_item_callback_t)>,
+}
+
+#[repr(C)]
+pub struct cef_download_item_t {
+    pub base: cef_base_ref_counted_t,
+    pub is_valid: Option<extern "C" fn(self_: *mut cef_download_item_t) -> i32>,
+    pub is_in_progress: Option<extern "C" fn(self_: *mut cef_download_item_t) -> i32>,
+    pub is_complete: Option<extern "C" fn(self_: *mut cef_download_item_t) -> i32>,
+    pub is_canceled: Option<extern "C" fn(self_: *mut cef_download_item_t) -> i32>,
+    pub get_current_speed: Option<extern "C" fn(self_: *mut cef_download_item_t) -> i64>,
+    pub get_percent_complete: Option<extern "C" fn(self_: *mut cef_download_item_t) -> i32>,
+    pub get_total_bytes: Option<extern "C" fn(self_: *mut cef_download_item_t) -> i64>,
+    pub get_received_bytes: Option<extern "C" fn(self_: *mut cef_download_item_t) -> i64>,
+    pub get_start_time: Option<extern "C" fn(self_: *mut cef_download_item_t) -> cef_time_t>,
+    pub get_end_time: Option<extern "C" fn(self_: *mut cef_download_item_t) -> cef_time_t>,
+    pub get_full_path: Option<extern "C" fn(self_: *mut cef_download_item_t) -> cef_string_userfree_t>,
+    pub get_id: Option<extern "C" fn(self_: *mut cef_download_item_t) -> u32>,
+    pub get_url: Option<extern "C" fn(self_: *mut cef_download_item_t) -> cef_string_userfree_t>,
+    pub get_original_url: Option<extern "C" fn(self_: *mut cef_download_item_t) -> cef_string_userfree_t>,
+    pub get_suggested_file_name: Option<extern "C" fn(self_: *mut cef_download_item_t) -> cef_string_userfree_t>,
+    pub get_content_disposition: Option<extern "C" fn(self_: *mut cef_download_item_t) -> cef_string_userfree_t>,
+    pub get_mime_type: Option<extern "C" fn(self_: *mut cef_download_item_t) -> cef_string_userfree_t>,
+}
+
+// cef_drag_handler_capi.h and cef_drag_data_capi.h
+
+#[repr(C)]
+pub struct cef_drag_handler_t {
+    pub base: cef_base_ref_counted_t,
+    pub on_drag_enter: Option<extern "C" fn(self_: *mut cef_drag_handler_t, browser: *mut cef_browser_t,
+        drag_data: *mut cef_drag_data_t, mask: cef_drag_operations_mask_t) -> i32>,
+    pub on_draggable_regions_changed: Option<extern "C" fn(self_: *mut cef_drag_handler_t,
+        browser: *mut cef_browser_t, regions_count: usize, regions: *const cef_draggable_region_t)>,
+}
+
+#[repr(C)]
+pub struct cef_drag_data_t {
+    pub base: cef_base_ref_counted_t,
+    pub clone: Option<extern "C" fn(self_: *mut cef_drag_data_t) -> *mut cef_drag_data_t>,
+    pub is_read_only: Option<extern "C" fn(self_: *mut cef_drag_data_t) -> i32>,
+    pub is_link: Option<extern "C" fn(self_: *mut cef_drag_data_t) -> i32>,
+    pub is_fragment: Option<extern "C" fn(self_: *mut cef_drag_data_t) -> i32>,
+    pub is_file: Option<extern "C" fn(self_: *mut cef_drag_data_t) -> i32>,
+    pub get_link_url: Option<extern "C" fn(self_: *mut cef_drag_data_t) -> cef_string_userfree_t>,
+    pub get_link_title: Option<extern "C" fn(self_: *mut cef_drag_data_t) -> cef_string_userfree_t>,
+    pub get_link_metadata: Option<extern "C" fn(self_: *mut cef_drag_data_t) -> cef_string_userfree_t>,
+    pub get_fragment_text: Option<extern "C" fn(self_: *mut cef_drag_data_t) -> cef_string_userfree_t>,
+    pub get_fragment_html: Option<extern "C" fn(self_: *mut cef_drag_data_t) -> cef_string_userfree_t>,
+    pub get_fragment_base_url: Option<extern "C" fn(self_: *mut cef_drag_data_t) -> cef_string_userfree_t>,
+    pub get_file_name: Option<extern "C" fn(self_: *mut cef_drag_data_t) -> cef_string_userfree_t>,
+    pub get_file_contents: Option<extern "C" fn(self_: *mut cef_drag_data_t, writer: *mut cef_stream_writer_t)
+        -> usize>,
+    pub get_file_names: Option<extern "C" fn(self_: *mut cef_drag_data_t, names: cef_string_list_t) -> i32>,
+    pub set_link_url: Option<extern "C" fn(self_: *mut cef_drag_data_t, url: *const cef_string_t)>,
+    pub set_link_title: Option<extern "C" fn(self_: *mut cef_drag_data_t, title: *const cef_string_t)>,
+    pub set_link_metadata: Option<extern "C" fn(self_: *mut cef_drag_data_t, data: *const cef_string_t)>,
+    pub set_fragment_text: Option<extern "C" fn(self_: *mut cef_drag_data_t, text: *const cef_string_t)>,
+    pub set_fragment_html: Option<extern "C" fn(self_: *mut cef_drag_data_t, html: *const cef_string_t)>,
+    pub set_fragment_base_url: Option<extern "C" fn(self_: *mut cef_drag_data_t, base_url: *const cef_string_t)>,
+    pub reset_file_contents: Option<extern "C" fn(self_: *mut cef_drag_data_t)>,
+    pub add_file: Option<extern "C" fn(self_: *mut cef_drag_data_t, path: *const cef_string_t,
+        display_name: *const cef_string_t)>,
+    pub get_image: Option<extern "C" fn(self_: *mut cef_drag_data_t) -> *mut cef_image_t>,
+    pub get_image_hotspot: Option<extern "C" fn(self_: *mut cef_drag_data_t) -> cef_point_t>,
+    pub has_image: Option<extern "C" fn(self_: *mut cef_drag_data_t) -> i32>,
+}
+
+// cef_find_handler_capi.h
+
+#[repr(C)]
+pub struct cef_find_handler_t {
+    pub base: cef_base_ref_counted_t,
+    pub on_find_result: Option<extern "C" fn(self_: *mut cef_find_handler_t, browser: *mut cef_browser_t,
+        identifier: i32, count: i32, selection_rect: *const cef_rect_t, active_match_ordinal: i32,
+        final_update: i32)>,
+}
+
+// cef_focus_handler_capi.h
+
+#[repr(C)]
+pub struct cef_focus_handler_t {
+    pub base: cef_base_ref_counted_t,
+    pub on_take_focus: Option<extern "C" fn(self_: *mut cef_focus_handler_t, browser: *mut cef_browser_t,
+        next: i32)>,
+    pub on_set_focus: Option<extern "C" fn(self_: *mut cef_focus_handler_t, browser: *mut cef_browser_t,
+        source: cef_focus_source_t) -> i32>,
+    pub on_got_focus: Option<extern "C" fn(self_: *mut cef_focus_handler_t, browser: *mut cef_browser_t)>,
+}
+
+// cef_jsdialog_handler_capi.h
+
+#[repr(C)]
+pub struct cef_jsdialog_callback_t {
+    pub base: cef_base_ref_counted_t,
+    pub cont: Option<extern "C" fn(self_: *mut cef_jsdialog_callback_t, success: i32,
+        user_input: *const cef_string_t)>,
+}
+
+#[repr(C)]
+pub struct cef_jsdialog_handler_t {
+    pub base: cef_base_ref_counted_t,
+    pub on_jsdialog: Option<extern "C" fn(self_: *mut cef_jsdialog_handler_t, browser: *mut cef_browser_t,
+        origin_url: *const cef_string_t, dialog_type: cef_jsdialog_type_t, message_text: *const cef_string_t,
+        default_prompt_text: *const cef_string_t, callback: *mut cef_jsdialog_callback_t,
+        suppress_message: *mut i32) -> i32>,
+    pub on_before_unload_dialog: Option<extern "C" fn(self_: *mut cef_jsdialog_handler_t,
+        browser: *mut cef_browser_t, message_text: *const cef_string_t, is_reload: i32,
+        callback: *mut cef_jsdialog_callback_t) -> i32>,
+    pub on_reset_dialog_state: Option<extern "C" fn(self_: *mut cef_jsdialog_handler_t,
+        browser: *mut cef_browser_t)>,
+    pub on_dialog_closed: Option<extern "C" fn(self_: *mut cef_jsdialog_handler_t, browser: *mut cef_browser_t)>,
+}
+
+// cef_keyboard_handler_capi.h
+
+#[repr(C)]
+pub struct cef_keyboard_handler_t {
+    pub base: cef_base_ref_counted_t,
+    pub on_pre_key_event: Option<extern "C" fn(self_: *mut cef_keyboard_handler_t, browser: *mut cef_browser_t,
+        event: *const cef_key_event_t, os_event: cef_event_handle_t, is_keyboard_shortcut: *mut i32) -> i32>,
+    pub on_key_event: Option<extern "C" fn(self_: *mut cef_keyboard_handler_t, browser: *mut cef_browser_t,
+        event: *const cef_key_event_t, os_event: cef_event_handle_t) -> i32>,
+}
+
+// cef_life_span_handler_capi.h
+
+#[repr(C)]
+pub struct cef_life_span_handler_t {
+    pub base: cef_base_ref_counted_t,
+    pub on_before_popup: Option<extern "C" fn(self_: *mut cef_life_span_handler_t, browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t, target_url: *const cef_string_t, target_frame_name: *const cef_string_t,
+        target_disposition: cef_window_open_disposition_t, user_gesture: i32,
+        popup_features: *const cef_popup_features_t, window_info: *mut cef_window_info_t,
+        client: *mut *mut cef_client_t, settings: *mut cef_browser_settings_t, no_javascript_access: *mut i32)
+        -> i32>,
+    pub on_after_created: Option<extern "C" fn(self_: *mut cef_life_span_handler_t, browser: *mut cef_browser_t)>,
+    pub do_close: Option<extern "C" fn(self_: *mut cef_life_span_handler_t, browser: *mut cef_browser_t) -> i32>,
+    pub on_before_close: Option<extern "C" fn(self_: *mut cef_life_span_handler_t, browser: *mut cef_browser_t)>,
+}
+
+// cef_load_handler_capi.h
+
+#[repr(C)]
+pub struct cef_load_handler_t {
+    pub base: cef_base_ref_counted_t,
+    pub on_loading_state_change: Option<extern "C" fn(self_: *mut cef_load_handler_t, browser: *mut cef_browser_t,
+        is_loading: i32, can_go_back: i32, can_go_forward: i32)>,
+    pub on_load_start: Option<extern "C" fn(self_: *mut cef_load_handler_t, browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t, transition_type: cef_transition_type_t)>,
+    pub on_load_end: Option<extern "C" fn(self_: *mut cef_load_handler_t, browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t, http_status_code: i32)>,
+    pub on_load_error: Option<extern "C" fn(self_: *mut cef_load_handler_t, browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t, error_code: cef_errorcode_t, error_text: *const cef_string_t,
+        failed_url: *const cef_string_t)>,
+}
+
+// cef_render_handler_capi.h
+
+#[repr(C)]
+pub struct cef_render_handler_t {
+    pub base: cef_base_ref_counted_t,
+    pub get_accessibility_handler: Option<extern "C" fn(self_: *mut cef_render_handler_t)
+        -> *mut cef_accessibility_handler_t>,
+    pub get_root_screen_rect: Option<extern "C" fn(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        rect: *mut cef_rect_t) -> i32>,
+    pub get_view_rect: Option<extern "C" fn(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        rect: *mut cef_rect_t)>,
+    pub get_screen_point: Option<extern "C" fn(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        view_x: i32, view_y: i32, screen_x: *mut i32, screen_y: *mut i32) -> i32>,
+    pub get_screen_info: Option<extern "C" fn(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        screen_info: *mut cef_screen_info_t) -> i32>,
+    pub on_popup_show: Option<extern "C" fn(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        show: i32)>,
+    pub on_popup_size: Option<extern "C" fn(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        rect: *const cef_rect_t)>,
+    pub on_paint: Option<extern "C" fn(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        type_: cef_paint_element_type_t, dirty_rects_count: usize, dirty_rects: *const cef_rect_t,
+        buffer: *const c_void, width: i32, height: i32)>,
+    pub on_accelerated_paint: Option<extern "C" fn(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        type_: cef_paint_element_type_t, dirty_rects_count: usize, dirty_rects: *const cef_rect_t,
+        shared_handle: *mut c_void)>,
+    pub on_cursor_change: Option<extern "C" fn(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        cursor: cef_cursor_handle_t, type_: cef_cursor_type_t, custom_cursor_info: *const cef_cursor_info_t)>,
+    pub start_dragging: Option<extern "C" fn(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        drag_data: *mut cef_drag_data_t, allowed_ops: cef_drag_operations_mask_t, x: i32, y: i32) -> i32>,
+    pub update_drag_cursor: Option<extern "C" fn(self_: *mut cef_render_handler_t, browser: *mut cef_browser_t,
+        operation: cef_drag_operations_mask_t)>,
+    pub on_scroll_offset_changed: Option<extern "C" fn(self_: *mut cef_render_handler_t,
+        browser: *mut cef_browser_t, x: f64, y: f64)>,
+    pub on_ime_composition_range_changed: Option<extern "C" fn(self_: *mut cef_render_handler_t,
+        browser: *mut cef_browser_t, selected_range: *const cef_range_t, character_bounds_count: usize,
+        character_bounds: *const cef_rect_t)>,
+    pub on_text_selection_changed: Option<extern "C" fn(self_: *mut cef_render_handler_t,
+        browser: *mut cef_browser_t, selected_text: *const cef_string_t, selected_range: *const cef_range_t)>,
+}
+
+// views/cef_view_delegate_capi.h, views/cef_panel_delegate_capi.h and views/cef_window_delegate_capi.h
+
+#[repr(C)]
+pub struct cef_view_delegate_t {
+    pub base: cef_base_ref_counted_t,
+    pub get_preferred_size: Option<extern "C" fn(self_: *mut cef_view_delegate_t, view: *mut cef_view_t)
+        -> cef_size_t>,
+    pub get_minimum_size: Option<extern "C" fn(self_: *mut cef_view_delegate_t, view: *mut cef_view_t)
+        -> cef_size_t>,
+    pub get_maximum_size: Option<extern "C" fn(self_: *mut cef_view_delegate_t, view: *mut cef_view_t)
+        -> cef_size_t>,
+    pub get_height_for_width: Option<extern "C" fn(self_: *mut cef_view_delegate_t, view: *mut cef_view_t,
+        width: i32) -> i32>,
+    pub on_parent_view_changed: Option<extern "C" fn(self_: *mut cef_view_delegate_t, view: *mut cef_view_t,
+        added: i32, parent: *mut cef_view_t)>,
+    pub on_child_view_changed: Option<extern "C" fn(self_: *mut cef_view_delegate_t, view: *mut cef_view_t,
+        added: i32, child: *mut cef_view_t)>,
+    pub on_focus: Option<extern "C" fn(self_: *mut cef_view_delegate_t, view: *mut cef_view_t)>,
+    pub on_blur: Option<extern "C" fn(self_: *mut cef_view_delegate_t, view: *mut cef_view_t)>,
+}
+
+#[repr(C)]
+pub struct cef_panel_delegate_t {
+    pub base: cef_view_delegate_t,
+}
+
+#[repr(C)]
+pub struct cef_window_delegate_t {
+    pub base: cef_panel_delegate_t,
+    pub on_window_created: Option<extern "C" fn(self_: *mut cef_window_delegate_t, window: *mut cef_window_t)>,
+    pub on_window_destroyed: Option<extern "C" fn(self_: *mut cef_window_delegate_t, window: *mut cef_window_t)>,
+    pub get_parent_window: Option<extern "C" fn(self_: *mut cef_window_delegate_t, window: *mut cef_window_t,
+        is_menu: *mut i32, can_activate_menu: *mut i32) -> *mut cef_window_t>,
+    pub is_frameless: Option<extern "C" fn(self_: *mut cef_window_delegate_t, window: *mut cef_window_t) -> i32>,
+    pub can_resize: Option<extern "C" fn(self_: *mut cef_window_delegate_t, window: *mut cef_window_t) -> i32>,
+    pub can_maximize: Option<extern "C" fn(self_: *mut cef_window_delegate_t, window: *mut cef_window_t) -> i32>,
+    pub can_minimize: Option<extern "C" fn(self_: *mut cef_window_delegate_t, window: *mut cef_window_t) -> i32>,
+    pub can_close: Option<extern "C" fn(self_: *mut cef_window_delegate_t, window: *mut cef_window_t) -> i32>,
+    pub on_accelerator: Option<extern "C" fn(self_: *mut cef_window_delegate_t, window: *mut cef_window_t,
+        command_id: i32) -> i32>,
+    pub on_key_event: Option<extern "C" fn(self_: *mut cef_window_delegate_t, window: *mut cef_window_t,
+        event: *const cef_key_event_t) -> i32>,
+}
+
+// views/cef_browser_view_delegate_capi.h
+
+#[repr(C)]
+pub struct cef_browser_view_delegate_t {
+    pub base: cef_view_delegate_t,
+    pub on_browser_created: Option<extern "C" fn(self_: *mut cef_browser_view_delegate_t,
+        browser_view: *mut cef_browser_view_t, browser: *mut cef_browser_t)>,
+    pub on_browser_destroyed: Option<extern "C" fn(self_: *mut cef_browser_view_delegate_t,
+        browser_view: *mut cef_browser_view_t, browser: *mut cef_browser_t)>,
+    pub get_delegate_for_popup_browser_view: Option<extern "C" fn(self_: *mut cef_browser_view_delegate_t,
+        browser_view: *mut cef_browser_view_t, settings: *const cef_browser_settings_t, client: *mut cef_client_t,
+        is_devtools: i32) -> *mut cef_browser_view_delegate_t>,
+    pub on_popup_browser_view_created: Option<extern "C" fn(self_: *mut cef_browser_view_delegate_t,
+        browser_view: *mut cef_browser_view_t, popup_browser_view: *mut cef_browser_view_t, is_devtools: i32)
+        -> i32>,
+}
+
+// views/cef_view_capi.h
+
+#[repr(C)]
+pub struct cef_view_t {
+    pub base: cef_base_ref_counted_t,
+    pub as_browser_view: Option<extern "C" fn(self_: *mut cef_view_t) -> *mut cef_browser_view_t>,
+    pub as_button: Option<extern "C" fn(self_: *mut cef_view_t) -> *mut cef_button_t>,
+    pub as_panel: Option<extern "C" fn(self_: *mut cef_view_t) -> *mut cef_panel_t>,
+    pub as_scroll_view: Option<extern "C" fn(self_: *mut cef_view_t) -> *mut cef_scroll_view_t>,
+    pub as_textfield: Option<extern "C" fn(self_: *mut cef_view_t) -> *mut cef_textfield_t>,
+    pub get_type_string: Option<extern "C" fn(self_: *mut cef_view_t) -> cef_string_userfree_t>,
+    pub to_string: Option<extern "C" fn(self_: *mut cef_view_t, include_children: i32) -> cef_string_userfree_t>,
+    pub is_valid: Option<extern "C" fn(self_: *mut cef_view_t) -> i32>,
+    pub is_attached: Option<extern "C" fn(self_: *mut cef_view_t) -> i32>,
+    pub is_same: Option<extern "C" fn(self_: *mut cef_view_t, that: *mut cef_view_t) -> i32>,
+    pub get_delegate: Option<extern "C" fn(self_: *mut cef_view_t) -> *mut cef_view_delegate_t>,
+    pub get_window: Option<extern "C" fn(self_: *mut cef_view_t) -> *mut cef_window_t>,
+    pub get_id: Option<extern "C" fn(self_: *mut cef_view_t) -> i32>,
+    pub set_id: Option<extern "C" fn(self_: *mut cef_view_t, id: i32)>,
+    pub get_group_id: Option<extern "C" fn(self_: *mut cef_view_t) -> i32>,
+    pub set_group_id: Option<extern "C" fn(self_: *mut cef_view_t, group_id: i32)>,
+    pub get_parent_view: Option<extern "C" fn(self_: *mut cef_view_t) -> *mut cef_view_t>,
+    pub get_view_for_id: Option<extern "C" fn(self_: *mut cef_view_t, id: i32) -> *mut cef_view_t>,
+    pub set_bounds: Option<extern "C" fn(self_: *mut cef_view_t, bounds: *const cef_rect_t)>,
+    pub get_bounds: Option<extern "C" fn(self_: *mut cef_view_t) -> cef_rect_t>,
+    pub get_bounds_in_screen: Option<extern "C" fn(self_: *mut cef_view_t) -> cef_rect_t>,
+    pub set_size: Option<extern "C" fn(self_: *mut cef_view_t, size: *const cef_size_t)>,
+    pub get_size: Option<extern "C" fn(self_: *mut cef_view_t) -> cef_size_t>,
+    pub set_position: Option<extern "C" fn(self_: *mut cef_view_t, position: *const cef_point_t)>,
+    pub get_position: Option<extern "C" fn(self_: *mut cef_view_t) -> cef_point_t>,
+    pub get_preferred_size: Option<extern "C" fn(self_: *mut cef_view_t) -> cef_size_t>,
+    pub size_to_preferred_size: Option<extern "C" fn(self_: *mut cef_view_t)>,
+    pub get_minimum_size: Option<extern "C" fn(self_: *mut cef_view_t) -> cef_size_t>,
+    pub get_maximum_size: Option<extern "C" fn(self_: *mut cef_view_t) -> cef_size_t>,
+    pub get_height_for_width: Option<extern "C" fn(self_: *mut cef_view_t, width: i32) -> i32>,
+    pub invalidate_layout: Option<extern "C" fn(self_: *mut cef_view_t)>,
+    pub set_visible: Option<extern "C" fn(self_: *mut cef_view_t, visible: i32)>,
+    pub is_visible: Option<extern "C" fn(self_: *mut cef_view_t) -> i32>,
+    pub is_drawn: Option<extern "C" fn(self_: *mut cef_view_t) -> i32>,
+    pub set_enabled: Option<extern "C" fn(self_: *mut cef_view_t, enabled: i32)>,
+    pub is_enabled: Option<extern "C" fn(self_: *mut cef_view_t) -> i32>,
+    pub set_focusable: Option<extern "C" fn(self_: *mut cef_view_t, focusable: i32)>,
+    pub is_focusable: Option<extern "C" fn(self_: *mut cef_view_t) -> i32>,
+    pub is_accessibility_focusable: Option<extern "C" fn(self_: *mut cef_view_t) -> i32>,
+    pub request_focus: Option<extern "C" fn(self_: *mut cef_view_t)>,
+    pub set_background_color: Option<extern "C" fn(self_: *mut cef_view_t, color: cef_color_t)>,
+    pub get_background_color: Option<extern "C" fn(self_: *mut cef_view_t) -> cef_color_t>,
+    pub convert_point_to_screen: Option<extern "C" fn(self_: *mut cef_view_t, point: *mut cef_point_t) -> i32>,
+    pub convert_point_from_screen: Option<extern "C" fn(self_: *mut cef_view_t, point: *mut cef_point_t) -> i32>,
+    pub convert_point_to_window: Option<extern "C" fn(self_: *mut cef_view_t, point: *mut cef_point_t) -> i32>,
+    pub convert_point_from_window: Option<extern "C" fn(self_: *mut cef_view_t, point: *mut cef_point_t) -> i32>,
+    pub convert_point_to_view: Option<extern "C" fn(self_: *mut cef_view_t, view: *mut cef_view_t,
+        point: *mut cef_point_t) -> i32>,
+    pub convert_point_from_view: Option<extern "C" fn(self_: *mut cef_view_t, view: *mut cef_view_t,
+        point: *mut cef_point_t) -> i32>,
+}
+
+// views/cef_browser_view_capi.h
+
+#[repr(C)]
+pub struct cef_browser_view_t {
+    pub base: cef_view_t,
+    pub get_browser: Option<extern "C" fn(self_: *mut cef_browser_view_t) -> *mut cef_browser_t>,
+    pub set_prefer_accelerators: Option<extern "C" fn(self_: *mut cef_browser_view_t, prefer_accelerators: i32)>,
+}
+
+// views/cef_layout_capi.h, views/cef_box_layout_capi.h and views/cef_fill_layout_capi.h
+
+#[repr(C)]
+pub struct cef_layout_t {
+    pub base: cef_base_ref_counted_t,
+    pub as_box_layout: Option<extern "C" fn(self_: *mut cef_layout_t) -> *mut cef_box_layout_t>,
+    pub as_fill_layout: Option<extern "C" fn(self_: *mut cef_layout_t) -> *mut cef_fill_layout_t>,
+    pub is_valid: Option<extern "C" fn(self_: *mut cef_layout_t) -> i32>,
+}
+
+#[repr(C)]
+pub struct cef_box_layout_t {
+    pub base: cef_layout_t,
+    pub set_flex_for_view: Option<extern "C" fn(self_: *mut cef_box_layout_t, view: *mut cef_view_t, flex: i32)>,
+    pub clear_flex_for_view: Option<extern "C" fn(self_: *mut cef_box_layout_t, view: *mut cef_view_t)>,
+}
+
+#[repr(C)]
+pub struct cef_fill_layout_t {
+    pub base: cef_layout_t,
+}
+
+// views/cef_panel_capi.h
+
+#[repr(C)]
+pub struct cef_panel_t {
+    pub base: cef_view_t,
+    pub as_window: Option<extern "C" fn(self_: *mut cef_panel_t) -> *mut cef_window_t>,
+    pub set_to_fill_layout: Option<extern "C" fn(self_: *mut cef_panel_t) -> *mut cef_fill_layout_t>,
+    pub set_to_box_layout: Option<extern "C" fn(self_: *mut cef_panel_t, settings: *const cef_box_layout_settings_t)
+        -> *mut cef_box_layout_t>,
+    pub get_layout: Option<extern "C" fn(self_: *mut cef_panel_t) -> *mut cef_layout_t>,
+    pub layout: Option<extern "C" fn(self_: *mut cef_panel_t)>,
+    pub add_child_view: Option<extern "C" fn(self_: *mut cef_panel_t, view: *mut cef_view_t)>,
+    pub add_child_view_at: Option<extern "C" fn(self_: *mut cef_panel_t, view: *mut cef_view_t, index: i32)>,
+    pub reorder_child_view: Option<extern "C" fn(self_: *mut cef_panel_t, view: *mut cef_view_t, index: i32)>,
+    pub remove_child_view: Option<extern "C" fn(self_: *mut cef_panel_t, view: *mut cef_view_t)>,
+    pub remove_all_child_views: Option<extern "C" fn(self_: *mut cef_panel_t)>,
+    pub get_child_view_count: Option<extern "C" fn(self_: *mut cef_panel_t) -> usize>,
+    pub get_child_view_at: Option<extern "C" fn(self_: *mut cef_panel_t, index: i32) -> *mut cef_view_t>,
+}
+
+// views/cef_window_capi.h
+
+#[repr(C)]
+pub struct cef_window_t {
+    pub base: cef_panel_t,
+    pub show: Option<extern "C" fn(self_: *mut cef_window_t)>,
+    pub hide: Option<extern "C" fn(self_: *mut cef_window_t)>,
+    pub center_window: Option<extern "C" fn(self_: *mut cef_window_t, size: *const cef_size_t)>,
+    pub close: Option<extern "C" fn(self_: *mut cef_window_t)>,
+    pub is_closed: Option<extern "C" fn(self_: *mut cef_window_t) -> i32>,
+    pub activate: Option<extern "C" fn(self_: *mut cef_window_t)>,
+    pub deactivate: Option<extern "C" fn(self_: *mut cef_window_t)>,
+    pub is_active: Option<extern "C" fn(self_: *mut cef_window_t) -> i32>,
+    pub bring_to_top: Option<extern "C" fn(self_: *mut cef_window_t)>,
+    pub set_always_on_top: Option<extern "C" fn(self_: *mut cef_window_t, on_top: i32)>,
+    pub is_always_on_top: Option<extern "C" fn(self_: *mut cef_window_t) -> i32>,
+    pub maximize: Option<extern "C" fn(self_: *mut cef_window_t)>,
+    pub minimize: Option<extern "C" fn(self_: *mut cef_window_t)>,
+    pub restore: Option<extern "C" fn(self_: *mut cef_window_t)>,
+    pub set_fullscreen: Option<extern "C" fn(self_: *mut cef_window_t, fullscreen: i32)>,
+    pub is_maximized: Option<extern "C" fn(self_: *mut cef_window_t) -> i32>,
+    pub is_minimized: Option<extern "C" fn(self_: *mut cef_window_t) -> i32>,
+    pub is_fullscreen: Option<extern "C" fn(self_: *mut cef_window_t) -> i32>,
+    pub set_title: Option<extern "C" fn(self_: *mut cef_window_t, title: *const cef_string_t)>,
+    pub get_title: Option<extern "C" fn(self_: *mut cef_window_t) -> cef_string_userfree_t>,
+    pub set_window_icon: Option<extern "C" fn(self_: *mut cef_window_t, image: *mut cef_image_t)>,
+    pub get_window_icon: Option<extern "C" fn(self_: *mut cef_window_t) -> *mut cef_image_t>,
+    pub set_window_app_icon: Option<extern "C" fn(self_: *mut cef_window_t, image: *mut cef_image_t)>,
+    pub get_window_app_icon: Option<extern "C" fn(self_: *mut cef_window_t) -> *mut cef_image_t>,
+    pub show_menu: Option<extern "C" fn(self_: *mut cef_window_t, menu_model: *mut cef_menu_model_t,
+        screen_point: *const cef_point_t, anchor_position: cef_menu_anchor_position_t)>,
+    pub cancel_menu: Option<extern "C" fn(self_: *mut cef_window_t)>,
+    pub get_display: Option<extern "C" fn(self_: *mut cef_window_t) -> *mut cef_display_t>,
+    pub get_client_area_bounds_in_screen: Option<extern "C" fn(self_: *mut cef_window_t) -> cef_rect_t>,
+    pub set_draggable_regions: Option<extern "C" fn(self_: *mut cef_window_t, regions_count: usize,
+        regions: *const cef_draggable_region_t)>,
+    pub get_window_handle: Option<extern "C" fn(self_: *mut cef_window_t) -> cef_window_handle_t>,
+    pub send_key_press: Option<extern "C" fn(self_: *mut cef_window_t, key_code: i32, event_flags: u32)>,
+    pub send_mouse_move: Option<extern "C" fn(self_: *mut cef_window_t, screen_x: i32, screen_y: i32)>,
+    pub send_mouse_events: Option<extern "C" fn(self_: *mut cef_window_t, button: cef_mouse_button_type_t,
+        mouse_down: i32, mouse_up: i32)>,
+    pub set_accelerator: Option<extern "C" fn(self_: *mut cef_window_t, command_id: i32, key_code: i32,
+        shift_pressed: i32, ctrl_pressed: i32, alt_pressed: i32)>,
+    pub remove_accelerator: Option<extern "C" fn(self_: *mut cef_window_t, command_id: i32)>,
+    pub remove_all_accelerators: Option<extern "C" fn(self_: *mut cef_window_t)>,
+}
+
+// In the tests, these functions are exported by the mock module instead of libcef.
+#[cfg_attr(not(test), link(name = "cef"))]
+extern "C" {
+    // cef_app_capi.h
+    pub fn cef_execute_process(args: *const cef_main_args_t, application: *mut cef_app_t,
+        windows_sandbox_info: *mut c_void) -> i32;
+    pub fn cef_initialize(args: *const cef_main_args_t, settings: *const cef_settings_t, application: *mut cef_app_t,
+        windows_sandbox_info: *mut c_void) -> i32;
+    pub fn cef_shutdown();
+    pub fn cef_do_message_loop_work();
+    pub fn cef_run_message_loop();
+    pub fn cef_quit_message_loop();
+    pub fn cef_set_osmodal_loop(os_modal_loop: i32);
+    pub fn cef_enable_highdpi_support();
+
+    // cef_browser_capi.h
+    pub fn cef_browser_host_create_browser(window_info: *const cef_window_info_t, client: *mut cef_client_t,
+        url: *const cef_string_t, settings: *const cef_browser_settings_t,
+        request_context: *mut cef_request_context_t) -> i32;
+    pub fn cef_browser_host_create_browser_sync(window_info: *const cef_window_info_t, client: *mut cef_client_t,
+        url: *const cef_string_t, settings: *const cef_browser_settings_t,
+        request_context: *mut cef_request_context_t) -> *mut cef_browser_t;
+
+    // cef_command_line_capi.h
+    pub fn cef_command_line_create() -> *mut cef_command_line_t;
+    pub fn cef_command_line_get_global() -> *mut cef_command_line_t;
+
+    // cef_process_message_capi.h
+    pub fn cef_process_message_create(name: *const cef_string_t) -> *mut cef_process_message_t;
+
+    // cef_task_capi.h
+    pub fn cef_currently_on(thread_id: cef_thread_id_t) -> i32;
+    pub fn cef_post_task(thread_id: cef_thread_id_t, task: *mut cef_task_t) -> i32;
+    pub fn cef_post_delayed_task(thread_id: cef_thread_id_t, task: *mut cef_task_t, delay_ms: i64) -> i32;
+
+    // cef_values_capi.h
+    pub fn cef_list_value_create() -> *mut cef_list_value_t;
+
+    // internal/cef_string.h
+    pub fn cef_string_wide_set(src: *const i32, src_len: usize, output: *mut cef_string_wide_t, copy: i32) -> i32;
+    pub fn cef_string_utf8_set(src: *const c_char, src_len: usize, output: *mut cef_string_utf8_t, copy: i32)
+        -> i32;
+    pub fn cef_string_utf16_set(src: *const char16, src_len: usize, output: *mut cef_string_utf16_t, copy: i32)
+        -> i32;
+    pub fn cef_string_wide_clear(str: *mut cef_string_wide_t);
+    pub fn cef_string_utf8_clear(str: *mut cef_string_utf8_t);
+    pub fn cef_string_utf16_clear(str: *mut cef_string_utf16_t);
+    pub fn cef_string_utf8_to_utf16(src: *const c_char, src_len: usize, output: *mut cef_string_utf16_t) -> i32;
+    pub fn cef_string_utf16_to_utf8(src: *const char16, src_len: usize, output: *mut cef_string_utf8_t) -> i32;
+    pub fn cef_string_userfree_wide_alloc() -> cef_string_userfree_wide_t;
+    pub fn cef_string_userfree_utf8_alloc() -> cef_string_userfree_utf8_t;
+    pub fn cef_string_userfree_utf16_alloc() -> cef_string_userfree_utf16_t;
+    pub fn cef_string_userfree_wide_free(str: cef_string_userfree_wide_t);
+    pub fn cef_string_userfree_utf8_free(str: cef_string_userfree_utf8_t);
+    pub fn cef_string_userfree_utf16_free(str: cef_string_userfree_utf16_t);
+
+    // internal/cef_string_list.h
+    pub fn cef_string_list_alloc() -> cef_string_list_t;
+    pub fn cef_string_list_size(list: cef_string_list_t) -> usize;
+    pub fn cef_string_list_value(list: cef_string_list_t, index: usize, value: *mut cef_string_t) -> i32;
+    pub fn cef_string_list_append(list: cef_string_list_t, value: *const cef_string_t);
+    pub fn cef_string_list_clear(list: cef_string_list_t);
+    pub fn cef_string_list_free(list: cef_string_list_t);
+    pub fn cef_string_list_copy(list: cef_string_list_t) -> cef_string_list_t;
+
+    // internal/cef_string_map.h
+    pub fn cef_string_map_alloc() -> cef_string_map_t;
+    pub fn cef_string_map_size(map: cef_string_map_t) -> usize;
+    pub fn cef_string_map_find(map: cef_string_map_t, key: *const cef_string_t, value: *mut cef_string_t) -> i32;
+    pub fn cef_string_map_key(map: cef_string_map_t, index: usize, key: *mut cef_string_t) -> i32;
+    pub fn cef_string_map_value(map: cef_string_map_t, index: usize, value: *mut cef_string_t) -> i32;
+    pub fn cef_string_map_append(map: cef_string_map_t, key: *const cef_string_t, value: *const cef_string_t)
+        -> i32;
+    pub fn cef_string_map_clear(map: cef_string_map_t);
+    pub fn cef_string_map_free(map: cef_string_map_t);
+
+    // views/cef_browser_view_capi.h
+    pub fn cef_browser_view_create(client: *mut cef_client_t, url: *const cef_string_t,
+        settings: *const cef_browser_settings_t, request_context: *mut cef_request_context_t,
+        delegate: *mut cef_browser_view_delegate_t) -> *mut cef_browser_view_t;
+    pub fn cef_browser_view_get_for_browser(browser: *mut cef_browser_t) -> *mut cef_browser_view_t;
+
+    // views/cef_window_capi.h
+    pub fn cef_window_create_top_level(delegate: *mut cef_window_delegate_t) -> *mut cef_window_t;
+}
@@ -0,0 +1,31 @@
+use std::mem;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use rc::RefCounted;
+use sys::*;
+
+type TaskRc = RefCounted<cef_task_t, Mutex<Option<Box<dyn FnOnce() + Send>>>>;
+
+fn wrap_task<F: FnOnce() + Send + 'static>(task: F) -> *mut cef_task_t {
+    extern "C" fn execute(self_: *mut cef_task_t) {
+        let task = unsafe { TaskRc::value(self_) }.lock()
+            .ok()
+            .and_then(|mut task| task.take());
+        if let Some(task) = task {
+            task();
+        }
+    }
+
+    let mut raw: cef_task_t = unsafe { mem::zeroed() };
+    raw.execute = Some(execute);
+    RefCounted::wrap(raw, Mutex::new(Some(Box::new(task) as Box<dyn FnOnce() + Send>)))
+}
+
+/// Run `task` on `thread` after `delay`. Returns false if CEF is not running, in which case `task` is dropped.
+pub(crate) fn post_delayed_task<F: FnOnce() + Send + 'static>(thread: cef_thread_id_t, delay: Duration, task: F)
+    -> bool
+{
+    let delay_ms = delay.as_millis().min(i64::MAX as u128) as i64;
+    unsafe { cef_post_delayed_task(thread, wrap_task(task), delay_ms) != 0 }
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+
+use sys::*;
+
+/// How a navigation was started.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TransitionSource {
+    /// A click on a link, or a navigation started by JavaScript.
+    Link,
+    /// Typed in the URL bar or requested by the application.
+    Explicit,
+    /// A subframe loaded automatically with its parent.
+    AutoSubframe,
+    /// A subframe navigated by the user.
+    ManualSubframe,
+    FormSubmit,
+    Reload,
+    /// A source not known by this version of the bindings.
+    Other(u8),
+}
+
+bitflags! {
+    pub struct TransitionQualifiers: cef_transition_type_t {
+        /// The navigation was blocked, for instance by a sandboxed frame.
+        const BLOCKED = TT_BLOCKED_FLAG;
+        /// Started by the back or forward buttons.
+        const FORWARD_BACK = TT_FORWARD_BACK_FLAG;
+        /// The first navigation of a chain of redirects.
+        const CHAIN_START = TT_CHAIN_START_FLAG;
+        /// The last navigation of a chain of redirects.
+        const CHAIN_END = TT_CHAIN_END_FLAG;
+        /// Redirected by JavaScript or a meta refresh.
+        const CLIENT_REDIRECT = TT_CLIENT_REDIRECT_FLAG;
+        /// Redirected by the HTTP headers of the server.
+        const SERVER_REDIRECT = TT_SERVER_REDIRECT_FLAG;
+    }
+}
+
+impl TransitionQualifiers {
+    pub fn is_redirect(&self) -> bool {
+        self.intersects(TransitionQualifiers::CLIENT_REDIRECT | TransitionQualifiers::SERVER_REDIRECT)
+    }
+}
+
+/// A `cef_transition_type_t`: a source combined with qualifiers.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TransitionType(cef_transition_type_t);
+
+impl TransitionType {
+    pub fn from_raw(transition_type: cef_transition_type_t) -> Self {
+        TransitionType(transition_type)
+    }
+
+    pub fn to_raw(self) -> cef_transition_type_t {
+        self.0
+    }
+
+    pub fn source(self) -> TransitionSource {
+        match self.0 & TT_SOURCE_MASK {
+            TT_LINK => TransitionSource::Link,
+            TT_EXPLICIT => TransitionSource::Explicit,
+            TT_AUTO_SUBFRAME => TransitionSource::AutoSubframe,
+            TT_MANUAL_SUBFRAME => TransitionSource::ManualSubframe,
+            TT_FORM_SUBMIT => TransitionSource::FormSubmit,
+            TT_RELOAD => TransitionSource::Reload,
+            source => TransitionSource::Other(source as u8),
+        }
+    }
+
+    /// The unknown qualifier bits are dropped, but are kept by `to_raw`.
+    pub fn qualifiers(self) -> TransitionQualifiers {
+        TransitionQualifiers::from_bits_truncate(self.0 & TT_QUALIFIER_MASK)
+    }
+
+    pub fn is_reload(self) -> bool {
+        self.source() == TransitionSource::Reload
+    }
+
+    pub fn is_redirect(self) -> bool {
+        self.0 & TT_IS_REDIRECT_MASK != 0
+    }
+}
+
+impl fmt::Debug for TransitionType {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_struct("TransitionType")
+            .field("source", &self.source())
+            .field("qualifiers", &self.qualifiers())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transition_type() {
+        let transition_type = TransitionType::from_raw(TT_LINK | TT_FORWARD_BACK_FLAG | TT_SERVER_REDIRECT_FLAG);
+        assert_eq!(transition_type.source(), TransitionSource::Link);
+        assert_eq!(transition_type.qualifiers(),
+            TransitionQualifiers::FORWARD_BACK | TransitionQualifiers::SERVER_REDIRECT);
+        assert!(transition_type.is_redirect());
+        assert!(!transition_type.is_reload());
+        assert!(TransitionType::from_raw(TT_RELOAD).is_reload());
+    }
+
+    #[test]
+    fn unknown_bits() {
+        let raw = 42 | 0x0200_0000 | TT_CHAIN_END_FLAG;
+        let transition_type = TransitionType::from_raw(raw);
+        assert_eq!(transition_type.source(), TransitionSource::Other(42));
+        assert_eq!(transition_type.qualifiers(), TransitionQualifiers::CHAIN_END);
+        assert_eq!(transition_type.to_raw(), raw);
+    }
+}
@@ -0,0 +1,45 @@
+use std::ptr;
+
+use rc::{CefRc, release_base};
+use string::CefString;
+use sys::*;
+
+/// The JavaScript context of a frame, only available in a render process, on its render thread.
+#[repr(transparent)]
+pub(crate) struct V8Context(cef_v8context_t);
+
+cef_object!(V8Context, cef_v8context_t);
+
+impl V8Context {
+    /// Run `code` in the context, and return its value, or `None` if it throws an exception. `script_url` is used
+    /// in the error messages.
+    pub fn eval(&self, code: &str, script_url: &str) -> Option<CefRc<V8Value>> {
+        let code = CefString::new(code);
+        let script_url = CefString::new(script_url);
+        let mut value = ptr::null_mut();
+        let mut exception = ptr::null_mut();
+        call!(&self.0, eval(code.as_raw(), script_url.as_raw(), 0, &mut value, &mut exception));
+        if !exception.is_null() {
+            unsafe { release_base(exception as *mut cef_base_ref_counted_t) };
+        }
+        unsafe { CefRc::from_raw(value) }
+    }
+}
+
+/// A JavaScript value, which these bindings only read as a number.
+#[repr(transparent)]
+pub(crate) struct V8Value(cef_v8value_t);
+
+cef_object!(V8Value, cef_v8value_t);
+
+impl V8Value {
+    /// The value of an integer which fits in an `i32`.
+    pub fn int_value(&self) -> Option<i32> {
+        if call!(&self.0, is_int()) != 0 {
+            Some(call!(&self.0, get_int_value()))
+        }
+        else {
+            None
+        }
+    }
+}
@@ -0,0 +1,280 @@
+/*
+ * Wrappers for the views framework, which CEF uses to create its own top-level windows.
+ */
+
+use std::ops::Deref;
+
+use browser::Browser;
+use rc::{CefObject, CefRc};
+use string::CefString;
+use sys::{
+    cef_browser_view_t,
+    cef_panel_t,
+    cef_view_t,
+    cef_window_t,
+};
+
+// The C struct of a view starts with the C struct of its parent view.
+macro_rules! view_parent {
+    ($name:ident, $parent:ident) => {
+        impl Deref for $name {
+            type Target = $parent;
+
+            fn deref(&self) -> &$parent {
+                unsafe { &*(self as *const $name as *const $parent) }
+            }
+        }
+    };
+}
+
+#[repr(transparent)]
+pub struct View(cef_view_t);
+
+cef_object!(View, cef_view_t);
+
+impl View {
+    pub fn as_browser_view(&self) -> Option<CefRc<BrowserView>> {
+        unsafe { CefRc::from_raw(call!(&self.0, as_browser_view())) }
+    }
+
+    pub fn as_panel(&self) -> Option<CefRc<Panel>> {
+        unsafe { CefRc::from_raw(call!(&self.0, as_panel())) }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        call!(&self.0, is_valid()) != 0
+    }
+
+    pub fn is_attached(&self) -> bool {
+        call!(&self.0, is_attached()) != 0
+    }
+
+    pub fn window(&self) -> Option<CefRc<Window>> {
+        unsafe { CefRc::from_raw(call!(&self.0, get_window())) }
+    }
+
+    pub fn id(&self) -> i32 {
+        call!(&self.0, get_id())
+    }
+
+    pub fn set_id(&self, id: i32) {
+        call!(&self.0, set_id(id))
+    }
+
+    pub fn parent_view(&self) -> Option<CefRc<View>> {
+        unsafe { CefRc::from_raw(call!(&self.0, get_parent_view())) }
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        call!(&self.0, set_visible(visible as i32))
+    }
+
+    pub fn is_visible(&self) -> bool {
+        call!(&self.0, is_visible()) != 0
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        call!(&self.0, set_enabled(enabled as i32))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        call!(&self.0, is_enabled()) != 0
+    }
+
+    pub fn request_focus(&self) {
+        call!(&self.0, request_focus())
+    }
+}
+
+/// A view hosting a browser.
+#[repr(transparent)]
+pub struct BrowserView(cef_browser_view_t);
+
+cef_object!(BrowserView, cef_browser_view_t);
+view_parent!(BrowserView, View);
+
+impl BrowserView {
+    /// Returns `None` until the browser is created, which happens when the view is added to a window.
+    pub fn browser(&self) -> Option<CefRc<Browser>> {
+        unsafe { CefRc::from_raw(call!(&self.0, get_browser())) }
+    }
+
+    /// Handle the keyboard accelerators of the window before the page.
+    pub fn set_prefer_accelerators(&self, prefer_accelerators: bool) {
+        call!(&self.0, set_prefer_accelerators(prefer_accelerators as i32))
+    }
+}
+
+/// A view containing other views.
+#[repr(transparent)]
+pub struct Panel(cef_panel_t);
+
+cef_object!(Panel, cef_panel_t);
+view_parent!(Panel, View);
+
+impl Panel {
+    pub fn as_window(&self) -> Option<CefRc<Window>> {
+        unsafe { CefRc::from_raw(call!(&self.0, as_window())) }
+    }
+
+    pub fn layout(&self) {
+        call!(&self.0, layout())
+    }
+
+    pub fn add_child_view(&self, view: &View) {
+        call!(&self.0, add_child_view(view.to_raw()))
+    }
+
+    pub fn add_child_view_at(&self, view: &View, index: i32) {
+        call!(&self.0, add_child_view_at(view.to_raw(), index))
+    }
+
+    pub fn remove_child_view(&self, view: &View) {
+        call!(&self.0, remove_child_view(view.to_raw()))
+    }
+
+    pub fn remove_all_child_views(&self) {
+        call!(&self.0, remove_all_child_views())
+    }
+
+    pub fn child_view_count(&self) -> usize {
+        call!(&self.0, get_child_view_count())
+    }
+
+    pub fn child_view_at(&self, index: i32) -> Option<CefRc<View>> {
+        unsafe { CefRc::from_raw(call!(&self.0, get_child_view_at(index))) }
+    }
+}
+
+/// A top-level window.
+#[repr(transparent)]
+pub struct Window(cef_window_t);
+
+cef_object!(Window, cef_window_t);
+view_parent!(Window, Panel);
+
+impl Window {
+    pub fn show(&self) {
+        call!(&self.0, show())
+    }
+
+    pub fn hide(&self) {
+        call!(&self.0, hide())
+    }
+
+    pub fn close(&self) {
+        call!(&self.0, close())
+    }
+
+    pub fn is_closed(&self) -> bool {
+        call!(&self.0, is_closed()) != 0
+    }
+
+    pub fn activate(&self) {
+        call!(&self.0, activate())
+    }
+
+    pub fn deactivate(&self) {
+        call!(&self.0, deactivate())
+    }
+
+    pub fn is_active(&self) -> bool {
+        call!(&self.0, is_active()) != 0
+    }
+
+    pub fn bring_to_top(&self) {
+        call!(&self.0, bring_to_top())
+    }
+
+    pub fn set_always_on_top(&self, on_top: bool) {
+        call!(&self.0, set_always_on_top(on_top as i32))
+    }
+
+    pub fn maximize(&self) {
+        call!(&self.0, maximize())
+    }
+
+    pub fn minimize(&self) {
+        call!(&self.0, minimize())
+    }
+
+    pub fn restore(&self) {
+        call!(&self.0, restore())
+    }
+
+    pub fn set_fullscreen(&self, fullscreen: bool) {
+        call!(&self.0, set_fullscreen(fullscreen as i32))
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        call!(&self.0, is_fullscreen()) != 0
+    }
+
+    pub fn set_title(&self, title: &str) {
+        let title = CefString::new(title);
+        call!(&self.0, set_title(title.as_raw()))
+    }
+
+    pub fn title(&self) -> String {
+        unsafe { CefString::from_userfree(call!(&self.0, get_title())) }.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use browser_settings::BrowserSettings;
+    use client::wrap_client;
+    use mock::{self, TestClient};
+    use sys::{cef_browser_view_create, cef_window_create_top_level};
+    use super::*;
+
+    #[test]
+    fn window() {
+        let _guard = mock::lock();
+        let cef = mock::start();
+        let url = CefString::new("https://example.com");
+        let (window, browser_view) = unsafe {
+            let browser_view = CefRc::<BrowserView>::from_raw(cef_browser_view_create(wrap_client(TestClient::new()),
+                url.as_raw(), BrowserSettings::default().as_raw(), ptr::null_mut(), ptr::null_mut()));
+            let window = CefRc::<Window>::from_raw(cef_window_create_top_level(ptr::null_mut()));
+            (window.expect("window"), browser_view.expect("browser view"))
+        };
+        assert_eq!(mock::window_count(), 1);
+        assert!(!browser_view.is_attached());
+        assert!(browser_view.browser().is_none());
+
+        window.add_child_view(&browser_view);
+        window.set_title("Title");
+        window.show();
+        browser_view.set_id(7);
+        assert_eq!(window.title(), "Title");
+        assert!(window.is_visible() && !window.is_closed());
+        assert_eq!(window.child_view_count(), 1);
+        let child = window.child_view_at(0).expect("child view");
+        assert_eq!(child.id(), 7);
+        assert!(child.as_panel().is_none());
+        assert!(child.as_browser_view().is_some());
+        assert!(window.child_view_at(1).is_none());
+        assert!(browser_view.window().is_some());
+        assert!(browser_view.parent_view().and_then(|parent| parent.as_panel()).and_then(|panel| panel.as_window())
+            .is_some());
+        let browser = browser_view.browser().expect("browser");
+        assert_eq!(mock::browser_ids(), vec![browser.identifier()]);
+
+        window.close();
+        mock::run_pending_tasks();
+        assert!(window.is_closed());
+        assert_eq!(mock::window_count(), 0);
+        assert_eq!(window.child_view_count(), 0);
+        assert!(!browser_view.is_attached());
+        assert_eq!(mock::browser_ids(), vec![]);
+        drop(browser);
+        drop(child);
+        drop(browser_view);
+        drop(window);
+        drop(cef);
+        assert_eq!(mock::live_objects(), 0);
+    }
+}
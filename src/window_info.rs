@@ -0,0 +1,67 @@
+use sys::{cef_window_handle_t, cef_window_info_t};
+
+/// Where a browser is shown: in a child window of a native window, or off-screen.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WindowInfo {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// 0 creates a top-level window, unless the rendering is windowless.
+    pub parent_window: cef_window_handle_t,
+    /// The pages are rendered by the `RenderHandler` of the client instead of a window.
+    pub windowless_rendering_enabled: bool,
+    pub shared_texture_enabled: bool,
+    pub external_begin_frame_enabled: bool,
+}
+
+impl WindowInfo {
+    /// A child window filling `bounds` (x, y, width, height) in `parent_window`.
+    pub fn child(parent_window: cef_window_handle_t, bounds: (u32, u32, u32, u32)) -> Self {
+        let (x, y, width, height) = bounds;
+        WindowInfo {
+            x,
+            y,
+            width,
+            height,
+            parent_window,
+            ..WindowInfo::default()
+        }
+    }
+
+    /// `parent_window` is used to position the dialogs and the context menus, and can be 0.
+    pub fn windowless(parent_window: cef_window_handle_t) -> Self {
+        WindowInfo {
+            parent_window,
+            windowless_rendering_enabled: true,
+            ..WindowInfo::default()
+        }
+    }
+
+    pub fn from_raw(raw: &cef_window_info_t) -> Self {
+        WindowInfo {
+            x: raw.x,
+            y: raw.y,
+            width: raw.width,
+            height: raw.height,
+            parent_window: raw.parent_window,
+            windowless_rendering_enabled: raw.windowless_rendering_enabled != 0,
+            shared_texture_enabled: raw.shared_texture_enabled != 0,
+            external_begin_frame_enabled: raw.external_begin_frame_enabled != 0,
+        }
+    }
+
+    pub fn to_raw(&self) -> cef_window_info_t {
+        cef_window_info_t {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+            parent_window: self.parent_window,
+            windowless_rendering_enabled: self.windowless_rendering_enabled as i32,
+            shared_texture_enabled: self.shared_texture_enabled as i32,
+            external_begin_frame_enabled: self.external_begin_frame_enabled as i32,
+            window: 0,
+        }
+    }
+}